@@ -0,0 +1,181 @@
+#![feature(test)]
+
+//! Benchmarks for the small methods on flags values that are expected to be inlined
+//! into downstream code, like `bits`, `contains`, and `intersects`.
+//!
+//! Each benchmark runs the method over a slice of values from outside of the `bitflags` crate,
+//! like a downstream consumer would. If these methods weren't inlined then they'd be noticeably
+//! slower than the equivalent operations on the raw bits in `raw_*`.
+
+extern crate test;
+
+use test::{black_box, Bencher};
+
+bitflags::bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Flags: u32 {
+        const A = 1;
+        const B = 1 << 1;
+        const C = 1 << 2;
+        const D = 1 << 3;
+        const AB = Self::A.bits() | Self::B.bits();
+    }
+}
+
+fn values() -> Vec<Flags> {
+    (0..1024)
+        .map(|i| Flags::from_bits_retain(i as u32 & 0b1111))
+        .collect()
+}
+
+fn raw_values() -> Vec<u32> {
+    values().iter().map(|flags| flags.bits()).collect()
+}
+
+#[bench]
+fn bits(b: &mut Bencher) {
+    let values = values();
+
+    b.iter(|| {
+        black_box(&values)
+            .iter()
+            .fold(0, |acc, flags| acc ^ flags.bits())
+    })
+}
+
+#[bench]
+fn contains(b: &mut Bencher) {
+    let values = values();
+
+    b.iter(|| {
+        let other = black_box(Flags::AB);
+
+        black_box(&values)
+            .iter()
+            .filter(|flags| flags.contains(other))
+            .count()
+    })
+}
+
+#[bench]
+fn intersects(b: &mut Bencher) {
+    let values = values();
+
+    b.iter(|| {
+        let other = black_box(Flags::AB);
+
+        black_box(&values)
+            .iter()
+            .filter(|flags| flags.intersects(other))
+            .count()
+    })
+}
+
+#[bench]
+fn insert(b: &mut Bencher) {
+    let values = values();
+
+    b.iter(|| {
+        black_box(&values)
+            .iter()
+            .fold(Flags::empty(), |mut acc, flags| {
+                acc.insert(*flags);
+                acc
+            })
+    })
+}
+
+#[bench]
+fn difference(b: &mut Bencher) {
+    let values = values();
+
+    b.iter(|| {
+        let other = black_box(Flags::AB);
+
+        black_box(&values)
+            .iter()
+            .fold(0, |acc, flags| acc ^ flags.difference(other).bits())
+    })
+}
+
+// A union of constants should fold to a constant, so this should be as fast as `raw_union_consts`
+#[bench]
+fn union_consts(b: &mut Bencher) {
+    let values = values();
+
+    b.iter(|| {
+        black_box(&values)
+            .iter()
+            .filter(|flags| flags.contains(Flags::A | Flags::C | Flags::D))
+            .count()
+    })
+}
+
+#[bench]
+fn raw_bits(b: &mut Bencher) {
+    let values = raw_values();
+
+    b.iter(|| black_box(&values).iter().fold(0, |acc, bits| acc ^ bits))
+}
+
+#[bench]
+fn raw_contains(b: &mut Bencher) {
+    let values = raw_values();
+
+    b.iter(|| {
+        let other = black_box(Flags::AB.bits());
+
+        black_box(&values)
+            .iter()
+            .filter(|bits| *bits & other == other)
+            .count()
+    })
+}
+
+#[bench]
+fn raw_intersects(b: &mut Bencher) {
+    let values = raw_values();
+
+    b.iter(|| {
+        let other = black_box(Flags::AB.bits());
+
+        black_box(&values)
+            .iter()
+            .filter(|bits| *bits & other != 0)
+            .count()
+    })
+}
+
+#[bench]
+fn raw_insert(b: &mut Bencher) {
+    let values = raw_values();
+
+    b.iter(|| black_box(&values).iter().fold(0, |acc, bits| acc | bits))
+}
+
+#[bench]
+fn raw_difference(b: &mut Bencher) {
+    let values = raw_values();
+
+    b.iter(|| {
+        let other = black_box(Flags::AB.bits());
+
+        black_box(&values)
+            .iter()
+            .fold(0, |acc, bits| acc ^ (bits & !other))
+    })
+}
+
+#[bench]
+fn raw_union_consts(b: &mut Bencher) {
+    let values = raw_values();
+
+    b.iter(|| {
+        const OTHER: u32 = 1 | 1 << 2 | 1 << 3;
+
+        black_box(&values)
+            .iter()
+            .filter(|bits| *bits & OTHER == OTHER)
+            .count()
+    })
+}
@@ -13,6 +13,9 @@ impl Bits for CustomBits {
     const EMPTY: Self = CustomBits([false; 3]);
 
     const ALL: Self = CustomBits([true; 3]);
+
+    // Each `bool` is a single bit, rather than the 8 bits in its memory
+    const BITS: u32 = 3;
 }
 
 impl PartialEq for CustomBits {
@@ -0,0 +1,75 @@
+/*!
+Errors for fallible operations on flags values.
+*/
+
+use core::fmt;
+
+use crate::parser::WriteHex;
+
+/**
+An error returned when removing flags that aren't all set in a flags value.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotPresent(());
+
+impl NotPresent {
+    /// The flags to remove weren't all set.
+    pub const fn new() -> Self {
+        NotPresent(())
+    }
+}
+
+impl Default for NotPresent {
+    fn default() -> Self {
+        NotPresent::new()
+    }
+}
+
+impl fmt::Display for NotPresent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the flags to remove aren't all set")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NotPresent {}
+#[cfg(all(feature = "core_error", not(feature = "std")))]
+impl core::error::Error for NotPresent {}
+
+/**
+An error returned when a flags value contains bits that don't correspond to any defined flag.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownBits<B> {
+    bits: B,
+}
+
+impl<B> UnknownBits<B> {
+    /// The flags value contained the given unknown bits.
+    pub const fn new(bits: B) -> Self {
+        UnknownBits { bits }
+    }
+
+    /// Get the unknown bits the flags value contained.
+    pub fn bits(&self) -> &B {
+        &self.bits
+    }
+
+    /// Get the unknown bits the flags value contained.
+    pub fn into_bits(self) -> B {
+        self.bits
+    }
+}
+
+impl<B: WriteHex> fmt::Display for UnknownBits<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the flags value contains unknown bits `0x")?;
+        self.bits.write_hex(&mut *f)?;
+        write!(f, "`")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<B: WriteHex + fmt::Debug> std::error::Error for UnknownBits<B> {}
+#[cfg(all(feature = "core_error", not(feature = "std")))]
+impl<B: WriteHex + fmt::Debug> core::error::Error for UnknownBits<B> {}
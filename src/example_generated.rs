@@ -42,11 +42,16 @@ __impl_public_bitflags_ops! {
     Flags
 }
 
+__impl_public_bitflags_bits_extend! {
+    Flags: u32
+}
+
 __impl_public_bitflags_iter! {
     Flags: u32, Flags
 }
 
 __impl_public_bitflags_consts! {
+    options: [],
     Flags: u32 {
         /// Field `A`.
         ///
@@ -74,6 +74,15 @@ pub(crate) mod __private {
 
     #[cfg(feature = "bytemuck")]
     pub use bytemuck;
+
+    #[cfg(feature = "schemars")]
+    pub use schemars;
+
+    #[cfg(feature = "schemars")]
+    pub use alloc::borrow::Cow;
+
+    #[cfg(feature = "subtle")]
+    pub use subtle;
 }
 
 /// Implements traits from external libraries for the internal bitflags type.
@@ -118,6 +127,24 @@ macro_rules! __impl_external_bitflags {
                 )*
             }
         }
+
+        $crate::__impl_external_bitflags_schemars! {
+            $InternalBitFlags: $T, $PublicBitFlags {
+                $(
+                    $(#[$inner $($args)*])*
+                    const $Flag;
+                )*
+            }
+        }
+
+        $crate::__impl_external_bitflags_subtle! {
+            $InternalBitFlags: $T, $PublicBitFlags {
+                $(
+                    $(#[$inner $($args)*])*
+                    const $Flag;
+                )*
+            }
+        }
     };
 }
 
@@ -260,3 +287,105 @@ macro_rules! __impl_external_bitflags_bytemuck {
         }
     ) => {};
 }
+
+#[cfg(feature = "schemars")]
+pub mod schemars;
+
+/// Implement `JsonSchema` for the internal bitflags type.
+#[macro_export]
+#[doc(hidden)]
+#[cfg(feature = "schemars")]
+macro_rules! __impl_external_bitflags_schemars {
+    (
+        $InternalBitFlags:ident: $T:ty, $PublicBitFlags:ident {
+            $(
+                $(#[$inner:ident $($args:tt)*])*
+                const $Flag:tt;
+            )*
+        }
+    ) => {
+        impl $crate::__private::schemars::JsonSchema for $InternalBitFlags {
+            fn schema_name() -> $crate::__private::Cow<'static, str> {
+                $crate::__private::Cow::Borrowed($crate::__private::core::stringify!(
+                    $PublicBitFlags
+                ))
+            }
+
+            fn schema_id() -> $crate::__private::Cow<'static, str> {
+                $crate::__private::Cow::Borrowed($crate::__private::core::concat!(
+                    $crate::__private::core::module_path!(),
+                    "::",
+                    $crate::__private::core::stringify!($PublicBitFlags)
+                ))
+            }
+
+            fn json_schema(
+                generator: &mut $crate::__private::schemars::SchemaGenerator,
+            ) -> $crate::__private::schemars::Schema {
+                $crate::schemars::json_schema::<$PublicBitFlags>(generator)
+            }
+        }
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+#[cfg(not(feature = "schemars"))]
+macro_rules! __impl_external_bitflags_schemars {
+    (
+        $InternalBitFlags:ident: $T:ty, $PublicBitFlags:ident {
+            $(
+                $(#[$inner:ident $($args:tt)*])*
+                const $Flag:tt;
+            )*
+        }
+    ) => {};
+}
+
+#[cfg(feature = "clap")]
+pub mod clap;
+
+#[cfg(feature = "subtle")]
+mod subtle;
+
+/// Implement `ConstantTimeEq` for the public bitflags type.
+///
+/// `subtle` doesn't have a derive for `ConstantTimeEq`, so unlike other libraries,
+/// it's implemented directly on the public type rather than the internal one.
+#[macro_export]
+#[doc(hidden)]
+#[cfg(feature = "subtle")]
+macro_rules! __impl_external_bitflags_subtle {
+    (
+        $InternalBitFlags:ident: $T:ty, $PublicBitFlags:ident {
+            $(
+                $(#[$inner:ident $($args:tt)*])*
+                const $Flag:tt;
+            )*
+        }
+    ) => {
+        impl $crate::__private::subtle::ConstantTimeEq for $PublicBitFlags {
+            #[inline]
+            fn ct_eq(&self, other: &Self) -> $crate::__private::subtle::Choice {
+                <$T as $crate::__private::subtle::ConstantTimeEq>::ct_eq(
+                    &self.bits(),
+                    &other.bits(),
+                )
+            }
+        }
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+#[cfg(not(feature = "subtle"))]
+macro_rules! __impl_external_bitflags_subtle {
+    (
+        $InternalBitFlags:ident: $T:ty, $PublicBitFlags:ident {
+            $(
+                $(#[$inner:ident $($args:tt)*])*
+                const $Flag:tt;
+            )*
+        }
+    ) => {};
+}
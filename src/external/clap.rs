@@ -0,0 +1,189 @@
+//! Parse flags values from command line arguments using `clap`.
+
+// `clap` always depends on `std`, even if `bitflags` doesn't
+extern crate std;
+
+use core::{fmt, marker::PhantomData};
+use std::{
+    ffi::OsStr,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use clap::{builder::TypedValueParser, error::ErrorKind, Arg, Command, Error};
+
+use crate::{
+    parser::{self, ParseHex},
+    Flags,
+};
+
+/**
+Get a `clap` value parser for a flags type.
+
+Arguments are parsed using the text format from the [`parser`](crate::parser) module, like `A | B`.
+If an argument can't be parsed then the error lists the names of the defined flags.
+
+```
+use bitflags::bitflags;
+use clap::{Arg, Command};
+
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Flags: u32 {
+        const A = 1;
+        const B = 1 << 1;
+        const C = 1 << 2;
+    }
+}
+
+let cmd = Command::new("app").arg(
+    Arg::new("flags")
+        .long("flags")
+        .value_parser(bitflags::clap::value_parser::<Flags>()),
+);
+
+let matches = cmd.try_get_matches_from(["app", "--flags", "A | C"]).unwrap();
+
+assert_eq!(Some(&(Flags::A | Flags::C)), matches.get_one::<Flags>("flags"));
+```
+*/
+pub fn value_parser<B: Flags>() -> FlagsValueParser<B> {
+    FlagsValueParser {
+        _marker: PhantomData,
+    }
+}
+
+/**
+A `clap` value parser for a flags type.
+
+This type is returned by [`value_parser`] and [`Flags::value_parser`](crate::Flags::value_parser).
+*/
+pub struct FlagsValueParser<B> {
+    _marker: PhantomData<fn() -> B>,
+}
+
+impl<B> Clone for FlagsValueParser<B> {
+    fn clone(&self) -> Self {
+        FlagsValueParser {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<B> fmt::Debug for FlagsValueParser<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FlagsValueParser").finish()
+    }
+}
+
+impl<B: Flags + Clone + Send + Sync> TypedValueParser for FlagsValueParser<B>
+where
+    B::Bits: ParseHex,
+{
+    type Value = B;
+
+    fn parse_ref(&self, cmd: &Command, arg: Option<&Arg>, value: &OsStr) -> Result<B, Error> {
+        let input = value.to_str().ok_or_else(|| {
+            invalid_value::<B>(cmd, arg, &value.to_string_lossy(), "invalid UTF-8")
+        })?;
+
+        parser::from_str::<B>(input).map_err(|e| invalid_value::<B>(cmd, arg, input, e))
+    }
+}
+
+fn invalid_value<B: Flags>(
+    cmd: &Command,
+    arg: Option<&Arg>,
+    input: &str,
+    error: impl fmt::Display,
+) -> Error {
+    // Use the names as they're written, after any `rename_all` transform
+    let names = B::FLAGS
+        .iter()
+        .filter(|flag| flag.is_named())
+        .map(|flag| {
+            let mut name = String::new();
+            let _ = B::RENAME_ALL.write_name(flag.name(), &mut name);
+            name
+        })
+        .collect::<Vec<_>>();
+
+    let arg = arg.map(|arg| arg.to_string());
+    let arg = arg.as_deref().unwrap_or("...");
+
+    let message = if names.is_empty() {
+        format!("invalid value '{}' for '{}': {}\n", input, arg, error)
+    } else {
+        format!(
+            "invalid value '{}' for '{}': {}\n\n  [possible flags: {}]\n",
+            input,
+            arg,
+            error,
+            names.join(", ")
+        )
+    };
+
+    Error::raw(ErrorKind::InvalidValue, message).with_cmd(cmd)
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::{error::ErrorKind, Arg, Command};
+
+    bitflags! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        struct CliFlags: u8 {
+            const READ = 1;
+            const WRITE = 1 << 1;
+            const EXECUTE = 1 << 2;
+        }
+    }
+
+    fn command() -> Command {
+        Command::new("test").arg(
+            Arg::new("flags")
+                .long("flags")
+                .value_parser(<CliFlags as crate::Flags>::value_parser()),
+        )
+    }
+
+    #[test]
+    fn parse() {
+        let matches = command()
+            .try_get_matches_from(["test", "--flags", "READ | EXECUTE | 0x8"])
+            .unwrap();
+
+        assert_eq!(
+            Some(&(CliFlags::READ | CliFlags::EXECUTE | CliFlags::from_bits_retain(1 << 3))),
+            matches.get_one::<CliFlags>("flags")
+        );
+
+        let matches = command()
+            .try_get_matches_from(["test", "--flags", ""])
+            .unwrap();
+
+        assert_eq!(
+            Some(&CliFlags::empty()),
+            matches.get_one::<CliFlags>("flags")
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let err = command()
+            .try_get_matches_from(["test", "--flags", "READ | DELETE"])
+            .unwrap_err();
+
+        assert_eq!(ErrorKind::InvalidValue, err.kind());
+
+        let message = err.to_string();
+        assert!(message.contains("'READ | DELETE'"), "{}", message);
+        assert!(message.contains("DELETE"), "{}", message);
+        assert!(
+            message.contains("[possible flags: READ, WRITE, EXECUTE]"),
+            "{}",
+            message
+        );
+    }
+}
@@ -0,0 +1,117 @@
+//! Specialized JSON schema generation for flags types using `schemars`.
+
+use crate::Flags;
+use alloc::{format, string::String, vec::Vec};
+use schemars::{json_schema, Schema, SchemaGenerator};
+
+/**
+Generate a JSON schema for the human-readable text form of a set of flags.
+
+The schema describes a string of `|` separated flag names or hex numbers, like `"A | B | 0x8"`,
+which is the format used by [`serde`](crate::serde) for human-readable formats like JSON.
+Each named flag is listed in the schema's description, and the schema's pattern only accepts
+those names.
+*/
+pub fn json_schema<B: Flags>(_generator: &mut SchemaGenerator) -> Schema {
+    let names = B::FLAGS
+        .iter()
+        .filter(|flag| flag.is_named())
+        .map(|flag| {
+            // Use the names as they're written, after any `rename_all` transform
+            let mut name = String::new();
+            let _ = B::RENAME_ALL.write_name(flag.name(), &mut name);
+            name
+        })
+        .collect::<Vec<_>>();
+
+    let mut flag = String::from("0x[0-9a-fA-F]+");
+    for name in &names {
+        flag.push('|');
+        flag.push_str(name);
+    }
+
+    let pattern = format!(r"^\s*((?:{flag})(\s*\|\s*(?:{flag}))*)?\s*$", flag = flag);
+
+    let description = if names.is_empty() {
+        String::from("A `|` separated list of hex numbers, like `0x1 | 0x2`.")
+    } else {
+        format!(
+            "A `|` separated list of flags, like `{}`. Defined flags: {}.",
+            names.join(" | "),
+            names
+                .iter()
+                .map(|name| format!("`{}`", name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    };
+
+    json_schema!({
+        "type": "string",
+        "pattern": pattern,
+        "description": description,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use schemars::{schema_for, JsonSchema};
+
+    bitflags! {
+        #[derive(JsonSchema)]
+        #[schemars(transparent)]
+        struct SchemaFlags: u32 {
+            const A = 1;
+            const B = 2;
+            const C = 4;
+
+            const _ = !0;
+        }
+    }
+
+    bitflags! {
+        #[derive(JsonSchema)]
+        #[schemars(transparent)]
+        struct SchemaEmpty: u32 {}
+    }
+
+    #[test]
+    fn test_schema_names() {
+        let schema = schema_for!(SchemaFlags);
+
+        assert_eq!(
+            Some("string"),
+            schema.get("type").and_then(|ty| ty.as_str())
+        );
+
+        let description = schema
+            .get("description")
+            .and_then(|description| description.as_str())
+            .unwrap();
+
+        for name in ["A", "B", "C"] {
+            assert!(
+                description.contains(&format!("`{}`", name)),
+                "{}",
+                description
+            );
+        }
+    }
+
+    #[test]
+    fn test_schema_pattern() {
+        let schema = schema_for!(SchemaFlags);
+
+        assert_eq!(
+            Some(r"^\s*((?:0x[0-9a-fA-F]+|A|B|C)(\s*\|\s*(?:0x[0-9a-fA-F]+|A|B|C))*)?\s*$"),
+            schema.get("pattern").and_then(|pattern| pattern.as_str())
+        );
+
+        let schema = schema_for!(SchemaEmpty);
+
+        assert_eq!(
+            Some(r"^\s*((?:0x[0-9a-fA-F]+)(\s*\|\s*(?:0x[0-9a-fA-F]+))*)?\s*$"),
+            schema.get("pattern").and_then(|pattern| pattern.as_str())
+        );
+    }
+}
@@ -1,4 +1,8 @@
 //! Specialized serialization for flags types using `serde`.
+//!
+//! Flags types that derive `Serialize` and `Deserialize` use [`serialize`] and [`deserialize`],
+//! which retain any unknown bits, like the [`retain`] module. The [`strict`] and [`truncate`]
+//! modules can be used with `#[serde(with)]` to handle unknown bits differently.
 
 use crate::{
     parser::{self, ParseHex, WriteHex},
@@ -66,6 +70,168 @@ where
     }
 }
 
+/**
+Serialize and deserialize a set of flags as their underlying bits, regardless of format.
+
+The [`serialize`] and [`deserialize`] functions pick between a text and a bits representation
+based on whether the format is human-readable. This module always uses the bits representation,
+which is useful for fields that need a fixed-width integer encoding even in human-readable formats.
+It can be used with `#[serde(with)]` on individual fields:
+
+```rust
+# use bitflags::bitflags;
+# use serde_derive::{Deserialize, Serialize};
+bitflags! {
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+    #[serde(transparent)]
+    pub struct Flags: u32 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Message {
+    // Serialized as text in human-readable formats, like `"A | B"`
+    pub text: Flags,
+    // Always serialized as bits, like `3`
+    #[serde(with = "bitflags::serde::as_bits")]
+    pub bits: Flags,
+}
+
+let message = Message {
+    text: Flags::A | Flags::B,
+    bits: Flags::A | Flags::B,
+};
+
+assert_eq!(
+    r#"{"text":"A | B","bits":3}"#,
+    serde_json::to_string(&message).unwrap()
+);
+```
+
+Any unknown bits will be retained.
+*/
+pub mod as_bits {
+    use crate::Flags;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /**
+    Serialize a set of flags as their underlying bits.
+
+    Any unknown bits will be retained.
+    */
+    pub fn serialize<B: Flags, S: Serializer>(flags: &B, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        B::Bits: Serialize,
+    {
+        flags.bits().serialize(serializer)
+    }
+
+    /**
+    Deserialize a set of flags from their underlying bits.
+
+    Any unknown bits will be retained.
+    */
+    pub fn deserialize<'de, B: Flags, D: Deserializer<'de>>(deserializer: D) -> Result<B, D::Error>
+    where
+        B::Bits: Deserialize<'de>,
+    {
+        let bits = B::Bits::deserialize(deserializer)?;
+
+        Ok(B::from_bits_retain(bits))
+    }
+}
+
+/**
+Serialize and deserialize a set of flags, retaining any unknown bits.
+
+This is the same behavior as the [`serialize`] and [`deserialize`] functions used by flags types
+that derive `Serialize` and `Deserialize`. It can be used with `#[serde(with)]` on individual
+fields to make the handling of unknown bits explicit.
+*/
+pub mod retain {
+    pub use super::{deserialize, serialize};
+}
+
+/**
+Serialize and deserialize a set of flags, failing on any unknown bits.
+
+Flags are serialized the same way as the [`serialize`](crate::serde::serialize) function.
+Deserializing a flags value with unknown bits set, like `"A | 0x80"` in a human-readable format
+or a number with bits that don't correspond to any defined flag, will fail.
+
+```rust
+# use bitflags::bitflags;
+# use serde_derive::{Deserialize, Serialize};
+bitflags! {
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+    #[serde(transparent)]
+    pub struct Flags: u32 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Message {
+    #[serde(with = "bitflags::serde::strict")]
+    pub flags: Flags,
+}
+
+assert!(serde_json::from_str::<Message>(r#"{"flags":"A | B"}"#).is_ok());
+assert!(serde_json::from_str::<Message>(r#"{"flags":"A | 0x80"}"#).is_err());
+```
+*/
+pub mod strict {
+    use crate::{parser::ParseHex, Flags};
+    use serde::{de::Error, Deserialize, Deserializer};
+
+    pub use super::serialize;
+
+    /**
+    Deserialize a set of flags from a human-readable string or their underlying bits.
+
+    This function will fail if any unknown bits are set.
+    */
+    pub fn deserialize<'de, B: Flags, D: Deserializer<'de>>(deserializer: D) -> Result<B, D::Error>
+    where
+        B::Bits: ParseHex + Deserialize<'de>,
+    {
+        let flags: B = super::deserialize(deserializer)?;
+
+        B::from_bits(flags.bits())
+            .ok_or_else(|| D::Error::custom("the flags value contains unknown bits"))
+    }
+}
+
+/**
+Serialize and deserialize a set of flags, ignoring any unknown bits.
+
+Flags are serialized the same way as the [`serialize`](crate::serde::serialize) function.
+Any unknown bits in a deserialized flags value are unset, like `from_bits_truncate`.
+*/
+pub mod truncate {
+    use crate::{parser::ParseHex, Flags};
+    use serde::{Deserialize, Deserializer};
+
+    pub use super::serialize;
+
+    /**
+    Deserialize a set of flags from a human-readable string or their underlying bits.
+
+    Any unknown bits will be ignored.
+    */
+    pub fn deserialize<'de, B: Flags, D: Deserializer<'de>>(deserializer: D) -> Result<B, D::Error>
+    where
+        B::Bits: ParseHex + Deserialize<'de>,
+    {
+        let flags: B = super::deserialize(deserializer)?;
+
+        Ok(B::from_bits_truncate(flags.bits()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_test::{assert_tokens, Configure, Token::*};
@@ -90,4 +256,147 @@ mod tests {
 
         assert_tokens(&(SerdeFlags::A | SerdeFlags::B).compact(), &[U32(1 | 2)]);
     }
+
+    #[test]
+    fn test_serde_bitflags_rename_all() {
+        bitflags! {
+            #[derive(serde_derive::Serialize, serde_derive::Deserialize, Debug, PartialEq, Eq)]
+            #[serde(transparent)]
+            #[bitflags(rename_all = "kebab-case")]
+            struct RenamedFlags: u32 {
+                const READ_WRITE = 1;
+                const EXECUTE = 2;
+            }
+        }
+
+        assert_tokens(
+            &(RenamedFlags::READ_WRITE | RenamedFlags::EXECUTE).readable(),
+            &[Str("read-write | execute")],
+        );
+
+        serde_test::assert_de_tokens(&RenamedFlags::READ_WRITE.readable(), &[Str("READ_WRITE")]);
+    }
+
+    #[derive(serde_derive::Serialize, serde_derive::Deserialize, Debug, PartialEq, Eq)]
+    struct AsBits {
+        #[serde(with = "crate::serde::as_bits")]
+        flags: SerdeFlags,
+    }
+
+    #[test]
+    fn test_serde_bitflags_as_bits() {
+        use serde_test::Token;
+
+        for bits in [0, 1 | 2, 1 << 31] {
+            let tokens = [
+                Struct {
+                    name: "AsBits",
+                    len: 1,
+                },
+                Token::Str("flags"),
+                U32(bits),
+                StructEnd,
+            ];
+
+            let flags = || AsBits {
+                flags: SerdeFlags::from_bits_retain(bits),
+            };
+
+            assert_tokens(&flags().readable(), &tokens);
+            assert_tokens(&flags().compact(), &tokens);
+        }
+    }
+
+    #[derive(serde_derive::Serialize, serde_derive::Deserialize, Debug, PartialEq, Eq)]
+    struct Adapters {
+        #[serde(with = "crate::serde::retain")]
+        retain: SerdeFlags,
+        #[serde(with = "crate::serde::truncate")]
+        truncate: SerdeFlags,
+    }
+
+    #[derive(serde_derive::Serialize, serde_derive::Deserialize, Debug, PartialEq, Eq)]
+    struct Strict {
+        #[serde(with = "crate::serde::strict")]
+        strict: SerdeFlags,
+    }
+
+    #[test]
+    fn test_serde_bitflags_adapters() {
+        use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_ser_tokens, Token};
+
+        let unknown = || SerdeFlags::A | SerdeFlags::from_bits_retain(1 << 31);
+
+        let adapters = |retain, truncate| Adapters { retain, truncate };
+        let tokens = |retain, truncate| {
+            [
+                Struct {
+                    name: "Adapters",
+                    len: 2,
+                },
+                Token::Str("retain"),
+                retain,
+                Token::Str("truncate"),
+                truncate,
+                StructEnd,
+            ]
+        };
+
+        // Unknown bits are always serialized
+        assert_ser_tokens(
+            &adapters(unknown(), unknown()).readable(),
+            &tokens(Str("A | 0x80000000"), Str("A | 0x80000000")),
+        );
+        assert_ser_tokens(
+            &adapters(unknown(), unknown()).compact(),
+            &tokens(U32(1 | 1 << 31), U32(1 | 1 << 31)),
+        );
+
+        // Unknown bits are retained or truncated when deserializing
+        assert_de_tokens(
+            &adapters(unknown(), SerdeFlags::A).readable(),
+            &tokens(Str("A | 0x80000000"), Str("A | 0x80000000")),
+        );
+        assert_de_tokens(
+            &adapters(unknown(), SerdeFlags::A).compact(),
+            &tokens(U32(1 | 1 << 31), U32(1 | 1 << 31)),
+        );
+
+        let strict = |value| {
+            [
+                Struct {
+                    name: "Strict",
+                    len: 1,
+                },
+                Token::Str("strict"),
+                value,
+                StructEnd,
+            ]
+        };
+
+        assert_de_tokens(
+            &Strict {
+                strict: SerdeFlags::A | SerdeFlags::D,
+            }
+            .readable(),
+            &strict(Str("A | D")),
+        );
+        assert_de_tokens(
+            &Strict {
+                strict: SerdeFlags::A | SerdeFlags::D,
+            }
+            .compact(),
+            &strict(U32(1 | 8)),
+        );
+
+        // Unknown bits fail when deserializing strictly
+        assert_de_tokens_error::<serde_test::Readable<Strict>>(
+            &strict(Str("A | 0x80000000")),
+            "the flags value contains unknown bits",
+        );
+        assert_de_tokens_error::<serde_test::Compact<Strict>>(
+            &strict(U32(1 | 1 << 31)),
+            "the flags value contains unknown bits",
+        );
+    }
 }
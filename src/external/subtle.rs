@@ -0,0 +1,31 @@
+#[cfg(test)]
+mod tests {
+    use subtle::ConstantTimeEq;
+
+    bitflags! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        struct Capabilities: u64 {
+            const READ = 1;
+            const WRITE = 1 << 1;
+            const ADMIN = 1 << 63;
+        }
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        let values = [
+            Capabilities::empty(),
+            Capabilities::READ,
+            Capabilities::READ | Capabilities::WRITE,
+            Capabilities::ADMIN,
+            Capabilities::all(),
+            Capabilities::from_bits_retain(1 << 7),
+        ];
+
+        for a in values {
+            for b in values {
+                assert_eq!(a == b, bool::from(a.ct_eq(&b)), "{:?}.ct_eq({:?})", a, b);
+            }
+        }
+    }
+}
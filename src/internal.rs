@@ -29,6 +29,7 @@ macro_rules! __declare_internal_bitflags {
 #[doc(hidden)]
 macro_rules! __impl_internal_bitflags {
     (
+        options: [$($options:tt)*],
         $InternalBitFlags:ident: $T:ty, $PublicBitFlags:ident {
             $(
                 $(#[$inner:ident $($args:tt)*])*
@@ -51,35 +52,36 @@ macro_rules! __impl_internal_bitflags {
             }
         }
 
-        impl $crate::__private::core::fmt::Debug for $InternalBitFlags {
-            fn fmt(&self, f: &mut $crate::__private::core::fmt::Formatter<'_>) -> $crate::__private::core::fmt::Result {
-                if self.is_empty() {
-                    // If no flags are set then write an empty hex flag to avoid
-                    // writing an empty string. In some contexts, like serialization,
-                    // an empty string is preferable, but it may be unexpected in
-                    // others for a format not to produce any output.
-                    //
-                    // We can remove this `0x0` and remain compatible with `FromStr`,
-                    // because an empty string will still parse to an empty set of flags,
-                    // just like `0x0` does.
-                    $crate::__private::core::write!(f, "{:#x}", <$T as $crate::Bits>::EMPTY)
-                } else {
-                    $crate::__private::core::fmt::Display::fmt(self, f)
-                }
-            }
+        $crate::__impl_internal_bitflags_debug! {
+            options: [$($options)*],
+            $InternalBitFlags: $T, $PublicBitFlags
         }
 
-        impl $crate::__private::core::fmt::Display for $InternalBitFlags {
-            fn fmt(&self, f: &mut $crate::__private::core::fmt::Formatter<'_>) -> $crate::__private::core::fmt::Result {
-                $crate::parser::to_writer(&$PublicBitFlags(*self), f)
-            }
-        }
+        // The text format is skipped by the `minimal` option
+        $crate::__bitflags_unless_minimal! {
+            options: [$($options)*],
+            {
+                impl $crate::__private::core::fmt::Display for $InternalBitFlags {
+                    fn fmt(&self, f: &mut $crate::__private::core::fmt::Formatter<'_>) -> $crate::__private::core::fmt::Result {
+                        $crate::parser::to_writer(&$PublicBitFlags(*self), f)
+                    }
+                }
+
+                impl $crate::__private::core::str::FromStr for $InternalBitFlags {
+                    type Err = $crate::parser::ParseError;
+
+                    fn from_str(s: &str) -> $crate::__private::core::result::Result<Self, Self::Err> {
+                        $crate::parser::from_str::<$PublicBitFlags>(s).map(|flags| flags.0)
+                    }
+                }
 
-        impl $crate::__private::core::str::FromStr for $InternalBitFlags {
-            type Err = $crate::parser::ParseError;
+                impl<'a> $crate::__private::core::convert::TryFrom<&'a str> for $InternalBitFlags {
+                    type Error = $crate::parser::ParseError;
 
-            fn from_str(s: &str) -> $crate::__private::core::result::Result<Self, Self::Err> {
-                $crate::parser::from_str::<$PublicBitFlags>(s).map(|flags| flags.0)
+                    fn try_from(s: &'a str) -> $crate::__private::core::result::Result<Self, Self::Error> {
+                        $crate::parser::from_str::<$PublicBitFlags>(s).map(|flags| flags.0)
+                    }
+                }
             }
         }
 
@@ -122,4 +124,115 @@ macro_rules! __impl_internal_bitflags {
             }
         }
     };
+    (
+        $InternalBitFlags:ident: $T:ty, $PublicBitFlags:ident {
+            $(
+                $(#[$inner:ident $($args:tt)*])*
+                const $Flag:tt = $value:expr;
+            )*
+        }
+    ) => {
+        $crate::__impl_internal_bitflags! {
+            options: [],
+            $InternalBitFlags: $T, $PublicBitFlags {
+                $(
+                    $(#[$inner $($args)*])*
+                    const $Flag = $value;
+                )*
+            }
+        }
+    };
+}
+
+/// Implement `Debug` for the private (bitflags-facing) bitflags type.
+///
+/// The format depends on the `debug` option passed through `#[bitflags(..)]`.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __impl_internal_bitflags_debug {
+    // `debug = "set"`: Format the flags value like a set, such as `{A, B, 0x8}`
+    (
+        options: [{ debug = "set" } $($options:tt)*],
+        $InternalBitFlags:ident: $T:ty, $PublicBitFlags:ident $(, $minimal:ident)?
+    ) => {
+        impl $crate::__private::core::fmt::Debug for $InternalBitFlags {
+            fn fmt(&self, f: &mut $crate::__private::core::fmt::Formatter<'_>) -> $crate::__private::core::fmt::Result {
+                let mut set = f.debug_set();
+
+                let mut iter = self.iter_names();
+                for (name, _) in &mut iter {
+                    set.entry(&$crate::__private::core::format_args!(
+                        "{}",
+                        $crate::__private::FlagNameWithRenameAll(
+                            <$PublicBitFlags as $crate::Flags>::RENAME_ALL,
+                            name,
+                        )
+                    ));
+                }
+
+                // Any remaining bits are written as a single hex entry
+                let remaining = iter.remaining().bits();
+                if remaining != <$T as $crate::Bits>::EMPTY {
+                    set.entry(&$crate::__private::core::format_args!("{:#x}", remaining));
+                }
+
+                set.finish()
+            }
+        }
+    };
+    // `minimal`: Remember the option, since the text format isn't available
+    // A `debug` option that comes after it still takes precedence
+    (
+        options: [{ minimal } $($options:tt)*],
+        $InternalBitFlags:ident: $T:ty, $PublicBitFlags:ident $(, $minimal:ident)?
+    ) => {
+        $crate::__impl_internal_bitflags_debug! {
+            options: [$($options)*],
+            $InternalBitFlags: $T, $PublicBitFlags, minimal
+        }
+    };
+    // Skip any other options
+    (
+        options: [{ $($option:tt)* } $($options:tt)*],
+        $InternalBitFlags:ident: $T:ty, $PublicBitFlags:ident $(, $minimal:ident)?
+    ) => {
+        $crate::__impl_internal_bitflags_debug! {
+            options: [$($options)*],
+            $InternalBitFlags: $T, $PublicBitFlags $(, $minimal)?
+        }
+    };
+    // No `debug` option, but `minimal`: Format the bits as a hex number, such as `0xb`
+    (
+        options: [],
+        $InternalBitFlags:ident: $T:ty, $PublicBitFlags:ident, minimal
+    ) => {
+        impl $crate::__private::core::fmt::Debug for $InternalBitFlags {
+            fn fmt(&self, f: &mut $crate::__private::core::fmt::Formatter<'_>) -> $crate::__private::core::fmt::Result {
+                $crate::__private::core::write!(f, "{:#x}", self.0)
+            }
+        }
+    };
+    // No `debug` option: Format the flags value using the text format, such as `A | B | 0x8`
+    (
+        options: [],
+        $InternalBitFlags:ident: $T:ty, $PublicBitFlags:ident
+    ) => {
+        impl $crate::__private::core::fmt::Debug for $InternalBitFlags {
+            fn fmt(&self, f: &mut $crate::__private::core::fmt::Formatter<'_>) -> $crate::__private::core::fmt::Result {
+                if self.is_empty() {
+                    // If no flags are set then write an empty hex flag to avoid
+                    // writing an empty string. In some contexts, like serialization,
+                    // an empty string is preferable, but it may be unexpected in
+                    // others for a format not to produce any output.
+                    //
+                    // We can remove this `0x0` and remain compatible with `FromStr`,
+                    // because an empty string will still parse to an empty set of flags,
+                    // just like `0x0` does.
+                    $crate::__private::core::write!(f, "{:#x}", <$T as $crate::Bits>::EMPTY)
+                } else {
+                    $crate::__private::core::fmt::Display::fmt(self, f)
+                }
+            }
+        }
+    };
 }
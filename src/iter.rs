@@ -2,6 +2,8 @@
 Yield the bits of a source flags value in a set of contained flags values.
 */
 
+use core::iter::FusedIterator;
+
 use crate::{Flag, Flags};
 
 /**
@@ -58,6 +60,79 @@ impl<B: Flags> Iterator for Iter<B> {
     }
 }
 
+// Once `done` is set, `Iter` will always return `None`
+impl<B: Flags> FusedIterator for Iter<B> {}
+
+/**
+A flags value yielded by [`IterWithUnknown`].
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlagOrBits<B: Flags> {
+    /// A contained, defined, named flag, along with its name.
+    Flag(&'static str, B),
+    /// Any remaining bits that didn't correspond to a yielded flag.
+    ///
+    /// This is always the final item, and is only yielded if there are remaining bits.
+    Unknown(B::Bits),
+}
+
+/**
+An iterator over flags values, and any remaining bits.
+
+This iterator will yield [`FlagOrBits::Flag`] for contained, defined, named flags first, with any remaining bits
+yielded as a final [`FlagOrBits::Unknown`]. Every bit in the source flags value is covered by exactly one
+kind of item, so all bits can be handled without separately checking for unknown bits.
+*/
+pub struct IterWithUnknown<B: 'static> {
+    inner: IterNames<B>,
+    done: bool,
+}
+
+impl<B: Flags> IterWithUnknown<B> {
+    pub(crate) fn new(flags: &B) -> Self {
+        IterWithUnknown {
+            inner: IterNames::new(flags),
+            done: false,
+        }
+    }
+}
+
+impl<B: 'static> IterWithUnknown<B> {
+    // Used by the `bitflags` macro
+    #[doc(hidden)]
+    pub const fn __private_const_new(flags: &'static [Flag<B>], source: B, remaining: B) -> Self {
+        IterWithUnknown {
+            inner: IterNames::__private_const_new(flags, source, remaining),
+            done: false,
+        }
+    }
+}
+
+impl<B: Flags> Iterator for IterWithUnknown<B> {
+    type Item = FlagOrBits<B>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.inner.next() {
+            Some((name, flag)) => Some(FlagOrBits::Flag(name, flag)),
+            None if !self.done => {
+                self.done = true;
+
+                // After iterating through valid names, if there are any bits left over
+                // then return them as the final item
+                if !self.inner.remaining().is_empty() {
+                    Some(FlagOrBits::Unknown(self.inner.remaining().bits()))
+                } else {
+                    None
+                }
+            }
+            None => None,
+        }
+    }
+}
+
+// Once `done` is set, `IterWithUnknown` will always return `None`
+impl<B: Flags> FusedIterator for IterWithUnknown<B> {}
+
 /**
 An iterator over flags values.
 
@@ -143,3 +218,6 @@ impl<B: Flags> Iterator for IterNames<B> {
         None
     }
 }
+
+// Once all flags have been visited, or all bits have been yielded, `IterNames` will always return `None`
+impl<B: Flags> FusedIterator for IterNames<B> {}
@@ -38,6 +38,9 @@ bitflags! {
 
 See the docs for the `bitflags` macro for the full syntax.
 
+The [`prelude`] module re-exports the `bitflags` macro along with the [`Flags`] trait and its
+supporting types, so `use bitflags::prelude::*;` brings generic methods like `Flags::iter` into scope.
+
 Also see the [`example_generated`](./example_generated/index.html) module for an example of what the `bitflags` macro generates for a flags type.
 
 ### Externally defined flags
@@ -71,10 +74,19 @@ You can derive some traits on generated flags types if you enable Cargo features
 libraries are currently supported:
 
 - `serde`: Support `#[derive(Serialize, Deserialize)]`, using text for human-readable formats,
-  and a raw number for binary formats.
+  and a raw number for binary formats. Fields can use `#[serde(with = "bitflags::serde::as_bits")]`
+  to always use a raw number.
 - `arbitrary`: Support `#[derive(Arbitrary)]`, only generating flags values with known bits.
 - `bytemuck`: Support `#[derive(Pod, Zeroable)]`, for casting between flags values and their
   underlying bits values.
+- `schemars`: Support `#[derive(JsonSchema)]`, describing the text form used by `serde` for
+  human-readable formats.
+- `subtle`: Implement `subtle::ConstantTimeEq` for flags types generated in `struct` mode, comparing
+  their bits values in constant time. Only that comparison is constant time; `==` and every other
+  method on flags values, like `contains` or formatting, can still leak their bits through timing.
+  Flags types declared in `impl` mode can implement it themselves with `self.bits().ct_eq(&other.bits())`.
+- `clap`: Parse flags values from command line arguments with `bitflags::clap::value_parser`
+  or `Flags::value_parser`, using the same text format as the [`parser`] module.
 
 You can also define your own flags type outside of the [`bitflags`] macro and then use it to generate methods.
 This can be useful if you need a custom `#[derive]` attribute for a library that `bitflags` doesn't
@@ -150,12 +162,22 @@ let c = !ab;
 
 See the docs for the [`Flags`] trait for more details on operators and how they behave.
 
+Flags values can also be compared directly with values of their underlying bits type using
+the [`bits_eq`](#bits_eq) option.
+
+The `|`, `&`, `^`, and `-` operators can also accept a raw bits value on the right-hand side
+using the [`bits_ops`](#bits_ops) option.
+
 # Formatting and parsing
 
 `bitflags` defines a text format that can be used to convert any flags value to and from strings.
 
 See the [`parser`] module for more details.
 
+Error types, like [`parser::ParseError`], implement `std::error::Error` with the `std` feature.
+In `no_std` builds, the `core_error` feature implements `core::error::Error` for them instead.
+It requires Rust 1.81 or newer, so it isn't enabled by `std` or by default.
+
 # Specification
 
 The terminology and behavior of generated flags types is
@@ -219,6 +241,29 @@ Flags with no bits set should be avoided because they interact strangely with [`
 and [`Flags::intersects`]. A zero-bit flag is always contained, but is never intersected. The
 names of zero-bit flags can be parsed, but are never formatted.
 
+Zero-bit flags don't contribute any bits to [`Flags::all`], so they're ignored by
+[`Flags::is_empty`] and [`Flags::is_all`] alike. A flags value containing only a zero-bit flag
+is empty, and a flags value is all if it contains every bit of the non-zero flags, whether or not
+a zero-bit flag is declared. For a flags type with only zero-bit flags, every value is all,
+including the empty one:
+
+```
+# use bitflags::bitflags;
+bitflags! {
+    #[derive(Debug, PartialEq, Eq)]
+    struct Flags: u8 {
+        const ZERO = 0;
+        const A = 1;
+    }
+}
+
+assert!(Flags::ZERO.is_empty());
+assert!(!Flags::ZERO.is_all());
+
+assert!(Flags::A.is_all());
+assert_eq!(Flags::A, Flags::all());
+```
+
 ## Multi-bit flags
 
 Flags that set multiple bits should be avoided unless each bit is also in a single-bit flag.
@@ -236,17 +281,53 @@ bitflags! {
 
 The result of `Flags::A ^ Flags::B` is `0b0000_0010`, which doesn't correspond to either
 `Flags::A` or `Flags::B` even though it's still a known bit.
+
+## Iteration order
+
+The spec leaves the order that flags are yielded in up to implementations. This crate guarantees
+that `iter` and `iter_names` visit named flags in the order they're declared, yielding each one
+that's contained in the flags value and still has bits that weren't yielded by an earlier flag.
+For `iter`, any remaining bits are then yielded together as a final flags value.
+
+The order depends only on the declaration of the flags type and the flags value being iterated,
+so it's the same across builds, platforms, and compilation units. Formatting follows the same
+order, so text produced by [`parser::to_writer`] is stable too. Changing this order is considered
+a breaking change.
+
+```
+# use bitflags::bitflags;
+bitflags! {
+    struct Flags: u8 {
+        const C = 1 << 2;
+        const A = 1;
+        const B = 1 << 1;
+    }
+}
+
+let names = (Flags::A | Flags::B | Flags::C)
+    .iter_names()
+    .map(|(name, _)| name)
+    .collect::<Vec<_>>();
+
+assert_eq!(vec!["C", "A", "B"], names);
+```
 */
 
 #![cfg_attr(not(any(feature = "std", test)), no_std)]
-#![cfg_attr(not(test), forbid(unsafe_code))]
+// `deny` rather than `forbid` so the `unsafe` declaration of `TransparentFlags` can be allowed
+#![cfg_attr(not(test), deny(unsafe_code))]
 #![cfg_attr(test, allow(mixed_script_confusables))]
 
+#[cfg(any(feature = "alloc", feature = "schemars"))]
+extern crate alloc;
+
 #[doc(inline)]
-pub use traits::{Bits, Flag, Flags};
+pub use traits::{Bits, BitsBytes, BitsCount, Flag, Flags, TransparentFlags};
 
+pub mod convert;
 pub mod iter;
 pub mod parser;
+pub mod prelude;
 
 mod traits;
 
@@ -257,6 +338,17 @@ pub mod __private {
     pub use crate::{external::__private::*, traits::__private::*};
 
     pub use core;
+
+    // Used by the `disjoint` option, so compile errors read as
+    // "expected `NamedFlagsOverlap<false>`, found `NamedFlagsOverlap<true>`"
+    pub struct NamedFlagsOverlap<const OVERLAPPING: bool>;
+
+    // Used by the `valid_mask` option, so compile errors read as
+    // "expected `FlagsOutsideValidMask<false>`, found `FlagsOutsideValidMask<true>`"
+    pub struct FlagsOutsideValidMask<const OUTSIDE: bool>;
+
+    #[cfg(feature = "alloc")]
+    pub use alloc::vec::Vec;
 }
 
 #[allow(unused_imports)]
@@ -366,6 +458,24 @@ bitflags! {
 }
 ```
 
+The value of a flag is always its bits type, but any `const` method can be used to build it,
+so composite flags can be declared with `union` and then converted back with `bits`:
+
+```
+# use bitflags::bitflags;
+bitflags! {
+    #[derive(Debug, PartialEq, Eq)]
+    struct Flags: u8 {
+        const A = 1;
+        const B = 1 << 1;
+        const C = 1 << 2;
+        const ABC = Self::A.union(Self::B).union(Self::C).bits();
+    }
+}
+
+assert_eq!(Flags::all(), Flags::ABC);
+```
+
 A single `bitflags` invocation may include zero or more flags type declarations:
 
 ```
@@ -383,6 +493,24 @@ bitflags! {
 }
 ```
 
+The bits type may be given through a type alias or path, so the width of several flags types
+can be changed in one place:
+
+```
+# use bitflags::bitflags;
+type Word = u16;
+
+bitflags! {
+    struct Flags1: Word {
+        const A = 1;
+    }
+
+    struct Flags2: Word {
+        const A = 1;
+    }
+}
+```
+
 # `impl` mode
 
 A declaration that begins with `impl` will only generate methods and trait implementations for the
@@ -442,202 +570,1306 @@ bitflags! {
     }
 }
 ```
-*/
-#[macro_export]
-macro_rules! bitflags {
-    (
-        $(#[$outer:meta])*
-        $vis:vis struct $BitFlags:ident: $T:ty {
-            $(
-                $(#[$inner:ident $($args:tt)*])*
-                const $Flag:tt = $value:expr;
-            )*
-        }
 
-        $($t:tt)*
-    ) => {
-        // Declared in the scope of the `bitflags!` call
-        // This type appears in the end-user's API
-        $crate::__declare_public_bitflags! {
-            $(#[$outer])*
-            $vis struct $BitFlags
-        }
+# Options
 
-        // Workaround for: https://github.com/bitflags/bitflags/issues/320
-        $crate::__impl_public_bitflags_consts! {
-            $BitFlags: $T {
-                $(
-                    $(#[$inner $($args)*])*
-                    const $Flag = $value;
-                )*
-            }
-        }
+The generated code can be configured with a `#[bitflags(..)]` attribute on the declaration.
+Options are separated by commas, and multiple `#[bitflags(..)]` attributes may be given.
+Unrecognized options are a compile error.
 
-        #[allow(
-            dead_code,
-            deprecated,
-            unused_doc_comments,
-            unused_attributes,
-            unused_mut,
-            unused_imports,
-            non_upper_case_globals,
-            clippy::assign_op_pattern,
-            clippy::indexing_slicing,
-            clippy::same_name_method,
-            clippy::iter_without_into_iter,
-        )]
-        const _: () = {
-            // Declared in a "hidden" scope that can't be reached directly
-            // These types don't appear in the end-user's API
-            $crate::__declare_internal_bitflags! {
-                $vis struct InternalBitFlags: $T
-            }
+Individual flags also accept a `#[bitflags(group = "..")]` attribute, which is available
+through [`Flag::group`](crate::Flag::group).
 
-            $crate::__impl_internal_bitflags! {
-                InternalBitFlags: $T, $BitFlags {
-                    $(
-                        $(#[$inner $($args)*])*
-                        const $Flag = $value;
-                    )*
-                }
-            }
+## `debug = "set"`
 
-            // This is where new library trait implementations can be added
-            $crate::__impl_external_bitflags! {
-                InternalBitFlags: $T, $BitFlags {
-                    $(
-                        $(#[$inner $($args)*])*
-                        const $Flag;
-                    )*
-                }
-            }
+Format flags values in a derived `Debug` implementation as a set of names, instead of the text format.
+Any unknown bits are formatted together as a final hex entry. This option is only supported in `struct` mode.
+Names are written using the `rename_all` transform, if there is one.
 
-            $crate::__impl_public_bitflags_forward! {
-                $BitFlags: $T, InternalBitFlags
-            }
+```
+# use bitflags::bitflags;
+bitflags! {
+    #[derive(Debug)]
+    #[bitflags(debug = "set")]
+    struct Flags: u8 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+}
 
-            $crate::__impl_public_bitflags_ops! {
-                $BitFlags
-            }
+assert_eq!("Flags({A, B, 0x8})", format!("{:?}", Flags::all() | Flags::from_bits_retain(1 << 3)));
+```
 
-            $crate::__impl_public_bitflags_iter! {
-                $BitFlags: $T, $BitFlags
-            }
-        };
+## `default = A | B`
 
-        $crate::bitflags! {
-            $($t)*
-        }
-    };
-    (
-        $(#[$outer:meta])*
-        impl $BitFlags:ident: $T:ty {
-            $(
-                $(#[$inner:ident $($args:tt)*])*
-                const $Flag:tt = $value:expr;
-            )*
-        }
+Implement `Default` for the flags type, returning the union of the given flags.
+Each flag must be defined on the flags type. This option replaces `#[derive(Default)]`,
+which returns an empty flags value, so the two can't be combined.
 
-        $($t:tt)*
-    ) => {
-        $crate::__impl_public_bitflags_consts! {
-            $BitFlags: $T {
-                $(
-                    $(#[$inner $($args)*])*
-                    const $Flag = $value;
-                )*
-            }
-        }
+```
+# use bitflags::bitflags;
+bitflags! {
+    #[derive(Debug, PartialEq, Eq)]
+    #[bitflags(default = A | C)]
+    struct Flags: u8 {
+        const A = 1;
+        const B = 1 << 1;
+        const C = 1 << 2;
+    }
+}
 
-        #[allow(
-            dead_code,
-            deprecated,
-            unused_doc_comments,
-            unused_attributes,
-            unused_mut,
-            unused_imports,
-            non_upper_case_globals,
-            clippy::assign_op_pattern,
-            clippy::iter_without_into_iter,
-        )]
-        const _: () = {
-            $crate::__impl_public_bitflags! {
-                $(#[$outer])*
-                $BitFlags: $T, $BitFlags {
-                    $(
-                        $(#[$inner $($args)*])*
-                        const $Flag = $value;
-                    )*
-                }
-            }
+assert_eq!(Flags::A | Flags::C, Flags::default());
+```
 
-            $crate::__impl_public_bitflags_ops! {
-                $BitFlags
-            }
+## `new`
 
-            $crate::__impl_public_bitflags_iter! {
-                $BitFlags: $T, $BitFlags
-            }
-        };
+Add a `const fn new(bits) -> Self` constructor to the flags type that retains all bits,
+like `from_bits_retain`. This option isn't enabled by default because it would conflict
+with any `new` method already defined on the flags type.
 
-        $crate::bitflags! {
-            $($t)*
-        }
-    };
-    () => {};
+```
+# use bitflags::bitflags;
+bitflags! {
+    #[derive(Debug, PartialEq, Eq)]
+    #[bitflags(new)]
+    struct Flags: u8 {
+        const A = 1;
+        const B = 1 << 1;
+    }
 }
 
-/// Implement functions on bitflags types.
-///
-/// We need to be careful about adding new methods and trait implementations here because they
-/// could conflict with items added by the end-user.
-#[macro_export]
-#[doc(hidden)]
-macro_rules! __impl_bitflags {
-    (
-        $(#[$outer:meta])*
-        $PublicBitFlags:ident: $T:ty {
-            fn empty() $empty:block
-            fn all() $all:block
-            fn bits($bits0:ident) $bits:block
-            fn from_bits($from_bits0:ident) $from_bits:block
-            fn from_bits_truncate($from_bits_truncate0:ident) $from_bits_truncate:block
-            fn from_bits_retain($from_bits_retain0:ident) $from_bits_retain:block
-            fn from_name($from_name0:ident) $from_name:block
-            fn is_empty($is_empty0:ident) $is_empty:block
-            fn is_all($is_all0:ident) $is_all:block
-            fn intersects($intersects0:ident, $intersects1:ident) $intersects:block
-            fn contains($contains0:ident, $contains1:ident) $contains:block
-            fn insert($insert0:ident, $insert1:ident) $insert:block
-            fn remove($remove0:ident, $remove1:ident) $remove:block
-            fn toggle($toggle0:ident, $toggle1:ident) $toggle:block
-            fn set($set0:ident, $set1:ident, $set2:ident) $set:block
-            fn intersection($intersection0:ident, $intersection1:ident) $intersection:block
-            fn union($union0:ident, $union1:ident) $union:block
-            fn difference($difference0:ident, $difference1:ident) $difference:block
-            fn symmetric_difference($symmetric_difference0:ident, $symmetric_difference1:ident) $symmetric_difference:block
-            fn complement($complement0:ident) $complement:block
-        }
-    ) => {
-        #[allow(dead_code, deprecated, unused_attributes)]
-        $(#[$outer])*
-        impl $PublicBitFlags {
-            /// Get a flags value with all bits unset.
-            #[inline]
-            pub const fn empty() -> Self {
-                $empty
-            }
+assert_eq!(Flags::A | Flags::B, Flags::new(0b11));
+assert_eq!(Flags::from_bits_retain(1 << 7), Flags::new(1 << 7));
+```
 
-            /// Get a flags value with all known bits set.
-            #[inline]
-            pub const fn all() -> Self {
+## `transparent`
+
+Add a `#[repr(transparent)]` attribute to the flags type, so it has the same ABI as its bits type,
+and implement [`TransparentFlags`](crate::TransparentFlags) for it. The size and alignment of the
+flags type are also checked against its bits type at compile time. This option is equivalent to
+writing `#[repr(transparent)]` on the flags type, but documents the intent of guarding its ABI.
+This option is only supported in `struct` mode.
+
+```
+# use bitflags::bitflags;
+bitflags! {
+    #[bitflags(transparent)]
+    struct Flags: u32 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+}
+
+assert_eq!(std::mem::size_of::<u32>(), std::mem::size_of::<Flags>());
+```
+
+## `canonical`
+
+Implement `PartialEq`, `Eq`, `PartialOrd`, `Ord`, and `Hash` for the flags type using only its
+known bits, so flags values that differ only in unknown bits are considered equal. This option
+replaces deriving those traits, so the two can't be combined. The inherent `const_eq` method
+still compares all bits.
+
+```
+# use bitflags::bitflags;
+use std::collections::HashSet;
+
+bitflags! {
+    #[derive(Debug, Clone, Copy)]
+    #[bitflags(canonical)]
+    struct Flags: u8 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+}
+
+assert_eq!(Flags::A, Flags::A | Flags::from_bits_retain(1 << 7));
+
+let set: HashSet<Flags> = [Flags::A, Flags::A | Flags::from_bits_retain(1 << 7)].into_iter().collect();
+assert_eq!(1, set.len());
+```
+
+## `kind = FlagsKind`
+
+Declare an enum with the given name alongside the flags type, with a variant for each named flag,
+and add a `kind(&self) -> Option<FlagsKind>` method to the flags type. The `kind` method returns
+the variant for a flags value that is exactly one single-bit flag, and `None` for anything else,
+including empty, composite, and unknown values. This makes it possible to `match` on a single
+flag. Variants are also declared for multi-bit flags, but `kind` never returns them.
+This option is only supported in `struct` mode.
+
+```
+# use bitflags::bitflags;
+bitflags! {
+    #[bitflags(kind = FlagsKind)]
+    struct Flags: u8 {
+        const A = 1;
+        const B = 1 << 1;
+        const AB = Self::A.bits() | Self::B.bits();
+    }
+}
+
+fn describe(flag: Flags) -> &'static str {
+    match flag.kind() {
+        Some(FlagsKind::A) => "a",
+        Some(FlagsKind::B) => "b",
+        _ => "something else",
+    }
+}
+
+assert_eq!("a", describe(Flags::A));
+assert_eq!("something else", describe(Flags::AB));
+assert_eq!(None, Flags::empty().kind());
+```
+
+## `validate`
+
+Add a `validate(&self) -> Result<(), UnknownBits<Bits>>` method to the flags type that fails
+if the flags value contains any unknown bits. The error is a [`convert::UnknownBits`](crate::convert::UnknownBits)
+carrying those bits. This is the same check as `contains_unknown_bits`, but returns a `Result`, so it can
+guard values that were converted with `from_bits_retain`, like those coming from FFI.
+
+```
+# use bitflags::{bitflags, convert::UnknownBits};
+bitflags! {
+    #[derive(Debug, PartialEq, Eq)]
+    #[bitflags(validate)]
+    struct Flags: u32 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+}
+
+// Imagine this is an `extern "C"` function called from C code
+fn set_mode(raw: u32) -> Result<(), UnknownBits<u32>> {
+    let flags = Flags::from_bits_retain(raw);
+    flags.validate()?;
+
+    // `flags` only contains known bits from here on
+    Ok(())
+}
+
+assert!(set_mode(0b11).is_ok());
+assert_eq!(Err(UnknownBits::new(1 << 7)), set_mode(0b11 | 1 << 7));
+```
+
+## `minimal`
+
+Skip generating methods and trait implementations that aren't needed for working with flags
+values as sets, to reduce compile times and code size on constrained targets. The flags type
+keeps its constants, the conversions to and from bits, like `bits` and `from_bits_retain`,
+the set operations and their operators, like `union` and `|`, the `Flags` trait, and any
+other options. The following items are omitted:
+
+- Iteration: `iter`, `iter_names`, and the other `iter_*` methods, along with the `IntoIterator`,
+  `FromIterator`, and `Extend` implementations.
+- Text formatting and parsing through `Display`, `FromStr`, and `TryFrom<&str>` on the
+  internal field of `struct` mode types. A derived `Debug` formats the bits as a hex
+  number instead, like `Flags(0x3)`, unless the `debug = "set"` option is also used.
+
+The [`Flags`] trait still provides generic versions of the omitted methods, like
+[`Flags::iter`], and the [`parser`] module still works with the flags type.
+
+The inherent `from_name` method is still generated, because it's part of the core set of methods
+generated for every flags type alongside `from_bits` and the set operations. It's `#[inline]`
+and not generic, so it doesn't add to code size unless it's called.
+
+```
+# use bitflags::bitflags;
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[bitflags(minimal)]
+    struct Flags: u8 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+}
+
+let flags = Flags::A | Flags::B;
+
+assert_eq!(0b11, flags.bits());
+assert_eq!("Flags(0x3)", format!("{:?}", flags));
+```
+
+## `subset_ord`
+
+Implement `PartialOrd` for the flags type as the subset order, so `a <= b` if `b` contains all the bits in `a`.
+Flags values that aren't subsets of each other are incomparable, so their `partial_cmp` returns `None`.
+This suits types like capabilities or permissions, where one set being "less than" another means it grants less.
+All bits are compared, including unknown bits. This option replaces `#[derive(PartialOrd)]`, and can't be combined
+with `#[derive(Ord)]`, since a subset order isn't total. The flags type still needs to implement `PartialEq`.
+
+```
+# use bitflags::bitflags;
+use std::cmp::Ordering;
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq)]
+    #[bitflags(subset_ord)]
+    struct Permissions: u8 {
+        const READ = 1;
+        const WRITE = 1 << 1;
+    }
+}
+
+assert!(Permissions::READ < Permissions::READ | Permissions::WRITE);
+assert!(Permissions::empty() <= Permissions::READ);
+assert_eq!(None, Permissions::READ.partial_cmp(&Permissions::WRITE));
+assert_eq!(Some(Ordering::Equal), Permissions::all().partial_cmp(&Permissions::all()));
+```
+
+## `rename_all = "lowercase"`
+
+Transform the names of flags in the text format, which is used by the [`parser`](crate::parser)
+module, `Debug`, and `serde`. The supported transforms are `"lowercase"`, like `read_write`, and `"kebab-case"`, like
+`read-write`. Flags are written using their transformed names, and parsed from either their
+original or transformed names, so `from_name` accepts both. The names returned by `iter_names`
+and [`Flag::name`](crate::Flag::name) are always the original names. The transform is available
+to generic code through [`Flags::RENAME_ALL`](crate::Flags::RENAME_ALL).
+
+```
+# use bitflags::bitflags;
+bitflags! {
+    #[derive(Debug, PartialEq, Eq)]
+    #[bitflags(rename_all = "kebab-case")]
+    struct Flags: u8 {
+        const READ_WRITE = 1;
+        const EXECUTE = 1 << 1;
+    }
+}
+
+let mut text = String::new();
+bitflags::parser::to_writer(&(Flags::READ_WRITE | Flags::EXECUTE), &mut text).unwrap();
+
+assert_eq!("read-write | execute", text);
+assert_eq!(Some(Flags::READ_WRITE), Flags::from_name("read-write"));
+assert_eq!(Some(Flags::READ_WRITE), Flags::from_name("READ_WRITE"));
+```
+
+## `disjoint`
+
+Check at compile time that no two named flags share any bits. This catches a flag accidentally
+declared with the same bits as another, which is easy to miss in long lists of flags or when
+flags are added to an existing type in `impl` mode. Composite flags and aliases overlap the flags
+they're made of, so they can't be declared alongside the `disjoint` option. Unnamed flags are
+ignored, so `const _ = !0` can still be used. The check requires a primitive integer bits type.
+
+```
+# use bitflags::bitflags;
+pub struct Flags(u8);
+
+bitflags! {
+    #[bitflags(disjoint)]
+    impl Flags: u8 {
+        const A = 1;
+        const B = 1 << 1;
+        const C = 1 << 2;
+
+        const _ = !0;
+    }
+}
+```
+
+Declaring a flag that overlaps another is a compile error:
+
+```compile_fail
+# use bitflags::bitflags;
+pub struct Flags(u8);
+
+bitflags! {
+    #[bitflags(disjoint)]
+    impl Flags: u8 {
+        const A = 1;
+        const B = 1 << 1;
+        const C = 1;
+    }
+}
+```
+
+The check can only see the flags declared in the same `bitflags` invocation. Any other constants
+defined on the flags type outside of it, like in a separate `impl` block, aren't checked.
+
+## `valid_mask = 0xFF`
+
+Check at compile time that the bits of every flag fit within a mask. This documents the bits a
+flags type is allowed to use, and catches a flag accidentally declared outside of them. The mask
+is a constant expression of the bits type. Unnamed flags are checked too, so use `const _ = 0xFF`
+with the same mask rather than `const _ = !0` to treat every valid bit as known. The check
+requires a primitive integer bits type.
+
+```
+# use bitflags::bitflags;
+bitflags! {
+    #[bitflags(valid_mask = 0xFF)]
+    pub struct Flags: u32 {
+        const A = 1;
+        const B = 1 << 1;
+        const HIGH = 1 << 7;
+    }
+}
+```
+
+Declaring a flag with bits outside of the mask is a compile error:
+
+```compile_fail
+# use bitflags::bitflags;
+bitflags! {
+    #[bitflags(valid_mask = 0xFF)]
+    pub struct Flags: u32 {
+        const A = 1;
+        const B = 1 << 8;
+    }
+}
+```
+
+Like `disjoint`, the check can only see the flags declared in the same `bitflags` invocation.
+
+## `bits_eq`
+
+Implement `PartialEq` between the flags type and its bits type, so flags values can be compared
+directly with raw bits, like `flags == 0b101`. This is convenient for test assertions. The
+comparison uses all bits, including any unknown bits, like `flags.bits() == 0b101`.
+
+```
+# use bitflags::bitflags;
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[bitflags(bits_eq)]
+    struct Flags: u32 {
+        const A = 1;
+        const B = 1 << 1;
+        const C = 1 << 2;
+    }
+}
+
+assert_eq!(Flags::A | Flags::C, 0b101);
+assert_ne!(Flags::A | Flags::from_bits_retain(1 << 7), 0b1);
+```
+
+This option is opt-in because a second `PartialEq` implementation makes comparisons that rely
+on inference, like `flags == Default::default()`, ambiguous. Only the flags value can be on
+the left-hand side. Implementing `PartialEq<Flags>` for the bits type would affect inference
+for every comparison of that primitive type in the crate, so `0b101 == flags` isn't supported.
+
+## `bits_ops`
+
+Implement the `|`, `&`, `^`, and `-` operators, and their assigning variants, with a raw bits
+value on the right-hand side. This reduces `from_bits_retain` noise in code that mixes flags
+with raw constants, like FFI glue. All bits are retained, so any unknown bits in the raw value
+survive.
+
+```
+# use bitflags::bitflags;
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[bitflags(bits_ops)]
+    struct Flags: u32 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+}
+
+let mut flags = Flags::A | 0x8000;
+assert_eq!(0x8001, flags.bits());
+
+flags -= 0x8000;
+assert_eq!(Flags::A, flags);
+```
+
+This option is opt-in because a second implementation of an operator makes operations that rely
+on inference, like `flags | Default::default()`, ambiguous.
+
+## `docs`
+
+Capture the doc comments on flags, so they're available through [`Flag::doc`].
+
+```
+# use bitflags::{bitflags, Flags};
+bitflags! {
+    #[bitflags(docs)]
+    struct MyFlags: u8 {
+        /// The `A` flag.
+        const A = 1;
+    }
+}
+
+assert_eq!(Some(" The `A` flag."), MyFlags::FLAGS[0].doc());
+```
+
+This option is opt-in because the text of every doc comment is stored in [`Flags::FLAGS`],
+and so in the final binary if it's used.
+*/
+#[macro_export]
+macro_rules! bitflags {
+    (
+        $(#[$outer:ident $($outer_args:tt)*])*
+        $vis:vis struct $BitFlags:ident: $T:ty {
+            $(
+                $(#[$inner:ident $($args:tt)*])*
+                const $Flag:tt = $value:expr;
+            )*
+        }
+
+        $($t:tt)*
+    ) => {
+        // Split any `#[bitflags]` options out from the attributes on the flags type
+        $crate::__bitflags_options! {
+            unprocessed: [$(#[$outer $($outer_args)*])*],
+            options: [],
+            attrs: [],
+            then: __bitflags_struct {
+                $vis struct $BitFlags: $T {
+                    $(
+                        $(#[$inner $($args)*])*
+                        const $Flag = $value;
+                    )*
+                }
+            },
+        }
+
+        $crate::bitflags! {
+            $($t)*
+        }
+    };
+    (
+        $(#[$outer:ident $($outer_args:tt)*])*
+        impl $BitFlags:ident: $T:ty {
+            $(
+                $(#[$inner:ident $($args:tt)*])*
+                const $Flag:tt = $value:expr;
+            )*
+        }
+
+        $($t:tt)*
+    ) => {
+        // Split any `#[bitflags]` options out from the attributes on the flags type
+        $crate::__bitflags_options! {
+            unprocessed: [$(#[$outer $($outer_args)*])*],
+            options: [],
+            attrs: [],
+            then: __bitflags_impl {
+                impl $BitFlags: $T {
+                    $(
+                        $(#[$inner $($args)*])*
+                        const $Flag = $value;
+                    )*
+                }
+            },
+        }
+
+        $crate::bitflags! {
+            $($t)*
+        }
+    };
+    () => {};
+}
+
+/// Generate a flags type in `struct` mode.
+///
+/// This is called by `bitflags!` after any options have been split out of its attributes.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __bitflags_struct {
+    (
+        options: [$($options:tt)*],
+        attrs: [$($attrs:tt)*],
+        $vis:vis struct $BitFlags:ident: $T:ty {
+            $(
+                $(#[$inner:ident $($args:tt)*])*
+                const $Flag:tt = $value:expr;
+            )*
+        }
+    ) => {
+        $(
+            $crate::__bitflags_check_option! {
+                mode: struct,
+                option: $options,
+            }
+        )*
+
+        $crate::__bitflags_check_subset_ord! {
+            options: [$($options)*],
+            attrs: [$($attrs)*],
+        }
+
+        // Declared in the scope of the `bitflags!` call
+        // This type appears in the end-user's API
+        $crate::__declare_public_bitflags! {
+            options: [$($options)*],
+            $($attrs)*
+            $vis struct $BitFlags
+        }
+
+        // Workaround for: https://github.com/bitflags/bitflags/issues/320
+        $crate::__impl_public_bitflags_consts! {
+            options: [$($options)*],
+            $BitFlags: $T {
+                $(
+                    $(#[$inner $($args)*])*
+                    const $Flag = $value;
+                )*
+            }
+        }
+
+        // Declared in the scope of the `bitflags!` call
+        // This type appears in the end-user's API if the `kind` option is used
+        $crate::__declare_public_bitflags_kind! {
+            options: [$($options)*],
+            $vis struct $BitFlags {
+                $(
+                    $(#[$inner $($args)*])*
+                    const $Flag;
+                )*
+            }
+        }
+
+        #[allow(
+            dead_code,
+            deprecated,
+            unused_doc_comments,
+            unused_attributes,
+            unused_mut,
+            unused_imports,
+            non_upper_case_globals,
+            clippy::assign_op_pattern,
+            clippy::indexing_slicing,
+            clippy::same_name_method,
+            clippy::iter_without_into_iter,
+        )]
+        const _: () = {
+            // Declared in a "hidden" scope that can't be reached directly
+            // These types don't appear in the end-user's API
+            $crate::__declare_internal_bitflags! {
+                $vis struct InternalBitFlags: $T
+            }
+
+            $crate::__impl_internal_bitflags! {
+                options: [$($options)*],
+                InternalBitFlags: $T, $BitFlags {
+                    $(
+                        $(#[$inner $($args)*])*
+                        const $Flag = $value;
+                    )*
+                }
+            }
+
+            // This is where new library trait implementations can be added
+            $crate::__impl_external_bitflags! {
+                InternalBitFlags: $T, $BitFlags {
+                    $(
+                        $(#[$inner $($args)*])*
+                        const $Flag;
+                    )*
+                }
+            }
+
+            $crate::__impl_public_bitflags_forward! {
+                $BitFlags: $T, InternalBitFlags
+            }
+
+            $crate::__impl_public_bitflags_ops! {
+                $BitFlags
+            }
+
+            // These are skipped by the `minimal` option
+            $crate::__bitflags_unless_minimal! {
+                options: [$($options)*],
+                {
+                    $crate::__impl_public_bitflags_bits_extend! {
+                        $BitFlags: $T
+                    }
+
+                    $crate::__impl_public_bitflags_iter! {
+                        $BitFlags: $T, $BitFlags
+                    }
+                }
+            }
+
+            $crate::__impl_public_bitflags_options! {
+                options: [$($options)*],
+                $BitFlags: $T
+            }
+
+            $crate::__impl_public_bitflags_transparent! {
+                options: [$($options)*],
+                attrs: [$($attrs)*],
+                $BitFlags: $T
+            }
+        };
+    };
+}
+
+/// Generate a flags type in `impl` mode.
+///
+/// This is called by `bitflags!` after any options have been split out of its attributes.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __bitflags_impl {
+    (
+        options: [$($options:tt)*],
+        attrs: [$(#[$outer:meta])*],
+        impl $BitFlags:ident: $T:ty {
+            $(
+                $(#[$inner:ident $($args:tt)*])*
+                const $Flag:tt = $value:expr;
+            )*
+        }
+    ) => {
+        $(
+            $crate::__bitflags_check_option! {
+                mode: impl,
+                option: $options,
+            }
+        )*
+
+        $crate::__impl_public_bitflags_consts! {
+            options: [$($options)*],
+            $BitFlags: $T {
+                $(
+                    $(#[$inner $($args)*])*
+                    const $Flag = $value;
+                )*
+            }
+        }
+
+        #[allow(
+            dead_code,
+            deprecated,
+            unused_doc_comments,
+            unused_attributes,
+            unused_mut,
+            unused_imports,
+            non_upper_case_globals,
+            clippy::assign_op_pattern,
+            clippy::iter_without_into_iter,
+        )]
+        const _: () = {
+            $crate::__impl_public_bitflags! {
+                $(#[$outer])*
+                $BitFlags: $T, $BitFlags {
+                    $(
+                        $(#[$inner $($args)*])*
+                        const $Flag = $value;
+                    )*
+                }
+            }
+
+            $crate::__impl_public_bitflags_ops! {
+                $BitFlags
+            }
+
+            // These are skipped by the `minimal` option
+            $crate::__bitflags_unless_minimal! {
+                options: [$($options)*],
+                {
+                    $crate::__impl_public_bitflags_bits_extend! {
+                        $BitFlags: $T
+                    }
+
+                    $crate::__impl_public_bitflags_iter! {
+                        $BitFlags: $T, $BitFlags
+                    }
+                }
+            }
+
+            $crate::__impl_public_bitflags_options! {
+                options: [$($options)*],
+                $BitFlags: $T
+            }
+        };
+    };
+}
+
+/// A macro that splits `#[bitflags(..)]` options out of the attributes on a flags type.
+///
+/// This macro is a token-tree muncher. Each `#[bitflags(..)]` attribute is split on `,`
+/// into individual options, which are collected as `{ .. }` groups. Any other attributes
+/// are passed through unchanged. Once all attributes are processed, the `then` macro is
+/// called with the collected options and attributes, followed by the rest of the declaration.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __bitflags_options {
+    // Process the next attribute
+    // `bitflags`: Split its contents into options
+    (
+        unprocessed: [
+            #[bitflags($($opt:tt)*)]
+            $($rest:tt)*
+        ],
+        options: [$($options:tt)*],
+        attrs: [$($attrs:tt)*],
+        then: $then:ident { $($decl:tt)* },
+    ) => {
+        $crate::__bitflags_options! {
+            split: [],
+            input: [$($opt)*],
+            unprocessed: [$($rest)*],
+            options: [$($options)*],
+            attrs: [$($attrs)*],
+            then: $then { $($decl)* },
+        }
+    };
+    // Process the next attribute
+    // `$other`: Pass the attribute through to the flags type
+    (
+        unprocessed: [
+            #[$other:ident $($args:tt)*]
+            $($rest:tt)*
+        ],
+        options: [$($options:tt)*],
+        attrs: [$($attrs:tt)*],
+        then: $then:ident { $($decl:tt)* },
+    ) => {
+        $crate::__bitflags_options! {
+            unprocessed: [$($rest)*],
+            options: [$($options)*],
+            attrs: [$($attrs)* #[$other $($args)*]],
+            then: $then { $($decl)* },
+        }
+    };
+    // Once all attributes are processed, generate the flags type
+    (
+        unprocessed: [],
+        options: [$($options:tt)*],
+        attrs: [$($attrs:tt)*],
+        then: $then:ident { $($decl:tt)* },
+    ) => {
+        $crate::$then! {
+            options: [$($options)*],
+            attrs: [$($attrs)*],
+            $($decl)*
+        }
+    };
+    // Split the contents of a `bitflags` attribute
+    // `,` with no current option: Skip the separator
+    (
+        split: [],
+        input: [, $($input:tt)*],
+        unprocessed: [$($rest:tt)*],
+        options: [$($options:tt)*],
+        attrs: [$($attrs:tt)*],
+        then: $then:ident { $($decl:tt)* },
+    ) => {
+        $crate::__bitflags_options! {
+            split: [],
+            input: [$($input)*],
+            unprocessed: [$($rest)*],
+            options: [$($options)*],
+            attrs: [$($attrs)*],
+            then: $then { $($decl)* },
+        }
+    };
+    // `,`: Finish the current option
+    (
+        split: [$($current:tt)+],
+        input: [, $($input:tt)*],
+        unprocessed: [$($rest:tt)*],
+        options: [$($options:tt)*],
+        attrs: [$($attrs:tt)*],
+        then: $then:ident { $($decl:tt)* },
+    ) => {
+        $crate::__bitflags_options! {
+            split: [],
+            input: [$($input)*],
+            unprocessed: [$($rest)*],
+            options: [$($options)* { $($current)+ }],
+            attrs: [$($attrs)*],
+            then: $then { $($decl)* },
+        }
+    };
+    // `$next`: Add the token to the current option
+    (
+        split: [$($current:tt)*],
+        input: [$next:tt $($input:tt)*],
+        unprocessed: [$($rest:tt)*],
+        options: [$($options:tt)*],
+        attrs: [$($attrs:tt)*],
+        then: $then:ident { $($decl:tt)* },
+    ) => {
+        $crate::__bitflags_options! {
+            split: [$($current)* $next],
+            input: [$($input)*],
+            unprocessed: [$($rest)*],
+            options: [$($options)*],
+            attrs: [$($attrs)*],
+            then: $then { $($decl)* },
+        }
+    };
+    // Once the attribute is split, finish the last option and process the next attribute
+    (
+        split: [],
+        input: [],
+        unprocessed: [$($rest:tt)*],
+        options: [$($options:tt)*],
+        attrs: [$($attrs:tt)*],
+        then: $then:ident { $($decl:tt)* },
+    ) => {
+        $crate::__bitflags_options! {
+            unprocessed: [$($rest)*],
+            options: [$($options)*],
+            attrs: [$($attrs)*],
+            then: $then { $($decl)* },
+        }
+    };
+    (
+        split: [$($current:tt)+],
+        input: [],
+        unprocessed: [$($rest:tt)*],
+        options: [$($options:tt)*],
+        attrs: [$($attrs:tt)*],
+        then: $then:ident { $($decl:tt)* },
+    ) => {
+        $crate::__bitflags_options! {
+            unprocessed: [$($rest)*],
+            options: [$($options)* { $($current)+ }],
+            attrs: [$($attrs)*],
+            then: $then { $($decl)* },
+        }
+    };
+}
+
+/// Check that an option passed through `#[bitflags(..)]` is recognized.
+///
+/// Each supported option is matched explicitly here. Any other option is a compile error.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __bitflags_check_option {
+    (
+        mode: struct,
+        option: { debug = "set" },
+    ) => {};
+    (
+        mode: impl,
+        option: { debug $($args:tt)* },
+    ) => {
+        $crate::__private::core::compile_error!(
+            "the `debug` option is only supported on flags types declared with `struct`"
+        );
+    };
+    (
+        mode: $mode:ident,
+        option: { debug $($args:tt)* },
+    ) => {
+        $crate::__private::core::compile_error!(
+            "the `debug` option expects a value like `debug = \"set\"`"
+        );
+    };
+    (
+        mode: $mode:ident,
+        option: { default = $($Flag:ident)|+ },
+    ) => {};
+    (
+        mode: $mode:ident,
+        option: { default $($args:tt)* },
+    ) => {
+        $crate::__private::core::compile_error!(
+            "the `default` option expects a value like `default = A | B`"
+        );
+    };
+    (
+        mode: $mode:ident,
+        option: { new },
+    ) => {};
+    (
+        mode: $mode:ident,
+        option: { new $($args:tt)* },
+    ) => {
+        $crate::__private::core::compile_error!(
+            "the `new` option doesn't accept a value"
+        );
+    };
+    (
+        mode: struct,
+        option: { transparent },
+    ) => {};
+    (
+        mode: impl,
+        option: { transparent $($args:tt)* },
+    ) => {
+        $crate::__private::core::compile_error!(
+            "the `transparent` option is only supported on flags types declared with `struct`"
+        );
+    };
+    (
+        mode: $mode:ident,
+        option: { transparent $($args:tt)* },
+    ) => {
+        $crate::__private::core::compile_error!(
+            "the `transparent` option doesn't accept a value"
+        );
+    };
+    (
+        mode: $mode:ident,
+        option: { canonical },
+    ) => {};
+    (
+        mode: $mode:ident,
+        option: { canonical $($args:tt)* },
+    ) => {
+        $crate::__private::core::compile_error!(
+            "the `canonical` option doesn't accept a value"
+        );
+    };
+    (
+        mode: struct,
+        option: { kind = $Kind:ident },
+    ) => {};
+    (
+        mode: impl,
+        option: { kind $($args:tt)* },
+    ) => {
+        $crate::__private::core::compile_error!(
+            "the `kind` option is only supported on flags types declared with `struct`"
+        );
+    };
+    (
+        mode: $mode:ident,
+        option: { kind $($args:tt)* },
+    ) => {
+        $crate::__private::core::compile_error!(
+            "the `kind` option expects a value like `kind = FlagsKind`"
+        );
+    };
+    (
+        mode: $mode:ident,
+        option: { validate },
+    ) => {};
+    (
+        mode: $mode:ident,
+        option: { validate $($args:tt)* },
+    ) => {
+        $crate::__private::core::compile_error!(
+            "the `validate` option doesn't accept a value"
+        );
+    };
+    (
+        mode: $mode:ident,
+        option: { minimal },
+    ) => {};
+    (
+        mode: $mode:ident,
+        option: { minimal $($args:tt)* },
+    ) => {
+        $crate::__private::core::compile_error!(
+            "the `minimal` option doesn't accept a value"
+        );
+    };
+    (
+        mode: $mode:ident,
+        option: { subset_ord },
+    ) => {};
+    (
+        mode: $mode:ident,
+        option: { subset_ord $($args:tt)* },
+    ) => {
+        $crate::__private::core::compile_error!(
+            "the `subset_ord` option doesn't accept a value"
+        );
+    };
+    (
+        mode: $mode:ident,
+        option: { rename_all = "lowercase" },
+    ) => {};
+    (
+        mode: $mode:ident,
+        option: { rename_all = "kebab-case" },
+    ) => {};
+    (
+        mode: $mode:ident,
+        option: { rename_all $($args:tt)* },
+    ) => {
+        $crate::__private::core::compile_error!(
+            "the `rename_all` option expects a value like `rename_all = \"lowercase\"` or `rename_all = \"kebab-case\"`"
+        );
+    };
+    (
+        mode: $mode:ident,
+        option: { disjoint },
+    ) => {};
+    (
+        mode: $mode:ident,
+        option: { disjoint $($args:tt)* },
+    ) => {
+        $crate::__private::core::compile_error!(
+            "the `disjoint` option doesn't accept a value"
+        );
+    };
+    (
+        mode: $mode:ident,
+        option: { bits_eq },
+    ) => {};
+    (
+        mode: $mode:ident,
+        option: { bits_eq $($args:tt)* },
+    ) => {
+        $crate::__private::core::compile_error!(
+            "the `bits_eq` option doesn't accept a value"
+        );
+    };
+    (
+        mode: $mode:ident,
+        option: { bits_ops },
+    ) => {};
+    (
+        mode: $mode:ident,
+        option: { bits_ops $($args:tt)* },
+    ) => {
+        $crate::__private::core::compile_error!(
+            "the `bits_ops` option doesn't accept a value"
+        );
+    };
+    (
+        mode: $mode:ident,
+        option: { valid_mask = $($mask:tt)+ },
+    ) => {};
+    (
+        mode: $mode:ident,
+        option: { valid_mask $($args:tt)* },
+    ) => {
+        $crate::__private::core::compile_error!(
+            "the `valid_mask` option expects a value like `valid_mask = 0xFF`"
+        );
+    };
+    (
+        mode: $mode:ident,
+        option: { docs },
+    ) => {};
+    (
+        mode: $mode:ident,
+        option: { docs $($args:tt)* },
+    ) => {
+        $crate::__private::core::compile_error!(
+            "the `docs` option doesn't accept a value"
+        );
+    };
+    (
+        mode: $mode:ident,
+        option: { $($option:tt)* },
+    ) => {
+        $crate::__private::core::compile_error!($crate::__private::core::concat!(
+            "unrecognized `bitflags` option `",
+            $crate::__private::core::stringify!($($option)*),
+            "`"
+        ));
+    };
+}
+
+/// Generate the given items unless the `minimal` option is used.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __bitflags_unless_minimal {
+    // `minimal`: Skip the items
+    (
+        options: [{ minimal } $($options:tt)*],
+        { $($items:tt)* }
+    ) => {};
+    // Skip any other options
+    (
+        options: [{ $($option:tt)* } $($options:tt)*],
+        { $($items:tt)* }
+    ) => {
+        $crate::__bitflags_unless_minimal! {
+            options: [$($options)*],
+            { $($items)* }
+        }
+    };
+    (
+        options: [],
+        { $($items:tt)* }
+    ) => {
+        $($items)*
+    };
+}
+
+/// Check that a flags type declared in `struct` mode with the `subset_ord` option
+/// doesn't also derive `PartialOrd` or `Ord`.
+///
+/// This macro looks for the option first, then scans the `#[derive(..)]` attributes token-by-token.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __bitflags_check_subset_ord {
+    // `subset_ord`: Check the attributes
+    (
+        options: [{ subset_ord } $($options:tt)*],
+        attrs: [$($attrs:tt)*],
+    ) => {
+        $crate::__bitflags_check_subset_ord! {
+            attrs: [$($attrs)*],
+        }
+    };
+    // Skip any other options
+    (
+        options: [{ $($option:tt)* } $($options:tt)*],
+        attrs: [$($attrs:tt)*],
+    ) => {
+        $crate::__bitflags_check_subset_ord! {
+            options: [$($options)*],
+            attrs: [$($attrs)*],
+        }
+    };
+    (
+        options: [],
+        attrs: [$($attrs:tt)*],
+    ) => {};
+    // `derive`: Check each derived trait
+    (
+        attrs: [#[derive($($derive:tt)*)] $($attrs:tt)*],
+    ) => {
+        $crate::__bitflags_check_subset_ord! {
+            derive: [$($derive)*],
+        }
+
+        $crate::__bitflags_check_subset_ord! {
+            attrs: [$($attrs)*],
+        }
+    };
+    // Skip any other attributes
+    (
+        attrs: [#[$other:ident $($args:tt)*] $($attrs:tt)*],
+    ) => {
+        $crate::__bitflags_check_subset_ord! {
+            attrs: [$($attrs)*],
+        }
+    };
+    (
+        attrs: [],
+    ) => {};
+    (
+        derive: [PartialOrd $($derive:tt)*],
+    ) => {
+        $crate::__private::core::compile_error!(
+            "the `subset_ord` option implements `PartialOrd`, so it can't be combined with `#[derive(PartialOrd)]`"
+        );
+    };
+    (
+        derive: [Ord $($derive:tt)*],
+    ) => {
+        $crate::__private::core::compile_error!(
+            "the `subset_ord` option is a partial order, so it can't be combined with `#[derive(Ord)]`"
+        );
+    };
+    (
+        derive: [$other:tt $($derive:tt)*],
+    ) => {
+        $crate::__bitflags_check_subset_ord! {
+            derive: [$($derive)*],
+        }
+    };
+    (
+        derive: [],
+    ) => {};
+}
+
+/// Implement functions on bitflags types.
+///
+/// We need to be careful about adding new methods and trait implementations here because they
+/// could conflict with items added by the end-user.
+///
+/// Methods use `#[inline]` rather than `#[inline(always)]`. The generated code is compiled in
+/// the end-user's crate anyway, and `#[inline]` is enough for small methods like `bits` and
+/// `contains` to compile down to the same code as operations on the raw bits in optimized builds.
+/// The `ops` benchmark compares them, and `#[inline(always)]` made no measurable difference.
+/// It would force inlining in debug builds too, making them larger and slower to compile.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __impl_bitflags {
+    (
+        $(#[$outer:meta])*
+        $PublicBitFlags:ident: $T:ty {
+            fn empty() $empty:block
+            fn all() $all:block
+            fn max_defined_bit() $max_defined_bit:block
+            fn min_defined_bit() $min_defined_bit:block
+            fn bits($bits0:ident) $bits:block
+            fn from_bits($from_bits0:ident) $from_bits:block
+            fn from_bits_truncate($from_bits_truncate0:ident) $from_bits_truncate:block
+            fn from_bits_retain($from_bits_retain0:ident) $from_bits_retain:block
+            fn from_bits_with_mask($from_bits_with_mask0:ident, $from_bits_with_mask1:ident) $from_bits_with_mask:block
+            fn from_bit_index($from_bit_index0:ident) $from_bit_index:block
+            fn to_be_bytes($to_be_bytes0:ident) $to_be_bytes:block
+            fn to_le_bytes($to_le_bytes0:ident) $to_le_bytes:block
+            fn from_be_bytes($from_be_bytes0:ident) $from_be_bytes:block
+            fn from_le_bytes($from_le_bytes0:ident) $from_le_bytes:block
+            fn from_name($from_name0:ident) $from_name:block
+            fn is_empty($is_empty0:ident) $is_empty:block
+            fn is_all($is_all0:ident) $is_all:block
+            fn is_all_bits($is_all_bits0:ident) $is_all_bits:block
+            fn const_eq($const_eq0:ident, $const_eq1:ident) $const_eq:block
+            fn intersects($intersects0:ident, $intersects1:ident) $intersects:block
+            fn contains($contains0:ident, $contains1:ident) $contains:block
+            fn present_of($present_of0:ident, $present_of1:ident) $present_of:block
+            fn insert($insert0:ident, $insert1:ident) $insert:block
+            fn remove($remove0:ident, $remove1:ident) $remove:block
+            fn remove_exact($remove_exact0:ident, $remove_exact1:ident) $remove_exact:block
+            fn insert_bits($insert_bits0:ident, $insert_bits1:ident) $insert_bits:block
+            fn remove_bits($remove_bits0:ident, $remove_bits1:ident) $remove_bits:block
+            fn toggle($toggle0:ident, $toggle1:ident) $toggle:block
+            fn intersect($intersect0:ident, $intersect1:ident) $intersect:block
+            fn retain_only($retain_only0:ident, $retain_only1:ident) $retain_only:block
+            fn set($set0:ident, $set1:ident, $set2:ident) $set:block
+            fn with_inserted($with_inserted0:ident, $with_inserted1:ident) $with_inserted:block
+            fn with_removed($with_removed0:ident, $with_removed1:ident) $with_removed:block
+            fn with_toggled($with_toggled0:ident, $with_toggled1:ident) $with_toggled:block
+            fn inserted($inserted0:ident, $inserted1:ident) $inserted:block
+            fn removed($removed0:ident, $removed1:ident) $removed:block
+            fn toggled($toggled0:ident, $toggled1:ident) $toggled:block
+            fn truncated($truncated0:ident) $truncated:block
+            fn intersection($intersection0:ident, $intersection1:ident) $intersection:block
+            fn union($union0:ident, $union1:ident) $union:block
+            fn difference($difference0:ident, $difference1:ident) $difference:block
+            fn symmetric_difference($symmetric_difference0:ident, $symmetric_difference1:ident) $symmetric_difference:block
+            fn complement($complement0:ident) $complement:block
+            fn complement_retain($complement_retain0:ident) $complement_retain:block
+        }
+    ) => {
+        #[allow(dead_code, deprecated, unused_attributes)]
+        $(#[$outer])*
+        impl $PublicBitFlags {
+            /// Get a flags value with all bits unset.
+            #[inline]
+            #[must_use]
+            pub const fn empty() -> Self {
+                $empty
+            }
+
+            /// Get a flags value with all known bits set.
+            #[inline]
+            #[must_use]
+            pub const fn all() -> Self {
                 $all
             }
 
+            /// A flags value with all known bits set.
+            ///
+            /// This is the same value as `all()`, the bitwise or of all declared flags, but as an
+            /// associated constant it can be used directly in `const` and `static` initializers.
+            pub const ALL_FLAGS: Self = Self::all();
+
+            /// Get the position of the highest bit set in any known flag.
+            ///
+            /// The returned value is a bit position, not a mask, so a flags type whose highest
+            /// flag is `1 << 3` will return `3`. This method will return `0` if no flags are defined.
+            #[inline]
+            #[must_use]
+            pub const fn max_defined_bit() -> u32 {
+                $max_defined_bit
+            }
+
+            /// Get the position of the lowest bit set in any known flag.
+            ///
+            /// The returned value is a bit position, not a mask, so a flags type whose lowest
+            /// flag is `1 << 3` will return `3`. This method will return `0` if no flags are defined.
+            #[inline]
+            #[must_use]
+            pub const fn min_defined_bit() -> u32 {
+                $min_defined_bit
+            }
+
             /// Get the underlying bits value.
             ///
             /// The returned value is exactly the bits set in this flags value.
             #[inline]
+            #[must_use]
             pub const fn bits(&self) -> $T {
                 let $bits0 = self;
                 $bits
@@ -647,6 +1879,7 @@ macro_rules! __impl_bitflags {
             ///
             /// This method will return `None` if any unknown bits are set.
             #[inline]
+            #[must_use]
             pub const fn from_bits(bits: $T) -> $crate::__private::core::option::Option<Self> {
                 let $from_bits0 = bits;
                 $from_bits
@@ -654,6 +1887,7 @@ macro_rules! __impl_bitflags {
 
             /// Convert from a bits value, unsetting any unknown bits.
             #[inline]
+            #[must_use]
             pub const fn from_bits_truncate(bits: $T) -> Self {
                 let $from_bits_truncate0 = bits;
                 $from_bits_truncate
@@ -661,16 +1895,82 @@ macro_rules! __impl_bitflags {
 
             /// Convert from a bits value exactly.
             #[inline]
+            #[must_use]
             pub const fn from_bits_retain(bits: $T) -> Self {
                 let $from_bits_retain0 = bits;
                 $from_bits_retain
             }
 
+            /// Convert from a bits value, allowing unknown bits only if they're in `allowed_extra`.
+            ///
+            /// This method will return `None` if any bits are set that don't correspond to
+            /// defined flags and aren't in `allowed_extra`. Any allowed unknown bits are retained.
+            /// This is useful for formats that reserve some bits that can be carried through,
+            /// but require others to be zero.
+            #[inline]
+            #[must_use]
+            pub const fn from_bits_with_mask(bits: $T, allowed_extra: $T) -> $crate::__private::core::option::Option<Self> {
+                let $from_bits_with_mask0 = bits;
+                let $from_bits_with_mask1 = allowed_extra;
+                $from_bits_with_mask
+            }
+
+            /// Convert from the index of a single bit.
+            ///
+            /// This method will return `None` if `index` is greater than or equal to the number of bits in
+            /// the bits type. The bit is retained even if it's unknown, so the returned flags value may not
+            /// correspond to any named flag.
+            #[inline]
+            #[must_use]
+            pub const fn from_bit_index(index: u32) -> $crate::__private::core::option::Option<Self> {
+                let $from_bit_index0 = index;
+                $from_bit_index
+            }
+
+            /// Get the bits value as a byte array in big-endian (network) order.
+            #[inline]
+            #[must_use]
+            #[allow(clippy::wrong_self_convention)]
+            pub const fn to_be_bytes(&self) -> [u8; $crate::__private::core::mem::size_of::<$T>()] {
+                let $to_be_bytes0 = self;
+                $to_be_bytes
+            }
+
+            /// Get the bits value as a byte array in little-endian order.
+            #[inline]
+            #[must_use]
+            #[allow(clippy::wrong_self_convention)]
+            pub const fn to_le_bytes(&self) -> [u8; $crate::__private::core::mem::size_of::<$T>()] {
+                let $to_le_bytes0 = self;
+                $to_le_bytes
+            }
+
+            /// Convert from a byte array in big-endian (network) order exactly.
+            ///
+            /// Any unknown bits are retained, like `from_bits_retain`.
+            #[inline]
+            #[must_use]
+            pub const fn from_be_bytes(bytes: [u8; $crate::__private::core::mem::size_of::<$T>()]) -> Self {
+                let $from_be_bytes0 = bytes;
+                $from_be_bytes
+            }
+
+            /// Convert from a byte array in little-endian order exactly.
+            ///
+            /// Any unknown bits are retained, like `from_bits_retain`.
+            #[inline]
+            #[must_use]
+            pub const fn from_le_bytes(bytes: [u8; $crate::__private::core::mem::size_of::<$T>()]) -> Self {
+                let $from_le_bytes0 = bytes;
+                $from_le_bytes
+            }
+
             /// Get a flags value with the bits of a flag with the given name set.
             ///
             /// This method will return `None` if `name` is empty or doesn't
             /// correspond to any named flag.
             #[inline]
+            #[must_use]
             pub fn from_name(name: &str) -> $crate::__private::core::option::Option<Self> {
                 let $from_name0 = name;
                 $from_name
@@ -678,20 +1978,51 @@ macro_rules! __impl_bitflags {
 
             /// Whether all bits in this flags value are unset.
             #[inline]
+            #[must_use]
             pub const fn is_empty(&self) -> bool {
                 let $is_empty0 = self;
                 $is_empty
             }
 
             /// Whether all known bits in this flags value are set.
+            ///
+            /// Zero-bit flags don't set any bits in `all()`, so they don't affect the result.
             #[inline]
+            #[must_use]
             pub const fn is_all(&self) -> bool {
                 let $is_all0 = self;
                 $is_all
             }
 
+            /// Whether every bit of the underlying bits type is set in this flags value.
+            ///
+            /// This is different from `is_all`, which only checks the bits of defined flags.
+            /// A value containing `all()` is always `is_all`, but it's only `is_all_bits` if
+            /// any unknown bits are also set, like through `from_bits_retain`.
+            #[inline]
+            #[must_use]
+            pub const fn is_all_bits(&self) -> bool {
+                let $is_all_bits0 = self;
+                $is_all_bits
+            }
+
+            /// Whether all bits in a source flags value are the same as the bits in a target flags value.
+            ///
+            /// This method is a `const` alternative to `==` for use in const contexts, like
+            /// `const _: () = assert!(Flags::A.const_eq(&Flags::from_bits_retain(1)));`.
+            /// It compares the underlying bits in the same way as a derived `PartialEq`,
+            /// which is still used for runtime comparisons with `==`.
+            #[inline]
+            #[must_use]
+            pub const fn const_eq(&self, other: &Self) -> bool {
+                let $const_eq0 = self;
+                let $const_eq1 = other;
+                $const_eq
+            }
+
             /// Whether any set bits in a source flags value are also set in a target flags value.
             #[inline]
+            #[must_use]
             pub const fn intersects(&self, other: Self) -> bool {
                 let $intersects0 = self;
                 let $intersects1 = other;
@@ -699,13 +2030,31 @@ macro_rules! __impl_bitflags {
             }
 
             /// Whether all set bits in a source flags value are also set in a target flags value.
+            ///
+            /// If `other` has multiple bits set, like a multi-bit flag, then every one of them
+            /// must be set for this method to return `true`. Use `intersects` to check whether
+            /// any of them are set.
             #[inline]
+            #[must_use]
             pub const fn contains(&self, other: Self) -> bool {
                 let $contains0 = self;
                 let $contains1 = other;
                 $contains
             }
 
+            /// Get the flags in a group that are set in a flags value.
+            ///
+            /// This method is the same as `intersection`, but is named for checking which flags
+            /// from a group, like a set of permissions, are present. Use `intersects` to check
+            /// whether any of them are.
+            #[inline]
+            #[must_use]
+            pub const fn present_of(&self, group: Self) -> Self {
+                let $present_of0 = self;
+                let $present_of1 = group;
+                $present_of
+            }
+
             /// The bitwise or (`|`) of the bits in two flags values.
             #[inline]
             pub fn insert(&mut self, other: Self) {
@@ -725,6 +2074,40 @@ macro_rules! __impl_bitflags {
                 $remove
             }
 
+            /// The intersection of a source flags value with the complement of a target flags value (`&!`),
+            /// if all bits in `other` are set.
+            ///
+            /// This method will return an error, leaving this flags value unchanged, if `self` doesn't
+            /// contain `other`.
+            #[inline]
+            pub fn remove_exact(&mut self, other: Self) -> $crate::__private::core::result::Result<(), $crate::convert::NotPresent> {
+                let $remove_exact0 = self;
+                let $remove_exact1 = other;
+                $remove_exact
+            }
+
+            /// The bitwise or (`|`) of the bits in this flags value and a raw bits value.
+            ///
+            /// This method is the same as `insert` with `from_bits_retain(bits)`. No truncation
+            /// occurs, so any unknown bits in `bits` will also be set.
+            #[inline]
+            pub fn insert_bits(&mut self, bits: $T) {
+                let $insert_bits0 = self;
+                let $insert_bits1 = bits;
+                $insert_bits
+            }
+
+            /// The intersection of this flags value with the complement of a raw bits value (`&!`).
+            ///
+            /// This method is the same as `remove` with `from_bits_retain(bits)`. No truncation
+            /// occurs, so any unknown bits in `bits` will also be unset.
+            #[inline]
+            pub fn remove_bits(&mut self, bits: $T) {
+                let $remove_bits0 = self;
+                let $remove_bits1 = bits;
+                $remove_bits
+            }
+
             /// The bitwise exclusive-or (`^`) of the bits in two flags values.
             #[inline]
             pub fn toggle(&mut self, other: Self) {
@@ -733,6 +2116,26 @@ macro_rules! __impl_bitflags {
                 $toggle
             }
 
+            /// The bitwise and (`&`) of the bits in two flags values.
+            ///
+            /// This method is the in-place form of `intersection`.
+            #[inline]
+            pub fn intersect(&mut self, other: Self) {
+                let $intersect0 = self;
+                let $intersect1 = other;
+                $intersect
+            }
+
+            /// The bitwise and (`&`) of the bits in two flags values.
+            ///
+            /// This method is the same as `intersect`. It only keeps bits that are also set in `other`.
+            #[inline]
+            pub fn retain_only(&mut self, other: Self) {
+                let $retain_only0 = self;
+                let $retain_only1 = other;
+                $retain_only
+            }
+
             /// Call `insert` when `value` is `true` or `remove` when `value` is `false`.
             #[inline]
             pub fn set(&mut self, other: Self, value: bool) {
@@ -742,6 +2145,82 @@ macro_rules! __impl_bitflags {
                 $set
             }
 
+            /// The bitwise or (`|`) of the bits in two flags values, returning `self` for chaining.
+            ///
+            /// This method is the same as `insert`, but can be chained, like
+            /// `flags.with_inserted(A).with_removed(B)`.
+            #[inline]
+            pub fn with_inserted(&mut self, other: Self) -> &mut Self {
+                let $with_inserted0 = self;
+                let $with_inserted1 = other;
+                $with_inserted
+            }
+
+            /// The intersection of a source flags value with the complement of a target flags value (`&!`),
+            /// returning `self` for chaining.
+            ///
+            /// This method is the same as `remove`, but can be chained.
+            #[inline]
+            pub fn with_removed(&mut self, other: Self) -> &mut Self {
+                let $with_removed0 = self;
+                let $with_removed1 = other;
+                $with_removed
+            }
+
+            /// The bitwise exclusive-or (`^`) of the bits in two flags values, returning `self` for chaining.
+            ///
+            /// This method is the same as `toggle`, but can be chained.
+            #[inline]
+            pub fn with_toggled(&mut self, other: Self) -> &mut Self {
+                let $with_toggled0 = self;
+                let $with_toggled1 = other;
+                $with_toggled
+            }
+
+            /// The bitwise or (`|`) of the bits in two flags values.
+            ///
+            /// This method is the consuming form of `insert`, so it can be used in `const` contexts.
+            #[inline]
+            #[must_use]
+            pub const fn inserted(self, other: Self) -> Self {
+                let $inserted0 = self;
+                let $inserted1 = other;
+                $inserted
+            }
+
+            /// The intersection of a source flags value with the complement of a target flags value (`&!`).
+            ///
+            /// This method is the consuming form of `remove`, so it can be used in `const` contexts.
+            #[inline]
+            #[must_use]
+            pub const fn removed(self, other: Self) -> Self {
+                let $removed0 = self;
+                let $removed1 = other;
+                $removed
+            }
+
+            /// The bitwise exclusive-or (`^`) of the bits in two flags values.
+            ///
+            /// This method is the consuming form of `toggle`, so it can be used in `const` contexts.
+            #[inline]
+            #[must_use]
+            pub const fn toggled(self, other: Self) -> Self {
+                let $toggled0 = self;
+                let $toggled1 = other;
+                $toggled
+            }
+
+            /// Remove any unknown bits from the flags value.
+            ///
+            /// This method is the same as `Self::from_bits_truncate(self.bits())`. It's the consuming
+            /// form of `Flags::truncate`, so it can be used in `const` contexts.
+            #[inline]
+            #[must_use]
+            pub const fn truncated(self) -> Self {
+                let $truncated0 = self;
+                $truncated
+            }
+
             /// The bitwise and (`&`) of the bits in two flags values.
             #[inline]
             #[must_use]
@@ -782,12 +2261,26 @@ macro_rules! __impl_bitflags {
             }
 
             /// The bitwise negation (`!`) of the bits in a flags value, truncating the result.
+            ///
+            /// Any unknown bits are unset in the result. Use `complement_retain` to keep them.
             #[inline]
             #[must_use]
             pub const fn complement(self) -> Self {
                 let $complement0 = self;
                 $complement
             }
+
+            /// The bitwise negation (`!`) of the bits in a flags value, retaining all bits.
+            ///
+            /// Unlike `complement`, the result isn't truncated, so any unknown bits that are unset
+            /// in this flags value will be set in the result. This is the same as
+            /// `from_bits_retain(!self.bits())`.
+            #[inline]
+            #[must_use]
+            pub const fn complement_retain(self) -> Self {
+                let $complement_retain0 = self;
+                $complement_retain
+            }
         }
     };
 }
@@ -840,54 +2333,294 @@ macro_rules! __impl_bitflags {
 /// and each pattern. This allows for correct matching of bitflag combinations, which is not possible
 /// with a regular match expression due to the way bitflags are implemented.
 ///
-/// Patterns are evaluated in order.
+/// Patterns are evaluated in order.
+#[macro_export]
+macro_rules! bitflags_match {
+    ($operation:expr, {
+        $($t:tt)*
+    }) => {
+        // Expand to a closure so we can use `return`
+        // This makes it possible to apply attributes to the "match arms"
+        (|| {
+            $crate::__bitflags_match!($operation, { $($t)* })
+        })()
+    };
+}
+
+/// Expand the `bitflags_match` macro
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __bitflags_match {
+    // Eat an optional `,` following a block match arm
+    ($operation:expr, { $pattern:expr => { $($body:tt)* } , $($t:tt)+ }) => {
+        $crate::__bitflags_match!($operation, { $pattern => { $($body)* } $($t)+ })
+    };
+    // Expand a block match arm `A => { .. }`
+    ($operation:expr, { $pattern:expr => { $($body:tt)* } $($t:tt)+ }) => {
+        {
+            if $operation == $pattern {
+                return {
+                    $($body)*
+                };
+            }
+
+            $crate::__bitflags_match!($operation, { $($t)+ })
+        }
+    };
+    // Expand an expression match arm `A => x,`
+    ($operation:expr, { $pattern:expr => $body:expr , $($t:tt)+ }) => {
+        {
+            if $operation == $pattern {
+                return $body;
+            }
+
+            $crate::__bitflags_match!($operation, { $($t)+ })
+        }
+    };
+    // Expand the default case
+    ($operation:expr, { _ => $default:expr $(,)? }) => {
+        $default
+    }
+}
+
+/// A macro that produces a bits value with only the bit at a given position set.
+///
+/// `bit!(n)` expands to `1 << n`, where the type of the result is inferred from where it's used.
+/// It's intended for declaring single-bit flags by their position:
+///
+/// ```rust
+/// use bitflags::{bit, bitflags};
+///
+/// bitflags! {
+///     struct Flags: u8 {
+///         const A = bit!(0);
+///         const B = bit!(1);
+///         const C = bit!(7);
+///     }
+/// }
+///
+/// assert_eq!(0b1000_0011, (Flags::A | Flags::B | Flags::C).bits());
+/// ```
+///
+/// In const contexts, like the values of flags, a position that's out of range for the bits
+/// type is a compile error:
+///
+/// ```compile_fail
+/// use bitflags::{bit, bitflags};
+///
+/// bitflags! {
+///     struct Flags: u8 {
+///         const A = bit!(8);
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! bit {
+    ($n:expr) => {
+        1 << $n
+    };
+}
+
+/// A macro that produces a bits value with a contiguous range of bits set.
+///
+/// `bits_range!(start..end)` sets the bits at positions `start` up to, but not including, `end`,
+/// and `bits_range!(start..=end)` also sets the bit at `end`. The type of the result is inferred
+/// from where it's used. It's intended for declaring multi-bit flags that cover a field in a register:
+///
+/// ```rust
+/// use bitflags::{bitflags, bits_range};
+///
+/// bitflags! {
+///     struct Register: u16 {
+///         const ENABLE = 1;
+///         const MODE = bits_range!(4..8);
+///         const PRESCALER = bits_range!(8..=15);
+///     }
+/// }
+///
+/// assert_eq!(0b0000_0000_1111_0000, Register::MODE.bits());
+/// assert_eq!(0b1111_1111_0000_0000, Register::PRESCALER.bits());
+/// ```
+///
+/// An empty range, like `bits_range!(4..4)`, produces an empty bits value. In const contexts,
+/// like the values of flags, a range that ends past the width of the bits type is a compile error:
+///
+/// ```compile_fail
+/// use bitflags::{bitflags, bits_range};
+///
+/// bitflags! {
+///     struct Flags: u8 {
+///         const A = bits_range!(4..9);
+///     }
+/// }
+/// ```
+///
+/// So is a range whose start is after its end:
+///
+/// ```compile_fail
+/// use bitflags::{bitflags, bits_range};
+///
+/// bitflags! {
+///     struct Flags: u8 {
+///         const A = bits_range!(6..4);
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! bits_range {
+    ($start:tt ..= $end:tt) => {
+        $crate::bits_range!($start..($end + 1))
+    };
+    ($start:tt .. $end:tt) => {{
+        let start = $start;
+        let end = $end;
+
+        // Fail if the range is reversed instead of producing an empty mask
+        #[allow(clippy::out_of_bounds_indexing)]
+        let _ = [(); 1][(start > end) as usize];
+
+        let mut mask = 0;
+        let mut i = start;
+        while i < end {
+            mask |= 1 << i;
+            i += 1;
+        }
+
+        mask
+    }};
+}
+
+/// A macro that produces a flags value from the union of a list of its named flags.
+///
+/// `flags!(Flags: A | B | C)` expands to `Flags::empty().union(Flags::A).union(Flags::B).union(Flags::C)`.
+/// It's intended as a shorthand for constructing values from long lists of flags at call sites,
+/// and can be used in const contexts:
+///
+/// ```rust
+/// use bitflags::flags;
+///
+/// mod ffi {
+///     bitflags::bitflags! {
+///         #[derive(Debug, PartialEq, Eq)]
+///         pub struct Flags: u8 {
+///             const A = 1;
+///             const B = 1 << 1;
+///             const C = 1 << 2;
+///         }
+///     }
+/// }
+///
+/// const AC: ffi::Flags = flags!(ffi::Flags: A | C);
+///
+/// assert_eq!(ffi::Flags::A | ffi::Flags::C, AC);
+/// assert_eq!(ffi::Flags::B, flags!(ffi::Flags: B));
+/// ```
+///
+/// An empty list of flags produces an empty flags value:
+///
+/// ```rust
+/// # use bitflags::{bitflags, flags};
+/// # bitflags! {
+/// #     #[derive(Debug, PartialEq, Eq)]
+/// #     pub struct Flags: u8 {
+/// #         const A = 1;
+/// #     }
+/// # }
+/// assert_eq!(Flags::empty(), flags!(Flags:));
+/// ```
 #[macro_export]
-macro_rules! bitflags_match {
-    ($operation:expr, {
-        $($t:tt)*
-    }) => {
-        // Expand to a closure so we can use `return`
-        // This makes it possible to apply attributes to the "match arms"
-        (|| {
-            $crate::__bitflags_match!($operation, { $($t)* })
-        })()
+macro_rules! flags {
+    ($Flags:path: $($Flag:ident)|*) => {
+        <$Flags>::empty()$(.union(<$Flags>::$Flag))*
     };
 }
 
-/// Expand the `bitflags_match` macro
+/// Generate a flags type whose bits are the discriminants of a C-style enum.
+///
+/// `from_enum!` accepts the same input as `bitflags!`, except that the value of each flag
+/// is a path to an enum variant. Each flag is declared as `1 << (Variant as u32)`, so the
+/// discriminant of the variant is the position of its bit:
+///
+/// ```rust
+/// use bitflags::from_enum;
+///
+/// #[derive(Clone, Copy)]
+/// pub enum Permission {
+///     Read = 0,
+///     Write = 1,
+///     Execute = 2,
+///     Admin = 7,
+/// }
+///
+/// from_enum! {
+///     #[derive(Debug, PartialEq, Eq)]
+///     pub struct Permissions: u8 {
+///         const READ = Permission::Read;
+///         const WRITE = Permission::Write;
+///         const EXECUTE = Permission::Execute;
+///         const ADMIN = Permission::Admin;
+///     }
+/// }
+///
+/// assert_eq!(0b1000_0011, (Permissions::READ | Permissions::WRITE | Permissions::ADMIN).bits());
+/// ```
+///
+/// The generated code is a regular `bitflags!` invocation, so options and attributes on the
+/// flags type and its flags work the same way. A discriminant that's out of range for the
+/// bits type is a compile error.
 #[macro_export]
-#[doc(hidden)]
-macro_rules! __bitflags_match {
-    // Eat an optional `,` following a block match arm
-    ($operation:expr, { $pattern:expr => { $($body:tt)* } , $($t:tt)+ }) => {
-        $crate::__bitflags_match!($operation, { $pattern => { $($body)* } $($t)+ })
-    };
-    // Expand a block match arm `A => { .. }`
-    ($operation:expr, { $pattern:expr => { $($body:tt)* } $($t:tt)+ }) => {
-        {
-            if $operation == $pattern {
-                return {
-                    $($body)*
-                };
+macro_rules! from_enum {
+    (
+        $(#[$outer:ident $($outer_args:tt)*])*
+        $vis:vis struct $BitFlags:ident: $T:ty {
+            $(
+                $(#[$inner:ident $($args:tt)*])*
+                const $Flag:tt = $variant:path;
+            )*
+        }
+
+        $($t:tt)*
+    ) => {
+        $crate::bitflags! {
+            $(#[$outer $($outer_args)*])*
+            $vis struct $BitFlags: $T {
+                $(
+                    $(#[$inner $($args)*])*
+                    const $Flag = 1 << ($variant as u32);
+                )*
             }
+        }
 
-            $crate::__bitflags_match!($operation, { $($t)+ })
+        $crate::from_enum! {
+            $($t)*
         }
     };
-    // Expand an expression match arm `A => x,`
-    ($operation:expr, { $pattern:expr => $body:expr , $($t:tt)+ }) => {
-        {
-            if $operation == $pattern {
-                return $body;
+    (
+        $(#[$outer:ident $($outer_args:tt)*])*
+        impl $BitFlags:ident: $T:ty {
+            $(
+                $(#[$inner:ident $($args:tt)*])*
+                const $Flag:tt = $variant:path;
+            )*
+        }
+
+        $($t:tt)*
+    ) => {
+        $crate::bitflags! {
+            $(#[$outer $($outer_args)*])*
+            impl $BitFlags: $T {
+                $(
+                    $(#[$inner $($args)*])*
+                    const $Flag = 1 << ($variant as u32);
+                )*
             }
+        }
 
-            $crate::__bitflags_match!($operation, { $($t)+ })
+        $crate::from_enum! {
+            $($t)*
         }
     };
-    // Expand the default case
-    ($operation:expr, { _ => $default:expr $(,)? }) => {
-        $default
-    }
+    () => {};
 }
 
 /// A macro that processed the input to `bitflags!` and shuffles attributes around
@@ -900,6 +2633,9 @@ macro_rules! __bitflags_match {
 ///
 /// If you find yourself with an attribute that should be considered expression-safe
 /// and isn't, it can be added here.
+///
+/// A `cfg_attr` is split into its individual attributes, so any `cfg` it contains is
+/// propagated under the same predicate while everything else is dropped.
 #[macro_export]
 #[doc(hidden)]
 macro_rules! __bitflags_expr_safe_attrs {
@@ -949,6 +2685,121 @@ macro_rules! __bitflags_expr_safe_attrs {
         }
     };
     // Process the next attribute on the current flag
+    // `cfg_attr(pred, cfg(..))`: The conditional `cfg` should be propagated to expressions
+    (
+        expr: { $e:expr },
+            attrs: {
+            unprocessed: [
+                #[cfg_attr($pred:meta, cfg $cfg:tt $(, $($rest:tt)*)?)]
+                $($attrs_rest:tt)*
+            ],
+            processed: [$($expr:tt)*],
+        },
+    ) => {
+        $crate::__bitflags_expr_safe_attrs! {
+            expr: { $e },
+            attrs: {
+                unprocessed: [
+                    // Any remaining attributes in the `cfg_attr` are processed next
+                    $(#[cfg_attr($pred, $($rest)*)])?
+                    $($attrs_rest)*
+                ],
+                processed: [
+                    $($expr)*
+                    #[cfg_attr($pred, cfg $cfg)]
+                ],
+            },
+        }
+    };
+    // `cfg_attr(pred, cfg_attr(inner, ..))`: Nested `cfg_attr`s are flattened by combining their predicates
+    (
+        expr: { $e:expr },
+            attrs: {
+            unprocessed: [
+                #[cfg_attr($pred:meta, cfg_attr($inner:meta, $($inner_attrs:tt)*) $(, $($rest:tt)*)?)]
+                $($attrs_rest:tt)*
+            ],
+            processed: [$($expr:tt)*],
+        },
+    ) => {
+        $crate::__bitflags_expr_safe_attrs! {
+            expr: { $e },
+            attrs: {
+                unprocessed: [
+                    #[cfg_attr(all($pred, $inner), $($inner_attrs)*)]
+                    $(#[cfg_attr($pred, $($rest)*)])?
+                    $($attrs_rest)*
+                ],
+                processed: [$($expr)*],
+            },
+        }
+    };
+    // `cfg_attr(pred)`: All the attributes in the `cfg_attr` have been processed
+    (
+        expr: { $e:expr },
+            attrs: {
+            unprocessed: [
+                #[cfg_attr($pred:meta $(,)?)]
+                $($attrs_rest:tt)*
+            ],
+            processed: [$($expr:tt)*],
+        },
+    ) => {
+        $crate::__bitflags_expr_safe_attrs! {
+            expr: { $e },
+            attrs: {
+                unprocessed: [
+                    $($attrs_rest)*
+                ],
+                processed: [$($expr)*],
+            },
+        }
+    };
+    // `cfg_attr(pred, $other, ..)`: Any other attribute in the `cfg_attr` is skipped
+    // one token at a time until the `,` before the next attribute
+    (
+        expr: { $e:expr },
+            attrs: {
+            unprocessed: [
+                #[cfg_attr($pred:meta, , $($rest:tt)*)]
+                $($attrs_rest:tt)*
+            ],
+            processed: [$($expr:tt)*],
+        },
+    ) => {
+        $crate::__bitflags_expr_safe_attrs! {
+            expr: { $e },
+            attrs: {
+                unprocessed: [
+                    #[cfg_attr($pred, $($rest)*)]
+                    $($attrs_rest)*
+                ],
+                processed: [$($expr)*],
+            },
+        }
+    };
+    (
+        expr: { $e:expr },
+            attrs: {
+            unprocessed: [
+                #[cfg_attr($pred:meta, $skip:tt $($rest:tt)*)]
+                $($attrs_rest:tt)*
+            ],
+            processed: [$($expr:tt)*],
+        },
+    ) => {
+        $crate::__bitflags_expr_safe_attrs! {
+            expr: { $e },
+            attrs: {
+                unprocessed: [
+                    #[cfg_attr($pred, $($rest)*)]
+                    $($attrs_rest)*
+                ],
+                processed: [$($expr)*],
+            },
+        }
+    };
+    // Process the next attribute on the current flag
     // `$other`: The next flag should not be propagated to expressions
     (
         expr: { $e:expr },
@@ -987,6 +2838,223 @@ macro_rules! __bitflags_expr_safe_attrs {
     }
 }
 
+/// A macro that collects the doc comments in the attributes on a flag.
+///
+/// This macro is a token-tree muncher that expands to `Some` with the values of any
+/// `#[doc = ..]` attributes joined by newlines, or `None` if there aren't any.
+/// Doc comments are only collected if the `docs` option is used.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __bitflags_doc {
+    // No `docs` option: Don't collect any doc comments
+    (
+        docs: false,
+        $(#[$inner:ident $($args:tt)*])*
+    ) => {
+        $crate::__private::core::option::Option::None
+    };
+    // Entrypoint: Move all attributes into an `unprocessed` list
+    // where they'll be munched one-at-a-time
+    (
+        docs: true,
+        $(#[$inner:ident $($args:tt)*])*
+    ) => {
+        $crate::__bitflags_doc! {
+            unprocessed: [$(#[$inner $($args)*])*],
+            docs: [],
+        }
+    };
+    // Process the next attribute
+    // `doc = ..`: Add the doc string
+    (
+        unprocessed: [
+            #[doc = $doc:expr]
+            $($attrs_rest:tt)*
+        ],
+        docs: [$($docs:expr,)*],
+    ) => {
+        $crate::__bitflags_doc! {
+            unprocessed: [$($attrs_rest)*],
+            docs: [$($docs,)* $doc,],
+        }
+    };
+    // Process the next attribute
+    // `$other`: Skip the attribute
+    (
+        unprocessed: [
+            #[$other:ident $($args:tt)*]
+            $($attrs_rest:tt)*
+        ],
+        docs: [$($docs:expr,)*],
+    ) => {
+        $crate::__bitflags_doc! {
+            unprocessed: [$($attrs_rest)*],
+            docs: [$($docs,)*],
+        }
+    };
+    // Once all attributes are processed, join any doc strings
+    (
+        unprocessed: [],
+        docs: [],
+    ) => {
+        $crate::__private::core::option::Option::None
+    };
+    (
+        unprocessed: [],
+        docs: [$first:expr, $($docs:expr,)*],
+    ) => {
+        $crate::__private::core::option::Option::Some(
+            $crate::__private::core::concat!($first $(, "\n", $docs)*)
+        )
+    };
+}
+
+/// A macro that collects the group in the attributes on a flag.
+///
+/// This macro is a token-tree muncher that expands to `Some` with the value of a
+/// `#[bitflags(group = ..)]` attribute, or `None` if there isn't one.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __bitflags_group {
+    // Entrypoint: Move all attributes into an `unprocessed` list
+    // where they'll be munched one-at-a-time
+    (
+        $(#[$inner:ident $($args:tt)*])*
+    ) => {
+        $crate::__bitflags_group! {
+            unprocessed: [$(#[$inner $($args)*])*],
+            group: [],
+        }
+    };
+    // Process the next attribute
+    // `bitflags(group = ..)`: Set the group
+    (
+        unprocessed: [
+            #[bitflags(group = $group:expr)]
+            $($attrs_rest:tt)*
+        ],
+        group: [],
+    ) => {
+        $crate::__bitflags_group! {
+            unprocessed: [$($attrs_rest)*],
+            group: [$group],
+        }
+    };
+    (
+        unprocessed: [
+            #[bitflags(group $($args:tt)*)]
+            $($attrs_rest:tt)*
+        ],
+        group: [$($group:expr)*],
+    ) => {
+        $crate::__private::core::compile_error!(
+            "a flag can only have a single group, with a value like `group = \"name\"`"
+        )
+    };
+    (
+        unprocessed: [
+            #[bitflags($($args:tt)*)]
+            $($attrs_rest:tt)*
+        ],
+        group: [$($group:expr)*],
+    ) => {
+        $crate::__private::core::compile_error!($crate::__private::core::concat!(
+            "unrecognized `bitflags` option on a flag `",
+            $crate::__private::core::stringify!($($args)*),
+            "`"
+        ))
+    };
+    // Process the next attribute
+    // `$other`: Skip the attribute
+    (
+        unprocessed: [
+            #[$other:ident $($args:tt)*]
+            $($attrs_rest:tt)*
+        ],
+        group: [$($group:expr)*],
+    ) => {
+        $crate::__bitflags_group! {
+            unprocessed: [$($attrs_rest)*],
+            group: [$($group)*],
+        }
+    };
+    // Once all attributes are processed, return the group
+    (
+        unprocessed: [],
+        group: [],
+    ) => {
+        $crate::__private::core::option::Option::None
+    };
+    (
+        unprocessed: [],
+        group: [$group:expr],
+    ) => {
+        $crate::__private::core::option::Option::Some($group)
+    };
+}
+
+/// A macro that applies the attributes on a flag to an item.
+///
+/// This macro is a token-tree muncher that removes any `#[bitflags(..)]` attributes,
+/// which are only understood by the `bitflags` macro, and passes all others through.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __bitflags_item_attrs {
+    // Entrypoint: Move all attributes into an `unprocessed` list
+    // where they'll be munched one-at-a-time
+    (
+        $(#[$inner:ident $($args:tt)*])*
+        { $($item:tt)* }
+    ) => {
+        $crate::__bitflags_item_attrs! {
+            item: { $($item)* },
+            unprocessed: [$(#[$inner $($args)*])*],
+            processed: [],
+        }
+    };
+    // Process the next attribute
+    // `bitflags`: Remove the attribute
+    (
+        item: { $($item:tt)* },
+        unprocessed: [
+            #[bitflags $($args:tt)*]
+            $($attrs_rest:tt)*
+        ],
+        processed: [$($attrs:tt)*],
+    ) => {
+        $crate::__bitflags_item_attrs! {
+            item: { $($item)* },
+            unprocessed: [$($attrs_rest)*],
+            processed: [$($attrs)*],
+        }
+    };
+    // Process the next attribute
+    // `$other`: Pass the attribute through to the item
+    (
+        item: { $($item:tt)* },
+        unprocessed: [
+            #[$other:ident $($args:tt)*]
+            $($attrs_rest:tt)*
+        ],
+        processed: [$($attrs:tt)*],
+    ) => {
+        $crate::__bitflags_item_attrs! {
+            item: { $($item)* },
+            unprocessed: [$($attrs_rest)*],
+            processed: [$($attrs)* #[$other $($args)*]],
+        }
+    };
+    // Once all attributes are processed, generate the item
+    (
+        item: { $($item:tt)* },
+        unprocessed: [],
+        processed: [$($attrs:tt)*],
+    ) => {
+        $($attrs)*
+        $($item)*
+    };
+}
+
 /// Implement a flag, which may be a wildcard `_`.
 #[macro_export]
 #[doc(hidden)]
@@ -4,9 +4,10 @@ Parsing flags from text.
 Format and parse a flags value as text using the following grammar:
 
 - _Flags:_ (_Whitespace_ _Flag_ _Whitespace_)`|`*
-- _Flag:_ _Name_ | _Hex Number_
+- _Flag:_ _Name_ | _Hex Number_ | _Negative Number_
 - _Name:_ The name of any defined flag
 - _Hex Number_: `0x`([0-9a-fA-F])*
+- _Negative Number_: `-`([0-9])*, only for signed bits types
 - _Whitespace_: (\s)*
 
 As an example, this is how `Flags::A | Flags::B | 0x0c` can be represented as text:
@@ -26,11 +27,26 @@ Note that identifiers are *case-sensitive*, so the following is *not equivalent*
 ```text
 a|b|0x0C
 ```
+
+## Round-tripping
+
+Any flags value written by [`to_writer`] or [`to_writer_with_options`] parses back to exactly the
+same bits with [`from_str`], including any unknown bits. The named flags that are written cover
+the same bits as the source value, and any remaining bits are written as a single hex number.
+Hex numbers are the bits of the value, so for signed bits types they're parsed in two's complement,
+like `0x80` for `i8::MIN`.
+
+[`to_writer_truncate`] and [`from_str_truncate`] round-trip the known bits of a flags value.
+[`to_writer_strict`] and [`from_str_strict`] round-trip the named flags that are fully contained
+in a flags value.
 */
 
 #![allow(clippy::let_unit_value)]
 
-use core::fmt::{self, Write};
+use core::{
+    fmt::{self, Write},
+    ops::Range,
+};
 
 use crate::{Bits, Flags};
 
@@ -39,7 +55,25 @@ Write a flags value as text.
 
 Any bits that aren't part of a contained flag will be formatted as a hex number.
 */
-pub fn to_writer<B: Flags>(flags: &B, mut writer: impl Write) -> Result<(), fmt::Error>
+pub fn to_writer<B: Flags>(flags: &B, writer: impl Write) -> Result<(), fmt::Error>
+where
+    B::Bits: WriteHex,
+{
+    to_writer_with_options(flags, writer, WriteOptions::new())
+}
+
+/**
+Write a flags value as text, using the given [`WriteOptions`].
+
+Any bits that aren't part of a contained flag will be formatted as a hex number.
+
+With the default options, this function is equivalent to [`to_writer`].
+*/
+pub fn to_writer_with_options<B: Flags>(
+    flags: &B,
+    mut writer: impl Write,
+    options: WriteOptions,
+) -> Result<(), fmt::Error>
 where
     B::Bits: WriteHex,
 {
@@ -60,7 +94,7 @@ where
         }
 
         first = false;
-        writer.write_str(name)?;
+        B::RENAME_ALL.write_name(name, &mut writer)?;
     }
 
     // Append any extra bits that correspond to flags to the end of the format
@@ -71,12 +105,152 @@ where
         }
 
         writer.write_str("0x")?;
-        remaining.write_hex(writer)?;
+
+        if options.pad_hex {
+            write_hex_padded(&remaining, B::bits_width(), writer)?;
+        } else {
+            remaining.write_hex(writer)?;
+        }
     }
 
     fmt::Result::Ok(())
 }
 
+/**
+Write a flags value as UTF-8 text to an I/O stream.
+
+This produces the same output as [`to_writer`], but writes it straight to `writer`
+without formatting into an intermediate `String` first.
+*/
+#[cfg(feature = "std")]
+pub fn to_io_writer<B: Flags, W: std::io::Write>(flags: &B, writer: W) -> std::io::Result<()>
+where
+    B::Bits: WriteHex,
+{
+    // Adapts an `io::Write` into a `fmt::Write`, holding on to the first I/O error
+    // so it can be returned instead of the opaque `fmt::Error`
+    struct Adapter<W> {
+        inner: W,
+        error: std::io::Result<()>,
+    }
+
+    impl<W: std::io::Write> Write for Adapter<W> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            match self.inner.write_all(s.as_bytes()) {
+                Ok(()) => Ok(()),
+                Err(e) => {
+                    self.error = Err(e);
+                    Err(fmt::Error)
+                }
+            }
+        }
+    }
+
+    let mut adapter = Adapter {
+        inner: writer,
+        error: Ok(()),
+    };
+
+    match to_writer(flags, &mut adapter) {
+        Ok(()) => Ok(()),
+        Err(_) => match adapter.error {
+            Err(e) => Err(e),
+            Ok(()) => Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "formatter error",
+            )),
+        },
+    }
+}
+
+/**
+Write a flags value as text into a fixed-capacity [`heapless::String`].
+
+This produces the same output as [`to_writer`], but doesn't need an allocator, so it can be
+used in `no_std` environments. If the text doesn't fit in `N` bytes then an error is returned.
+*/
+#[cfg(feature = "heapless")]
+pub fn to_heapless<B: Flags, const N: usize>(flags: &B) -> Result<heapless::String<N>, fmt::Error>
+where
+    B::Bits: WriteHex,
+{
+    let mut s = heapless::String::new();
+
+    to_writer(flags, &mut s)?;
+    Ok(s)
+}
+
+/**
+Write a value as hex, padded with leading zeros to the number of digits needed for `width` bits.
+*/
+fn write_hex_padded<T: WriteHex>(value: &T, width: u32, mut writer: impl Write) -> fmt::Result {
+    // Count the digits in the hex value up-front so padding doesn't need a buffer
+    struct Count(usize);
+
+    impl Write for Count {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0 += s.len();
+            Ok(())
+        }
+    }
+
+    let mut count = Count(0);
+    value.write_hex(&mut count)?;
+
+    let digits = ((width + 3) / 4) as usize;
+    for _ in count.0..digits {
+        writer.write_char('0')?;
+    }
+
+    value.write_hex(writer)
+}
+
+/**
+Options for writing flags values as text.
+
+The default options write the same format as [`to_writer`]. Alternative formatting can be enabled
+through the methods on this type:
+
+```
+use bitflags::parser::{self, WriteOptions};
+
+# bitflags::bitflags! {
+#     struct Flags: u32 {
+#         const A = 1;
+#     }
+# }
+let mut text = String::new();
+let options = WriteOptions::new().pad_hex(true);
+
+parser::to_writer_with_options(&Flags::from_bits_retain(0xf1), &mut text, options).unwrap();
+
+assert_eq!("A | 0x000000f0", text);
+```
+*/
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WriteOptions {
+    pad_hex: bool,
+}
+
+impl WriteOptions {
+    /**
+    Get the default options.
+    */
+    pub const fn new() -> Self {
+        WriteOptions { pad_hex: false }
+    }
+
+    /**
+    Pad any hex number with leading zeros to the full width of the underlying bits type.
+
+    The width is given by [`Flags::bits_width`], so a `u32` value of `0xf` is written as `0x0000000f`.
+    */
+    pub const fn pad_hex(mut self, pad_hex: bool) -> Self {
+        self.pad_hex = pad_hex;
+        self
+    }
+}
+
 #[cfg(feature = "serde")]
 pub(crate) struct AsDisplay<'a, B>(pub(crate) &'a B);
 
@@ -94,46 +268,697 @@ where
 Parse a flags value from text.
 
 This function will fail on any names that don't correspond to defined flags.
-Unknown bits will be retained.
+Unknown bits will be retained. Input that's empty or only whitespace is parsed as an
+empty flags value.
 */
 pub fn from_str<B: Flags>(input: &str) -> Result<B, ParseError>
+where
+    B::Bits: ParseHex,
+{
+    from_str_with_options(input, ParseOptions::new())
+}
+
+/**
+Parse a flags value from text, using the given [`ParseOptions`].
+
+This function will fail on any names that don't correspond to defined flags.
+Unknown bits will be retained.
+
+With the default options, this function is equivalent to [`from_str`].
+*/
+pub fn from_str_with_options<B: Flags>(input: &str, options: ParseOptions) -> Result<B, ParseError>
+where
+    B::Bits: ParseHex,
+{
+    let input = options.strip_comment(input);
+
+    let mut parsed_flags = B::empty();
+
+    // If the input is empty then return an empty set of flags,
+    // unless empty input has been configured as an error
+    if input.trim().is_empty() {
+        return if options.empty_is_error {
+            Err(ParseError::empty_flag())
+        } else {
+            Ok(parsed_flags)
+        };
+    }
+
+    for flag in options.split(input) {
+        parsed_flags.insert(parse_flag(flag.trim(), options)?);
+    }
+
+    Ok(parsed_flags)
+}
+
+/**
+Parse a single, trimmed flag between separators.
+*/
+fn parse_flag<B: Flags>(flag: &str, options: ParseOptions) -> Result<B, ParseError>
+where
+    B::Bits: ParseHex,
+{
+    // If the flag is empty then we've got missing input
+    if flag.is_empty() {
+        return Err(ParseError::empty_flag());
+    }
+
+    // If the flag starts with `0x` then it's a hex number
+    // Parse it directly to the underlying bits type
+    if let Some(flag) = flag.strip_prefix("0x") {
+        let bits = <B::Bits>::parse_hex(flag).map_err(|e| hex_flag_error(e, flag))?;
+
+        Ok(B::from_bits_retain(bits))
+    }
+    // If the flag starts with `-` then it's a negative decimal number
+    // Parse it directly to the underlying bits type if it's signed
+    else if flag.starts_with('-') {
+        Ok(B::from_bits_retain(parse_negative::<B>(flag)?))
+    }
+    // If quotes are enabled and the flag is quoted then the name inside them
+    // needs unescaping before it can be compared with the names of defined flags
+    else if options.quoted && flag.starts_with('"') {
+        unquote(flag)
+            .and_then(from_escaped_name::<B>)
+            .ok_or_else(|| ParseError::invalid_named_flag(flag))
+    }
+    // If keywords are enabled then `all` and `none` are recognized,
+    // unless the flags type defines a flag with the same name
+    else if let Some(keyword) = parse_keyword::<B>(flag, options) {
+        Ok(keyword)
+    }
+    // If escapes are enabled then the name may need unescaping
+    // before it can be compared with the names of defined flags
+    else if options.escapes {
+        from_escaped_name::<B>(flag).ok_or_else(|| ParseError::invalid_named_flag(flag))
+    }
+    // Otherwise the flag is a name
+    // The generated flags type will determine whether
+    // or not it's a valid identifier
+    else {
+        B::from_name(flag).ok_or_else(|| ParseError::invalid_named_flag(flag))
+    }
+}
+
+fn parse_keyword<B: Flags>(flag: &str, options: ParseOptions) -> Option<B> {
+    if !options.keywords || B::from_name(flag).is_some() {
+        return None;
+    }
+
+    match flag {
+        "all" => Some(B::all()),
+        "none" => Some(B::empty()),
+        _ => None,
+    }
+}
+
+/**
+Split text into the tokens of the flags format, without resolving them against a flags type.
+
+Each token records its [kind](TokenKind) and the range of bytes it covers in `input`, without
+any surrounding whitespace. This is useful for tooling, like syntax highlighting, that needs to
+work with flags text before it's parsed. Numbers are recognized by their `0x` or `-` prefix,
+so their digits, like names, are only checked when the token is parsed into a flags value.
+
+Flags that are missing between separators produce an [empty flag](ParseError::empty_flag) error
+with an empty span where the flag was expected. Tokenizing continues after an error.
+
+```
+use bitflags::parser::{self, TokenKind};
+
+let tokens = parser::tokenize("A | 0x0c")
+    .map(|token| token.map(|token| (token.kind(), token.span())))
+    .collect::<Result<Vec<_>, _>>()
+    .unwrap();
+
+assert_eq!(
+    vec![
+        (TokenKind::Name, 0..1),
+        (TokenKind::Separator, 2..3),
+        (TokenKind::Number, 4..8),
+    ],
+    tokens,
+);
+```
+*/
+pub fn tokenize(input: &str) -> impl Iterator<Item = Result<Token, ParseError>> + '_ {
+    Tokenizer {
+        input,
+        pos: 0,
+        expect_flag: true,
+        any: false,
+        done: false,
+    }
+}
+
+/**
+A token in the flags format, returned by [`tokenize`].
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    kind: TokenKind,
+    span: Range<usize>,
+}
+
+impl Token {
+    /// The kind of token.
+    pub fn kind(&self) -> TokenKind {
+        self.kind
+    }
+
+    /// The range of bytes in the input covered by the token.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+/**
+The kind of a [`Token`].
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TokenKind {
+    /// The name of a flag, like `A`.
+    Name,
+    /// A hex number, like `0x0c`, or a negative decimal number, like `-1`.
+    Number,
+    /// The `|` separator between flags.
+    Separator,
+}
+
+struct Tokenizer<'a> {
+    input: &'a str,
+    pos: usize,
+    // Whether the next token needs to be a flag rather than a separator
+    expect_flag: bool,
+    // Whether any tokens have been produced, so missing flags at the end are an error
+    any: bool,
+    done: bool,
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Result<Token, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let rest = &self.input[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+
+        // If we're at the end of the input then a flag is missing if
+        // the last token was a separator
+        if self.pos == self.input.len() {
+            self.done = true;
+
+            return if self.expect_flag && self.any {
+                Some(Err(ParseError::empty_flag().with_span(self.pos..self.pos)))
+            } else {
+                None
+            };
+        }
+
+        self.any = true;
+
+        // A separator where a flag is expected means the flag is missing
+        // The separator itself is produced on the next call
+        if self.input[self.pos..].starts_with('|') {
+            if self.expect_flag {
+                self.expect_flag = false;
+
+                return Some(Err(ParseError::empty_flag().with_span(self.pos..self.pos)));
+            }
+
+            self.expect_flag = true;
+            self.pos += 1;
+
+            return Some(Ok(Token {
+                kind: TokenKind::Separator,
+                span: self.pos - 1..self.pos,
+            }));
+        }
+
+        // Otherwise the token is a flag that runs up to the next separator
+        let rest = &self.input[self.pos..];
+        let end = rest.find('|').unwrap_or(rest.len());
+        let flag = rest[..end].trim_end();
+
+        let kind = if flag.starts_with("0x") || flag.starts_with('-') {
+            TokenKind::Number
+        } else {
+            TokenKind::Name
+        };
+
+        let span = self.pos..self.pos + flag.len();
+
+        self.expect_flag = false;
+        self.pos += end;
+
+        Some(Ok(Token { kind, span }))
+    }
+}
+
+/**
+Parse a flags value from text, collecting all errors instead of stopping at the first one.
+
+This function parses the same format as [`from_str`], but keeps parsing after any flag that
+fails. If any flags failed then all of their errors are returned, in the order they appear
+in `input`, each with a [span](ParseError::span) of the failing flag. Unknown bits will be retained.
+
+```
+use bitflags::parser;
+
+# bitflags::bitflags! {
+#     #[derive(Debug, PartialEq, Eq)]
+#     struct Flags: u8 {
+#         const A = 1;
+#         const B = 1 << 1;
+#     }
+# }
+let errors = parser::parse_collecting_errors::<Flags>("A | X | B | Y").unwrap_err();
+
+assert_eq!(2, errors.len());
+assert_eq!(Some(4..5), errors[0].span());
+assert_eq!(Some(12..13), errors[1].span());
+```
+*/
+#[cfg(feature = "alloc")]
+pub fn parse_collecting_errors<B: Flags>(input: &str) -> Result<B, alloc::vec::Vec<ParseError>>
 where
     B::Bits: ParseHex,
 {
     let mut parsed_flags = B::empty();
+    let mut errors = alloc::vec::Vec::new();
 
     // If the input is empty then return an empty set of flags
     if input.trim().is_empty() {
         return Ok(parsed_flags);
     }
 
-    for flag in input.split('|') {
-        let flag = flag.trim();
+    for token in tokenize(input) {
+        let token = match token {
+            Ok(token) => token,
+            Err(e) => {
+                errors.push(e);
+                continue;
+            }
+        };
 
-        // If the flag is empty then we've got missing input
-        if flag.is_empty() {
-            return Err(ParseError::empty_flag());
+        if token.kind() == TokenKind::Separator {
+            continue;
         }
 
+        match parse_flag::<B>(&input[token.span()], ParseOptions::new()) {
+            Ok(parsed_flag) => parsed_flags.insert(parsed_flag),
+            Err(e) => errors.push(e.with_span(token.span())),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(parsed_flags)
+    } else {
+        Err(errors)
+    }
+}
+
+/**
+Parse a flags value from the start of some text, returning the unparsed remainder.
+
+This function parses as many `|` separated flags from the start of `input` as it can, using
+the same format as [`from_str`], and stops at the first token that isn't a separator followed
+by a flag. A flag is either a hex number, a negative decimal number, or a run of alphanumeric
+characters and underscores that matches the name of a defined flag. The returned remainder starts immediately after the
+last parsed flag, so any whitespace or `|` separator that isn't followed by another flag is
+left unconsumed.
+
+If `input` doesn't start with a flag then an empty flags value is returned along with the
+whole `input`. This function will fail on hex or negative numbers that can't be parsed.
+Unknown bits will be retained.
+
+```
+use bitflags::parser;
+
+# bitflags::bitflags! {
+#     #[derive(Debug, PartialEq, Eq)]
+#     struct Flags: u8 {
+#         const A = 1;
+#         const B = 1 << 1;
+#     }
+# }
+let (flags, rest) = parser::parse_prefix::<Flags>("A | B; rest").unwrap();
+
+assert_eq!(Flags::A | Flags::B, flags);
+assert_eq!("; rest", rest);
+```
+*/
+pub fn parse_prefix<B: Flags>(input: &str) -> Result<(B, &str), ParseError>
+where
+    B::Bits: ParseHex,
+{
+    let mut parsed_flags = B::empty();
+
+    let mut rest = input;
+    let mut next = input.trim_start();
+
+    loop {
         // If the flag starts with `0x` then it's a hex number
-        // Parse it directly to the underlying bits type
-        let parsed_flag = if let Some(flag) = flag.strip_prefix("0x") {
+        // The number ends at the first character that isn't a hex digit
+        let (parsed_flag, remaining) = if let Some(flag) = next.strip_prefix("0x") {
+            let end = flag
+                .find(|c: char| !c.is_ascii_hexdigit())
+                .unwrap_or(flag.len());
+
             let bits =
-                <B::Bits>::parse_hex(flag).map_err(|_| ParseError::invalid_hex_flag(flag))?;
+                <B::Bits>::parse_hex(&flag[..end]).map_err(|e| hex_flag_error(e, &flag[..end]))?;
+
+            (B::from_bits_retain(bits), &flag[end..])
+        }
+        // If the flag starts with `-` then it's a negative decimal number
+        // The number ends at the first character that isn't a decimal digit
+        else if let Some(flag) = next.strip_prefix('-') {
+            let end = flag
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(flag.len());
 
-            B::from_bits_retain(bits)
+            let bits = parse_negative::<B>(&next[..end + 1])?;
+
+            (B::from_bits_retain(bits), &flag[end..])
         }
         // Otherwise the flag is a name
-        // The generated flags type will determine whether
-        // or not it's a valid identifier
+        // The name ends at the first character that couldn't be part of an identifier
+        // If it doesn't correspond to a defined flag then parsing stops
         else {
-            B::from_name(flag).ok_or_else(|| ParseError::invalid_named_flag(flag))?
+            // Kebab-case names can also contain dashes
+            let kebab = B::RENAME_ALL == RenameAll::KebabCase;
+            let end = next
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || (kebab && c == '-')))
+                .unwrap_or(next.len());
+
+            match B::from_name(&next[..end]) {
+                Some(parsed_flag) => (parsed_flag, &next[end..]),
+                None => break,
+            }
         };
 
         parsed_flags.insert(parsed_flag);
+        rest = remaining;
+
+        // Only continue if the flag is followed by a separator
+        match rest.trim_start().strip_prefix('|') {
+            Some(remaining) => next = remaining.trim_start(),
+            None => break,
+        }
     }
 
-    Ok(parsed_flags)
+    Ok((parsed_flags, rest))
+}
+
+/**
+Options for parsing flags values from text.
+
+The default options parse the same format as [`from_str`]. Additional syntax can be enabled
+through the methods on this type:
+
+```
+use bitflags::parser::{self, ParseOptions};
+
+# bitflags::bitflags! {
+#     #[derive(Debug, PartialEq, Eq)]
+#     struct Flags: u8 {
+#         const A = 1;
+#         const B = 1 << 1;
+#     }
+# }
+let options = ParseOptions::new().strip_line_comment('#');
+
+let flags: Flags = parser::from_str_with_options("A | B # the defaults", options).unwrap();
+
+assert_eq!(Flags::A | Flags::B, flags);
+```
+*/
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ParseOptions {
+    comment: Option<char>,
+    escapes: bool,
+    quoted: bool,
+    keywords: bool,
+    empty_is_error: bool,
+}
+
+impl ParseOptions {
+    /**
+    Get the default options.
+    */
+    pub const fn new() -> Self {
+        ParseOptions {
+            comment: None,
+            escapes: false,
+            quoted: false,
+            keywords: false,
+            empty_is_error: false,
+        }
+    }
+
+    /**
+    Ignore everything from the first unescaped `comment` character to the end of the input.
+    */
+    pub const fn strip_line_comment(mut self, comment: char) -> Self {
+        self.comment = Some(comment);
+        self
+    }
+
+    /**
+    Treat a backslash (`\`) as escaping the character that follows it.
+
+    An escaped `|` is considered part of a flag name instead of a separator, and an escaped
+    comment character doesn't start a comment. Use `\\` for a literal backslash.
+    */
+    pub const fn escapes(mut self, escapes: bool) -> Self {
+        self.escapes = escapes;
+        self
+    }
+
+    /**
+    Allow flag names to be wrapped in double quotes (`"`).
+
+    A quoted name may contain `|` and comment characters, which are considered part of
+    the name. Within quotes, a backslash (`\`) escapes the character that follows it,
+    so `\"` is a literal quote and `\\` is a literal backslash.
+    */
+    pub const fn allow_quoted(mut self, quoted: bool) -> Self {
+        self.quoted = quoted;
+        self
+    }
+
+    /**
+    Recognize `all` as all defined flags, and `none` as an empty flags value.
+
+    The keywords can be combined with other flags, like `none | A`. If the flags type defines
+    a flag named `all` or `none` then that flag is parsed instead of the keyword. Quoted
+    names are never treated as keywords.
+    */
+    pub const fn keywords(mut self, keywords: bool) -> Self {
+        self.keywords = keywords;
+        self
+    }
+
+    /**
+    Treat input that's empty or only whitespace as an empty flags value.
+
+    This is the default. If set to `false`, empty input will fail to parse with an
+    empty flag error instead. Input that only contains a comment is also considered empty.
+    */
+    pub const fn empty_is_empty(mut self, empty_is_empty: bool) -> Self {
+        self.empty_is_error = !empty_is_empty;
+        self
+    }
+
+    fn strip_comment<'a>(&self, input: &'a str) -> &'a str {
+        match self.comment {
+            Some(comment) => match self.find_unescaped(input, comment) {
+                Some(end) => &input[..end],
+                None => input,
+            },
+            None => input,
+        }
+    }
+
+    fn split<'a>(&self, mut input: &'a str) -> impl Iterator<Item = &'a str> {
+        let options = *self;
+        let mut done = false;
+
+        core::iter::from_fn(move || {
+            if done {
+                return None;
+            }
+
+            match options.find_unescaped(input, '|') {
+                Some(end) => {
+                    let flag = &input[..end];
+                    input = &input[end + 1..];
+
+                    Some(flag)
+                }
+                None => {
+                    done = true;
+
+                    Some(input)
+                }
+            }
+        })
+    }
+
+    fn find_unescaped(&self, input: &str, needle: char) -> Option<usize> {
+        let mut escaped = false;
+        let mut quoted = false;
+
+        for (i, c) in input.char_indices() {
+            if escaped {
+                escaped = false;
+            } else if (self.escapes || quoted) && c == '\\' {
+                escaped = true;
+            } else if self.quoted && c == '"' {
+                quoted = !quoted;
+            } else if !quoted && c == needle {
+                return Some(i);
+            }
+        }
+
+        None
+    }
+}
+
+/**
+A case transform applied to the names of flags in the text format.
+
+The transform for a flags type is given by [`Flags::RENAME_ALL`], which is set through the
+`rename_all` option on the `bitflags!` macro. Flags are written using their transformed names,
+and parsed from either their original or transformed names. The names returned by
+[`Flag::name`](crate::Flag::name) and [`Flags::iter_names`] are always the original names.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RenameAll {
+    /// Names are used as they're defined, like `READ_WRITE`.
+    Verbatim,
+    /// Names are converted to lowercase, like `read_write`.
+    Lowercase,
+    /// Names are converted to lowercase and underscores are replaced with dashes, like `read-write`.
+    KebabCase,
+}
+
+impl RenameAll {
+    // Callers should check for `Verbatim` first, since this always lowercases
+    fn transform(self, name: &str) -> impl Iterator<Item = char> + '_ {
+        let kebab = self == RenameAll::KebabCase;
+
+        name.chars()
+            .map(move |c| if kebab && c == '_' { '-' } else { c })
+            .flat_map(char::to_lowercase)
+    }
+
+    pub(crate) fn write_name(self, name: &str, mut writer: impl Write) -> fmt::Result {
+        if self == RenameAll::Verbatim {
+            return writer.write_str(name);
+        }
+
+        for c in self.transform(name) {
+            writer.write_char(c)?;
+        }
+
+        Ok(())
+    }
+
+    /// Whether `input` is either the original or transformed form of `name`.
+    pub(crate) fn matches(self, name: &str, input: &str) -> bool {
+        name == input || (self != RenameAll::Verbatim && self.transform(name).eq(input.chars()))
+    }
+}
+
+// Keep overflow errors from parsing a hex flag, but report anything else as an invalid hex flag
+fn hex_flag_error(err: ParseError, flag: &str) -> ParseError {
+    match err.kind {
+        ParseErrorKind::OverflowingFlag { .. } => err,
+        _ => ParseError::invalid_hex_flag(flag),
+    }
+}
+
+/**
+Parse a negative decimal number, like `-1`, to the underlying bits type.
+
+This will fail if the bits type isn't signed.
+*/
+fn parse_negative<B: Flags>(flag: &str) -> Result<B::Bits, ParseError>
+where
+    B::Bits: ParseHex,
+{
+    if !B::Bits::SIGNED {
+        return Err(ParseError::unsigned_negative_flag(flag));
+    }
+
+    <B::Bits>::parse_negative(flag)
+}
+
+/**
+Get the escaped name inside a quoted flag, like `"A | B"`.
+
+This will fail if the flag doesn't end with the closing quote.
+*/
+fn unquote(flag: &str) -> Option<&str> {
+    let inner = flag.strip_prefix('"')?;
+    let mut escaped = false;
+
+    for (i, c) in inner.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == '"' {
+            // The closing quote must be the end of the flag
+            return if i + 1 == inner.len() {
+                Some(&inner[..i])
+            } else {
+                None
+            };
+        }
+    }
+
+    None
+}
+
+/**
+Find a named flag whose name matches the given escaped name.
+*/
+fn from_escaped_name<B: Flags>(escaped: &str) -> Option<B> {
+    // Names are compared character-by-character instead of unescaping
+    // into a buffer so parsing doesn't need to allocate
+    fn eq_unescaped(escaped: &str, mut name: impl Iterator<Item = char>) -> bool {
+        let mut escaped = escaped.chars();
+
+        loop {
+            let e = match escaped.next() {
+                Some('\\') => escaped.next(),
+                e => e,
+            };
+
+            match (e, name.next()) {
+                (None, None) => return true,
+                (Some(e), Some(n)) if e == n => continue,
+                _ => return false,
+            }
+        }
+    }
+
+    B::FLAGS
+        .iter()
+        .find(|flag| {
+            flag.is_named()
+                && (eq_unescaped(escaped, flag.name().chars())
+                    || (B::RENAME_ALL != RenameAll::Verbatim
+                        && eq_unescaped(escaped, B::RENAME_ALL.transform(flag.name()))))
+        })
+        .map(|flag| B::from_bits_retain(flag.value().bits()))
 }
 
 /**
@@ -174,7 +999,7 @@ pub fn to_writer_strict<B: Flags>(flags: &B, mut writer: impl Write) -> Result<(
         }
 
         first = false;
-        writer.write_str(name)?;
+        B::RENAME_ALL.write_name(name, &mut writer)?;
     }
 
     fmt::Result::Ok(())
@@ -237,11 +1062,25 @@ pub trait ParseHex {
     fn parse_hex(input: &str) -> Result<Self, ParseError>
     where
         Self: Sized;
+
+    /// Parse the value from a negative decimal number, including its leading `-`.
+    ///
+    /// This method is only called for [signed](crate::Bits::SIGNED) bits types.
+    /// The default implementation always fails.
+    fn parse_negative(input: &str) -> Result<Self, ParseError>
+    where
+        Self: Sized,
+    {
+        Err(ParseError::invalid_negative_flag(input))
+    }
 }
 
 /// An error encountered while parsing flags from text.
 #[derive(Debug)]
-pub struct ParseError(ParseErrorKind);
+pub struct ParseError {
+    kind: ParseErrorKind,
+    span: Option<Range<usize>>,
+}
 
 #[derive(Debug)]
 #[allow(clippy::enum_variant_names)]
@@ -259,6 +1098,24 @@ enum ParseErrorKind {
         #[cfg(feature = "std")]
         got: String,
     },
+    InvalidNegativeFlag {
+        #[cfg(not(feature = "std"))]
+        got: (),
+        #[cfg(feature = "std")]
+        got: String,
+    },
+    UnsignedNegativeFlag {
+        #[cfg(not(feature = "std"))]
+        got: (),
+        #[cfg(feature = "std")]
+        got: String,
+    },
+    OverflowingFlag {
+        #[cfg(not(feature = "std"))]
+        got: (),
+        #[cfg(feature = "std")]
+        got: String,
+    },
 }
 
 impl ParseError {
@@ -273,7 +1130,7 @@ impl ParseError {
             }
         };
 
-        ParseError(ParseErrorKind::InvalidHexFlag { got })
+        ParseError::from_kind(ParseErrorKind::InvalidHexFlag { got })
     }
 
     /// A named flag that doesn't correspond to any on the flags type was encountered.
@@ -287,18 +1144,76 @@ impl ParseError {
             }
         };
 
-        ParseError(ParseErrorKind::InvalidNamedFlag { got })
+        ParseError::from_kind(ParseErrorKind::InvalidNamedFlag { got })
+    }
+
+    /// An invalid negative decimal flag was encountered.
+    pub fn invalid_negative_flag(flag: impl fmt::Display) -> Self {
+        let _flag = flag;
+
+        let got = {
+            #[cfg(feature = "std")]
+            {
+                _flag.to_string()
+            }
+        };
+
+        ParseError::from_kind(ParseErrorKind::InvalidNegativeFlag { got })
+    }
+
+    /// A negative decimal flag was encountered for an unsigned bits type.
+    pub fn unsigned_negative_flag(flag: impl fmt::Display) -> Self {
+        let _flag = flag;
+
+        let got = {
+            #[cfg(feature = "std")]
+            {
+                _flag.to_string()
+            }
+        };
+
+        ParseError::from_kind(ParseErrorKind::UnsignedNegativeFlag { got })
+    }
+
+    /// A hex or negative decimal flag was encountered that doesn't fit in the bits type.
+    pub fn overflowing_flag(flag: impl fmt::Display) -> Self {
+        let _flag = flag;
+
+        let got = {
+            #[cfg(feature = "std")]
+            {
+                _flag.to_string()
+            }
+        };
+
+        ParseError::from_kind(ParseErrorKind::OverflowingFlag { got })
     }
 
     /// A hex or named flag wasn't found between separators.
     pub const fn empty_flag() -> Self {
-        ParseError(ParseErrorKind::EmptyFlag)
+        ParseError::from_kind(ParseErrorKind::EmptyFlag)
+    }
+
+    /// The range of bytes in the input that caused the error.
+    ///
+    /// This is only available for errors returned by `parse_collecting_errors` and [`tokenize`].
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    const fn from_kind(kind: ParseErrorKind) -> Self {
+        ParseError { kind, span: None }
+    }
+
+    fn with_span(mut self, span: Range<usize>) -> Self {
+        self.span = Some(span);
+        self
     }
 }
 
 impl fmt::Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match &self.0 {
+        match &self.kind {
             ParseErrorKind::InvalidNamedFlag { got } => {
                 let _got = got;
 
@@ -319,6 +1234,36 @@ impl fmt::Display for ParseError {
                     write!(f, " `{}`", _got)?;
                 }
             }
+            ParseErrorKind::InvalidNegativeFlag { got } => {
+                let _got = got;
+
+                write!(f, "invalid negative flag")?;
+
+                #[cfg(feature = "std")]
+                {
+                    write!(f, " `{}`", _got)?;
+                }
+            }
+            ParseErrorKind::UnsignedNegativeFlag { got } => {
+                let _got = got;
+
+                write!(f, "negative flag for unsigned bits")?;
+
+                #[cfg(feature = "std")]
+                {
+                    write!(f, " `{}`", _got)?;
+                }
+            }
+            ParseErrorKind::OverflowingFlag { got } => {
+                let _got = got;
+
+                write!(f, "flag overflows the bits type")?;
+
+                #[cfg(feature = "std")]
+                {
+                    write!(f, " `{}`", _got)?;
+                }
+            }
             ParseErrorKind::EmptyFlag => {
                 write!(f, "encountered empty flag")?;
             }
@@ -330,3 +1275,5 @@ impl fmt::Display for ParseError {
 
 #[cfg(feature = "std")]
 impl std::error::Error for ParseError {}
+#[cfg(all(feature = "core_error", not(feature = "std")))]
+impl core::error::Error for ParseError {}
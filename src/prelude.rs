@@ -0,0 +1,25 @@
+/*!
+The most commonly used items, for glob importing.
+
+This module re-exports the [`bitflags`] macro along with the [`Flags`], [`Bits`],
+and [`Flag`] traits and types, so generic methods like [`Flags::iter`] are in scope with a single import:
+
+```
+use bitflags::prelude::*;
+
+bitflags! {
+    struct MyFlags: u8 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+}
+
+fn count<F: Flags>(flags: &F) -> usize {
+    flags.iter().count()
+}
+
+assert_eq!(2, count(&MyFlags::all()));
+```
+*/
+
+pub use crate::{bitflags, Bits, Flag, Flags};
@@ -9,6 +9,40 @@
 #[macro_export]
 #[doc(hidden)]
 macro_rules! __declare_public_bitflags {
+    // `transparent`: Add `#[repr(transparent)]` to the flags type
+    (
+        options: [{ transparent } $($options:tt)*],
+        $(#[$outer:meta])*
+        $vis:vis struct $PublicBitFlags:ident
+    ) => {
+        $crate::__declare_public_bitflags! {
+            $(#[$outer])*
+            #[repr(transparent)]
+            $vis struct $PublicBitFlags
+        }
+    };
+    // Skip any other options
+    (
+        options: [{ $($option:tt)* } $($options:tt)*],
+        $(#[$outer:meta])*
+        $vis:vis struct $PublicBitFlags:ident
+    ) => {
+        $crate::__declare_public_bitflags! {
+            options: [$($options)*],
+            $(#[$outer])*
+            $vis struct $PublicBitFlags
+        }
+    };
+    (
+        options: [],
+        $(#[$outer:meta])*
+        $vis:vis struct $PublicBitFlags:ident
+    ) => {
+        $crate::__declare_public_bitflags! {
+            $(#[$outer])*
+            $vis struct $PublicBitFlags
+        }
+    };
     (
         $(#[$outer:meta])*
         $vis:vis struct $PublicBitFlags:ident
@@ -18,6 +52,165 @@ macro_rules! __declare_public_bitflags {
     };
 }
 
+/// Declare an enum with a variant for each named flag in the public (user-facing) bitflags type.
+///
+/// The enum is only declared if the flags type has the `kind` option.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __declare_public_bitflags_kind {
+    // `kind = $Kind`: Declare the enum and a `kind` method that returns its variants
+    (
+        options: [{ kind = $Kind:ident } $($options:tt)*],
+        $vis:vis struct $PublicBitFlags:ident {
+            $(
+                $(#[$inner:ident $($args:tt)*])*
+                const $Flag:tt;
+            )*
+        }
+    ) => {
+        $crate::__declare_public_bitflags_kind! {
+            kind: $vis $Kind for $PublicBitFlags,
+            unprocessed: [$({ $(#[$inner $($args)*])* const $Flag; })*],
+            current: [],
+            variants: [],
+        }
+
+        impl $PublicBitFlags {
+            /// Get the single flag this flags value is exactly equal to.
+            ///
+            /// This method will return `None` if the flags value is empty, contains multiple
+            /// bits, or doesn't correspond to a named flag.
+            #[allow(deprecated)]
+            #[inline]
+            pub fn kind(&self) -> $crate::__private::core::option::Option<$Kind> {
+                $(
+                    $crate::__bitflags_flag!({
+                        name: $Flag,
+                        named: {
+                            $crate::__bitflags_expr_safe_attrs!(
+                                $(#[$inner $($args)*])*
+                                {
+                                    if self.bits() == Self::$Flag.bits() && Self::$Flag.bits().count_ones() == 1 {
+                                        return $crate::__private::core::option::Option::Some($Kind::$Flag);
+                                    }
+                                }
+                            );
+                        },
+                        unnamed: {},
+                    });
+                )*
+
+                $crate::__private::core::option::Option::None
+            }
+        }
+    };
+    // Skip any other options
+    (
+        options: [{ $($option:tt)* } $($options:tt)*],
+        $vis:vis struct $PublicBitFlags:ident {
+            $($flags:tt)*
+        }
+    ) => {
+        $crate::__declare_public_bitflags_kind! {
+            options: [$($options)*],
+            $vis struct $PublicBitFlags {
+                $($flags)*
+            }
+        }
+    };
+    (
+        options: [],
+        $vis:vis struct $PublicBitFlags:ident {
+            $($flags:tt)*
+        }
+    ) => {};
+    // Process the next attribute on the current flag
+    // `bitflags`: Remove the attribute
+    (
+        kind: $vis:vis $Kind:ident for $PublicBitFlags:ident,
+        unprocessed: [{ #[bitflags $($args:tt)*] $($flag:tt)* } $($unprocessed:tt)*],
+        current: [$($current:tt)*],
+        variants: [$($variants:tt)*],
+    ) => {
+        $crate::__declare_public_bitflags_kind! {
+            kind: $vis $Kind for $PublicBitFlags,
+            unprocessed: [{ $($flag)* } $($unprocessed)*],
+            current: [$($current)*],
+            variants: [$($variants)*],
+        }
+    };
+    // Process the next attribute on the current flag
+    // `$other`: Pass the attribute through to the variant
+    (
+        kind: $vis:vis $Kind:ident for $PublicBitFlags:ident,
+        unprocessed: [{ #[$other:ident $($args:tt)*] $($flag:tt)* } $($unprocessed:tt)*],
+        current: [$($current:tt)*],
+        variants: [$($variants:tt)*],
+    ) => {
+        $crate::__declare_public_bitflags_kind! {
+            kind: $vis $Kind for $PublicBitFlags,
+            unprocessed: [{ $($flag)* } $($unprocessed)*],
+            current: [$($current)* #[$other $($args)*]],
+            variants: [$($variants)*],
+        }
+    };
+    // Process the current flag
+    // `_`: Unnamed flags don't get a variant
+    (
+        kind: $vis:vis $Kind:ident for $PublicBitFlags:ident,
+        unprocessed: [{ const _; } $($unprocessed:tt)*],
+        current: [$($current:tt)*],
+        variants: [$($variants:tt)*],
+    ) => {
+        $crate::__declare_public_bitflags_kind! {
+            kind: $vis $Kind for $PublicBitFlags,
+            unprocessed: [$($unprocessed)*],
+            current: [],
+            variants: [$($variants)*],
+        }
+    };
+    // Process the current flag
+    // `$Flag`: Add a variant with the same name and attributes as the flag
+    (
+        kind: $vis:vis $Kind:ident for $PublicBitFlags:ident,
+        unprocessed: [{ const $Flag:ident; } $($unprocessed:tt)*],
+        current: [$($current:tt)*],
+        variants: [$($variants:tt)*],
+    ) => {
+        $crate::__declare_public_bitflags_kind! {
+            kind: $vis $Kind for $PublicBitFlags,
+            unprocessed: [$($unprocessed)*],
+            current: [],
+            variants: [$($variants)* $($current)* $Flag,],
+        }
+    };
+    // Once all flags are processed, declare the enum
+    (
+        kind: $vis:vis $Kind:ident for $PublicBitFlags:ident,
+        unprocessed: [],
+        current: [],
+        variants: [$($variants:tt)*],
+    ) => {
+        #[doc = $crate::__private::core::concat!(
+            "A single named flag in [`",
+            $crate::__private::core::stringify!($PublicBitFlags),
+            "`]."
+        )]
+        #[derive(
+            $crate::__private::core::fmt::Debug,
+            $crate::__private::core::clone::Clone,
+            $crate::__private::core::marker::Copy,
+            $crate::__private::core::cmp::PartialEq,
+            $crate::__private::core::cmp::Eq,
+            $crate::__private::core::hash::Hash,
+        )]
+        #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+        $vis enum $Kind {
+            $($variants)*
+        }
+    };
+}
+
 /// Implement functions on the public (user-facing) bitflags type.
 ///
 /// We need to be careful about adding new methods and trait implementations here because they
@@ -40,6 +233,14 @@ macro_rules! __impl_public_bitflags_forward {
                     Self($InternalBitFlags::all())
                 }
 
+                fn max_defined_bit() {
+                    $InternalBitFlags::max_defined_bit()
+                }
+
+                fn min_defined_bit() {
+                    $InternalBitFlags::min_defined_bit()
+                }
+
                 fn bits(f) {
                     f.0.bits()
                 }
@@ -59,6 +260,36 @@ macro_rules! __impl_public_bitflags_forward {
                     Self($InternalBitFlags::from_bits_retain(bits))
                 }
 
+                fn from_bits_with_mask(bits, allowed_extra) {
+                    match $InternalBitFlags::from_bits_with_mask(bits, allowed_extra) {
+                        $crate::__private::core::option::Option::Some(bits) => $crate::__private::core::option::Option::Some(Self(bits)),
+                        $crate::__private::core::option::Option::None => $crate::__private::core::option::Option::None,
+                    }
+                }
+
+                fn from_bit_index(index) {
+                    match $InternalBitFlags::from_bit_index(index) {
+                        $crate::__private::core::option::Option::Some(bits) => $crate::__private::core::option::Option::Some(Self(bits)),
+                        $crate::__private::core::option::Option::None => $crate::__private::core::option::Option::None,
+                    }
+                }
+
+                fn to_be_bytes(f) {
+                    f.0.to_be_bytes()
+                }
+
+                fn to_le_bytes(f) {
+                    f.0.to_le_bytes()
+                }
+
+                fn from_be_bytes(bytes) {
+                    Self($InternalBitFlags::from_be_bytes(bytes))
+                }
+
+                fn from_le_bytes(bytes) {
+                    Self($InternalBitFlags::from_le_bytes(bytes))
+                }
+
                 fn from_name(name) {
                     match $InternalBitFlags::from_name(name) {
                         $crate::__private::core::option::Option::Some(bits) => $crate::__private::core::option::Option::Some(Self(bits)),
@@ -74,6 +305,14 @@ macro_rules! __impl_public_bitflags_forward {
                     f.0.is_all()
                 }
 
+                fn is_all_bits(f) {
+                    f.0.is_all_bits()
+                }
+
+                fn const_eq(f, other) {
+                    f.0.const_eq(&other.0)
+                }
+
                 fn intersects(f, other) {
                     f.0.intersects(other.0)
                 }
@@ -82,6 +321,10 @@ macro_rules! __impl_public_bitflags_forward {
                     f.0.contains(other.0)
                 }
 
+                fn present_of(f, group) {
+                    Self(f.0.present_of(group.0))
+                }
+
                 fn insert(f, other) {
                     f.0.insert(other.0)
                 }
@@ -90,14 +333,65 @@ macro_rules! __impl_public_bitflags_forward {
                     f.0.remove(other.0)
                 }
 
+                fn remove_exact(f, other) {
+                    f.0.remove_exact(other.0)
+                }
+
+                fn insert_bits(f, bits) {
+                    f.0.insert_bits(bits)
+                }
+
+                fn remove_bits(f, bits) {
+                    f.0.remove_bits(bits)
+                }
+
                 fn toggle(f, other) {
                     f.0.toggle(other.0)
                 }
 
+                fn intersect(f, other) {
+                    f.0.intersect(other.0)
+                }
+
+                fn retain_only(f, other) {
+                    f.0.retain_only(other.0)
+                }
+
                 fn set(f, other, value) {
                     f.0.set(other.0, value)
                 }
 
+                fn with_inserted(f, other) {
+                    f.0.insert(other.0);
+                    f
+                }
+
+                fn with_removed(f, other) {
+                    f.0.remove(other.0);
+                    f
+                }
+
+                fn with_toggled(f, other) {
+                    f.0.toggle(other.0);
+                    f
+                }
+
+                fn inserted(f, other) {
+                    Self(f.0.inserted(other.0))
+                }
+
+                fn removed(f, other) {
+                    Self(f.0.removed(other.0))
+                }
+
+                fn toggled(f, other) {
+                    Self(f.0.toggled(other.0))
+                }
+
+                fn truncated(f) {
+                    Self(f.0.truncated())
+                }
+
                 fn intersection(f, other) {
                     Self(f.0.intersection(other.0))
                 }
@@ -117,6 +411,10 @@ macro_rules! __impl_public_bitflags_forward {
                 fn complement(f) {
                     Self(f.0.complement())
                 }
+
+                fn complement_retain(f) {
+                    Self(f.0.complement_retain())
+                }
             }
         }
     };
@@ -165,6 +463,26 @@ macro_rules! __impl_public_bitflags {
                     Self::from_bits_retain(truncated)
                 }
 
+                fn max_defined_bit() {
+                    let all = Self::all().bits();
+
+                    if all == <$T as $crate::Bits>::EMPTY {
+                        0
+                    } else {
+                        <$T>::BITS - 1 - all.leading_zeros()
+                    }
+                }
+
+                fn min_defined_bit() {
+                    let all = Self::all().bits();
+
+                    if all == <$T as $crate::Bits>::EMPTY {
+                        0
+                    } else {
+                        all.trailing_zeros()
+                    }
+                }
+
                 fn bits(f) {
                     f.0
                 }
@@ -187,6 +505,38 @@ macro_rules! __impl_public_bitflags {
                     Self(bits)
                 }
 
+                fn from_bits_with_mask(bits, allowed_extra) {
+                    if bits & !(Self::all().bits() | allowed_extra) == <$T as $crate::Bits>::EMPTY {
+                        $crate::__private::core::option::Option::Some(Self(bits))
+                    } else {
+                        $crate::__private::core::option::Option::None
+                    }
+                }
+
+                fn from_bit_index(index) {
+                    if index < <$T>::BITS {
+                        $crate::__private::core::option::Option::Some(Self(1 << index))
+                    } else {
+                        $crate::__private::core::option::Option::None
+                    }
+                }
+
+                fn to_be_bytes(f) {
+                    f.bits().to_be_bytes()
+                }
+
+                fn to_le_bytes(f) {
+                    f.bits().to_le_bytes()
+                }
+
+                fn from_be_bytes(bytes) {
+                    Self::from_bits_retain(<$T>::from_be_bytes(bytes))
+                }
+
+                fn from_le_bytes(bytes) {
+                    Self::from_bits_retain(<$T>::from_le_bytes(bytes))
+                }
+
                 fn from_name(name) {
                     $(
                         $crate::__bitflags_flag!({
@@ -205,7 +555,13 @@ macro_rules! __impl_public_bitflags {
                         });
                     )*
 
-                    let _ = name;
+                    // If the flags type renames its flags then also check their transformed names
+                    if <$PublicBitFlags as $crate::Flags>::RENAME_ALL != $crate::parser::RenameAll::Verbatim {
+                        if let $crate::__private::core::option::Option::Some(flags) = <$PublicBitFlags as $crate::Flags>::from_name(name) {
+                            return $crate::__private::core::option::Option::Some(Self(flags.bits()));
+                        }
+                    }
+
                     $crate::__private::core::option::Option::None
                 }
 
@@ -219,6 +575,14 @@ macro_rules! __impl_public_bitflags {
                     Self::all().bits() | f.bits() == f.bits()
                 }
 
+                fn is_all_bits(f) {
+                    f.bits() == <$T as $crate::Bits>::ALL
+                }
+
+                fn const_eq(f, other) {
+                    f.bits() == other.bits()
+                }
+
                 fn intersects(f, other) {
                     f.bits() & other.bits() != <$T as $crate::Bits>::EMPTY
                 }
@@ -227,6 +591,10 @@ macro_rules! __impl_public_bitflags {
                     f.bits() & other.bits() == other.bits()
                 }
 
+                fn present_of(f, group) {
+                    Self::from_bits_retain(f.bits() & group.bits())
+                }
+
                 fn insert(f, other) {
                     *f = Self::from_bits_retain(f.bits()).union(other);
                 }
@@ -235,10 +603,36 @@ macro_rules! __impl_public_bitflags {
                     *f = Self::from_bits_retain(f.bits()).difference(other);
                 }
 
+                fn remove_exact(f, other) {
+                    if f.bits() & other.bits() == other.bits() {
+                        f.remove(other);
+
+                        $crate::__private::core::result::Result::Ok(())
+                    } else {
+                        $crate::__private::core::result::Result::Err($crate::convert::NotPresent::new())
+                    }
+                }
+
+                fn insert_bits(f, bits) {
+                    *f = Self::from_bits_retain(f.bits() | bits);
+                }
+
+                fn remove_bits(f, bits) {
+                    *f = Self::from_bits_retain(f.bits() & !bits);
+                }
+
                 fn toggle(f, other) {
                     *f = Self::from_bits_retain(f.bits()).symmetric_difference(other);
                 }
 
+                fn intersect(f, other) {
+                    *f = Self::from_bits_retain(f.bits()).intersection(other);
+                }
+
+                fn retain_only(f, other) {
+                    f.intersect(other);
+                }
+
                 fn set(f, other, value) {
                     if value {
                         f.insert(other);
@@ -247,6 +641,37 @@ macro_rules! __impl_public_bitflags {
                     }
                 }
 
+                fn with_inserted(f, other) {
+                    f.insert(other);
+                    f
+                }
+
+                fn with_removed(f, other) {
+                    f.remove(other);
+                    f
+                }
+
+                fn with_toggled(f, other) {
+                    f.toggle(other);
+                    f
+                }
+
+                fn inserted(f, other) {
+                    f.union(other)
+                }
+
+                fn removed(f, other) {
+                    f.difference(other)
+                }
+
+                fn toggled(f, other) {
+                    f.symmetric_difference(other)
+                }
+
+                fn truncated(f) {
+                    Self::from_bits_truncate(f.bits())
+                }
+
                 fn intersection(f, other) {
                     Self::from_bits_retain(f.bits() & other.bits())
                 }
@@ -266,6 +691,10 @@ macro_rules! __impl_public_bitflags {
                 fn complement(f) {
                     Self::from_bits_truncate(!f.bits())
                 }
+
+                fn complement_retain(f) {
+                    Self::from_bits_retain(!f.bits())
+                }
             }
         }
     };
@@ -285,6 +714,8 @@ macro_rules! __impl_public_bitflags_iter {
             ///
             /// Each yielded flags value will correspond to a defined named flag. Any unknown bits
             /// will be yielded together as a final flags value.
+            ///
+            /// Flags are yielded in the order they're declared.
             #[inline]
             pub const fn iter(&self) -> $crate::iter::Iter<$PublicBitFlags> {
                 $crate::iter::Iter::__private_const_new(
@@ -306,6 +737,151 @@ macro_rules! __impl_public_bitflags_iter {
                     $PublicBitFlags::from_bits_retain(self.bits()),
                 )
             }
+
+            /// Yield a set of contained named flags values, followed by any remaining bits.
+            ///
+            /// This method is like [`iter`](#method.iter), except named flags are yielded along with their names,
+            /// and any remaining bits are yielded as a final `FlagOrBits::Unknown`.
+            #[inline]
+            pub const fn iter_with_unknown(&self) -> $crate::iter::IterWithUnknown<$PublicBitFlags> {
+                $crate::iter::IterWithUnknown::__private_const_new(
+                    <$PublicBitFlags as $crate::Flags>::FLAGS,
+                    $PublicBitFlags::from_bits_retain(self.bits()),
+                    $PublicBitFlags::from_bits_retain(self.bits()),
+                )
+            }
+
+            /// Yield a set of contained flags values, along with the position of their lowest set bit.
+            ///
+            /// This method is like [`iter`](#method.iter), except each flags value is paired with the
+            /// number of trailing zeros in its bits. Flags with multiple bits set, and any unknown bits
+            /// yielded together as a final flags value, are paired with the position of their lowest set bit.
+            #[inline]
+            pub fn iter_positions(
+                &self,
+            ) -> impl $crate::__private::core::iter::Iterator<Item = (u32, $PublicBitFlags)> {
+                $crate::__private::core::iter::Iterator::map(self.iter(), |flag| {
+                    (flag.bits().trailing_zeros(), flag)
+                })
+            }
+
+            /// Yield a set of contained flags values in ascending order of their bits.
+            ///
+            /// This method yields the same flags values as [`iter`](#method.iter), but sorted by their bits
+            /// instead of the order they're defined in. Bits are compared as unsigned, so for signed bits types
+            /// a flag with the sign bit set is yielded last. Any unknown bits are yielded together as a single
+            /// flags value in its sorted position.
+            pub fn iter_sorted(
+                &self,
+            ) -> impl $crate::__private::core::iter::Iterator<Item = $PublicBitFlags> {
+                // Flipping the sign bit orders signed bits the same as unsigned ones.
+                // For unsigned bits types, `MIN` is zero so this doesn't change them
+                fn key(bits: $T) -> $T {
+                    bits ^ <$T>::MIN
+                }
+
+                let source = self.bits();
+                let mut last = $crate::__private::core::option::Option::None;
+
+                $crate::__private::core::iter::from_fn(move || {
+                    let mut next = $crate::__private::core::option::Option::None;
+
+                    // Yielded flags values never have the same bits, so the next one is the smallest after the last
+                    for flag in <$PublicBitFlags as $crate::Flags>::iter(&$PublicBitFlags::from_bits_retain(source)) {
+                        let bits = key(flag.bits());
+
+                        let after_last = match last {
+                            $crate::__private::core::option::Option::Some(last) => bits > last,
+                            $crate::__private::core::option::Option::None => true,
+                        };
+                        let before_next = match next {
+                            $crate::__private::core::option::Option::Some(next) => bits < next,
+                            $crate::__private::core::option::Option::None => true,
+                        };
+
+                        if after_last && before_next {
+                            next = $crate::__private::core::option::Option::Some(bits);
+                        }
+                    }
+
+                    last = next;
+                    $crate::__private::core::option::Option::map(next, |bits| $PublicBitFlags::from_bits_retain(key(bits)))
+                })
+            }
+
+            /// Yield a set of contained flags values, preferring the largest defined flags.
+            ///
+            /// This method is like [`iter`](#method.iter), except instead of yielding flags in the order
+            /// they're defined, it yields whichever contained flag with bits that haven't been yielded yet
+            /// has the most bits set. Flags with the same number of bits set are yielded in the order they're
+            /// defined. This means a value with all the bits of a defined multi-bit flag set will yield that flag
+            /// instead of any single-bit flags it covers. Any unknown bits will be yielded together as a final flags value.
+            pub fn iter_coalesced(
+                &self,
+            ) -> impl $crate::__private::core::iter::Iterator<Item = $PublicBitFlags> {
+                let source = self.bits();
+                let mut remaining = source;
+                let mut done = false;
+
+                $crate::__private::core::iter::from_fn(move || {
+                    if done {
+                        return $crate::__private::core::option::Option::None;
+                    }
+
+                    let mut largest = <$T as $crate::Bits>::EMPTY;
+                    for flag in <$PublicBitFlags as $crate::Flags>::FLAGS {
+                        let bits = flag.value().bits();
+
+                        // Only consider contained, named flags that cover bits that haven't been yielded yet
+                        if flag.is_named()
+                            && bits & source == bits
+                            && bits & remaining != <$T as $crate::Bits>::EMPTY
+                            && bits.count_ones() > largest.count_ones()
+                        {
+                            largest = bits;
+                        }
+                    }
+
+                    if largest != <$T as $crate::Bits>::EMPTY {
+                        remaining &= !largest;
+
+                        return $crate::__private::core::option::Option::Some($PublicBitFlags::from_bits_retain(largest));
+                    }
+
+                    done = true;
+
+                    // After yielding named flags, any bits left over are yielded as one final value
+                    if remaining != <$T as $crate::Bits>::EMPTY {
+                        $crate::__private::core::option::Option::Some($PublicBitFlags::from_bits_retain(remaining))
+                    } else {
+                        $crate::__private::core::option::Option::None
+                    }
+                })
+            }
+
+            /// Yield the positions of all set bits, in ascending order.
+            ///
+            /// This method is lower-level than [`iter`](#method.iter). It works on the raw bits, so
+            /// unknown bits are yielded along with the bits of any defined flags, and multi-bit flags
+            /// yield a position for each of their bits.
+            #[inline]
+            pub fn iter_bit_positions(
+                &self,
+            ) -> impl $crate::__private::core::iter::Iterator<Item = u32> {
+                let mut remaining = self.bits();
+
+                $crate::__private::core::iter::from_fn(move || {
+                    if remaining == <$T as $crate::Bits>::EMPTY {
+                        return $crate::__private::core::option::Option::None;
+                    }
+
+                    let position = remaining.trailing_zeros();
+                    let bit: $T = 1 << position;
+                    remaining &= !bit;
+
+                    $crate::__private::core::option::Option::Some(position)
+                })
+            }
         }
 
         $(#[$outer:meta])*
@@ -317,10 +893,535 @@ macro_rules! __impl_public_bitflags_iter {
                 self.iter()
             }
         }
+
+        $crate::__impl_public_bitflags_alloc! {
+            $(#[$outer])*
+            $BitFlags: $T, $PublicBitFlags
+        }
     };
 }
 
-/// Implement traits on the public (user-facing) bitflags type.
+/// Implement methods on the public (user-facing) bitflags type that need an allocator.
+///
+/// Like the macros for external libraries, this macro is called in an end-user's library,
+/// so it's defined twice; once with the `alloc` feature and once without.
+#[macro_export]
+#[doc(hidden)]
+#[cfg(feature = "alloc")]
+macro_rules! __impl_public_bitflags_alloc {
+    (
+        $(#[$outer:meta])*
+        $BitFlags:ident: $T:ty, $PublicBitFlags:ident
+    ) => {
+        $(#[$outer])*
+        impl $BitFlags {
+            /// Collect the positions of all set bits, in ascending order.
+            ///
+            /// This method collects the same positions yielded by
+            /// [`iter_bit_positions`](#method.iter_bit_positions), including any unknown bits.
+            #[inline]
+            pub fn bit_positions(&self) -> $crate::__private::Vec<u32> {
+                $crate::__private::core::iter::Iterator::collect(self.iter_bit_positions())
+            }
+        }
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+#[cfg(not(feature = "alloc"))]
+macro_rules! __impl_public_bitflags_alloc {
+    (
+        $(#[$outer:meta])*
+        $BitFlags:ident: $T:ty, $PublicBitFlags:ident
+    ) => {};
+}
+
+/// Implement items on the public (user-facing) bitflags type that are opted into
+/// through `#[bitflags(..)]` options.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __impl_public_bitflags_options {
+    // `default = A | B`: Implement `Default`, returning the union of the given flags
+    (
+        options: [{ default = $($Flag:ident)|+ } $($options:tt)*],
+        $PublicBitFlags:ident: $T:ty
+    ) => {
+        impl $crate::__private::core::default::Default for $PublicBitFlags {
+            #[inline]
+            fn default() -> Self {
+                Self::empty()$(.union(Self::$Flag))+
+            }
+        }
+
+        $crate::__impl_public_bitflags_options! {
+            options: [$($options)*],
+            $PublicBitFlags: $T
+        }
+    };
+    // `new`: Add a `new` constructor that retains all bits
+    (
+        options: [{ new } $($options:tt)*],
+        $PublicBitFlags:ident: $T:ty
+    ) => {
+        impl $PublicBitFlags {
+            /// Convert from a bits value exactly.
+            ///
+            /// This method is the same as `from_bits_retain`, so any unknown bits are retained.
+            #[inline]
+            pub const fn new(bits: $T) -> Self {
+                Self::from_bits_retain(bits)
+            }
+        }
+
+        $crate::__impl_public_bitflags_options! {
+            options: [$($options)*],
+            $PublicBitFlags: $T
+        }
+    };
+    // `canonical`: Implement comparison and hashing traits over only the known bits
+    (
+        options: [{ canonical } $($options:tt)*],
+        $PublicBitFlags:ident: $T:ty
+    ) => {
+        impl $crate::__private::core::cmp::PartialEq for $PublicBitFlags {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.bits() & Self::all().bits() == other.bits() & Self::all().bits()
+            }
+        }
+
+        impl $crate::__private::core::cmp::Eq for $PublicBitFlags {}
+
+        impl $crate::__private::core::cmp::PartialOrd for $PublicBitFlags {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> $crate::__private::core::option::Option<$crate::__private::core::cmp::Ordering> {
+                $crate::__private::core::option::Option::Some($crate::__private::core::cmp::Ord::cmp(self, other))
+            }
+        }
+
+        impl $crate::__private::core::cmp::Ord for $PublicBitFlags {
+            #[inline]
+            fn cmp(&self, other: &Self) -> $crate::__private::core::cmp::Ordering {
+                $crate::__private::core::cmp::Ord::cmp(
+                    &(self.bits() & Self::all().bits()),
+                    &(other.bits() & Self::all().bits()),
+                )
+            }
+        }
+
+        impl $crate::__private::core::hash::Hash for $PublicBitFlags {
+            #[inline]
+            fn hash<H: $crate::__private::core::hash::Hasher>(&self, state: &mut H) {
+                $crate::__private::core::hash::Hash::hash(&(self.bits() & Self::all().bits()), state)
+            }
+        }
+
+        $crate::__impl_public_bitflags_options! {
+            options: [$($options)*],
+            $PublicBitFlags: $T
+        }
+    };
+    // `subset_ord`: Implement `PartialOrd` as the subset order over all bits
+    (
+        options: [{ subset_ord } $($options:tt)*],
+        $PublicBitFlags:ident: $T:ty
+    ) => {
+        impl $crate::__private::core::cmp::PartialOrd for $PublicBitFlags {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> $crate::__private::core::option::Option<$crate::__private::core::cmp::Ordering> {
+                let (lhs, rhs) = (self.bits(), other.bits());
+
+                if lhs == rhs {
+                    $crate::__private::core::option::Option::Some($crate::__private::core::cmp::Ordering::Equal)
+                } else if lhs & rhs == lhs {
+                    $crate::__private::core::option::Option::Some($crate::__private::core::cmp::Ordering::Less)
+                } else if lhs & rhs == rhs {
+                    $crate::__private::core::option::Option::Some($crate::__private::core::cmp::Ordering::Greater)
+                } else {
+                    $crate::__private::core::option::Option::None
+                }
+            }
+        }
+
+        $crate::__impl_public_bitflags_options! {
+            options: [$($options)*],
+            $PublicBitFlags: $T
+        }
+    };
+    // `validate`: Add a `validate` method that fails if any unknown bits are set
+    (
+        options: [{ validate } $($options:tt)*],
+        $PublicBitFlags:ident: $T:ty
+    ) => {
+        impl $PublicBitFlags {
+            /// Check that the flags value doesn't contain any unknown bits.
+            ///
+            /// This method returns the unknown bits as an error if there are any.
+            /// It's useful for asserting a flags value is well-formed after converting it
+            /// with `from_bits_retain`, like at an FFI boundary or after deserialization.
+            #[inline]
+            pub const fn validate(&self) -> $crate::__private::core::result::Result<(), $crate::convert::UnknownBits<$T>> {
+                let unknown = self.bits() & !Self::all().bits();
+
+                if unknown == <$T as $crate::Bits>::EMPTY {
+                    $crate::__private::core::result::Result::Ok(())
+                } else {
+                    $crate::__private::core::result::Result::Err($crate::convert::UnknownBits::new(unknown))
+                }
+            }
+        }
+
+        $crate::__impl_public_bitflags_options! {
+            options: [$($options)*],
+            $PublicBitFlags: $T
+        }
+    };
+    // `disjoint`: Assert that no two named flags share any bits
+    (
+        options: [{ disjoint } $($options:tt)*],
+        $PublicBitFlags:ident: $T:ty
+    ) => {
+        const _: $crate::__private::NamedFlagsOverlap<false> = $crate::__private::NamedFlagsOverlap::<{
+            let flags = <$PublicBitFlags as $crate::Flags>::FLAGS;
+            let mut overlapping = false;
+
+            let mut i = 0;
+            while i < flags.len() {
+                let mut j = i + 1;
+                while j < flags.len() {
+                    if !flags[i].name().is_empty()
+                        && !flags[j].name().is_empty()
+                        && flags[i].value().bits() & flags[j].value().bits() != <$T as $crate::Bits>::EMPTY
+                    {
+                        overlapping = true;
+                    }
+
+                    j += 1;
+                }
+
+                i += 1;
+            }
+
+            overlapping
+        }>;
+
+        $crate::__impl_public_bitflags_options! {
+            options: [$($options)*],
+            $PublicBitFlags: $T
+        }
+    };
+    // `bits_eq`: Implement `PartialEq` with the bits type
+    (
+        options: [{ bits_eq } $($options:tt)*],
+        $PublicBitFlags:ident: $T:ty
+    ) => {
+        $crate::__impl_public_bitflags_bits_eq! {
+            $PublicBitFlags: $T
+        }
+
+        $crate::__impl_public_bitflags_options! {
+            options: [$($options)*],
+            $PublicBitFlags: $T
+        }
+    };
+    // `bits_ops`: Implement operators with the bits type
+    (
+        options: [{ bits_ops } $($options:tt)*],
+        $PublicBitFlags:ident: $T:ty
+    ) => {
+        $crate::__impl_public_bitflags_bits_ops! {
+            $PublicBitFlags: $T
+        }
+
+        $crate::__impl_public_bitflags_options! {
+            options: [$($options)*],
+            $PublicBitFlags: $T
+        }
+    };
+    // `valid_mask`: Assert that every flag fits within the mask
+    (
+        options: [{ valid_mask = $($mask:tt)+ } $($options:tt)*],
+        $PublicBitFlags:ident: $T:ty
+    ) => {
+        const _: $crate::__private::FlagsOutsideValidMask<false> = $crate::__private::FlagsOutsideValidMask::<{
+            let flags = <$PublicBitFlags as $crate::Flags>::FLAGS;
+            let mask: $T = $($mask)+;
+            let mut outside = false;
+
+            let mut i = 0;
+            while i < flags.len() {
+                if flags[i].value().bits() & !mask != <$T as $crate::Bits>::EMPTY {
+                    outside = true;
+                }
+
+                i += 1;
+            }
+
+            outside
+        }>;
+
+        $crate::__impl_public_bitflags_options! {
+            options: [$($options)*],
+            $PublicBitFlags: $T
+        }
+    };
+    // Skip any other options
+    (
+        options: [{ $($option:tt)* } $($options:tt)*],
+        $PublicBitFlags:ident: $T:ty
+    ) => {
+        $crate::__impl_public_bitflags_options! {
+            options: [$($options)*],
+            $PublicBitFlags: $T
+        }
+    };
+    (
+        options: [],
+        $PublicBitFlags:ident: $T:ty
+    ) => {};
+}
+
+/// Set `Flags::RENAME_ALL` in the `Flags` implementation for the public (user-facing) bitflags type.
+///
+/// Names are left verbatim unless the `rename_all` option is given.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __impl_public_bitflags_rename_all {
+    // `rename_all = "lowercase"`: Write flag names in lowercase
+    (
+        options: [{ rename_all = "lowercase" } $($options:tt)*]
+    ) => {
+        const RENAME_ALL: $crate::parser::RenameAll = $crate::parser::RenameAll::Lowercase;
+    };
+    // `rename_all = "kebab-case"`: Write flag names in lowercase, with dashes instead of underscores
+    (
+        options: [{ rename_all = "kebab-case" } $($options:tt)*]
+    ) => {
+        const RENAME_ALL: $crate::parser::RenameAll = $crate::parser::RenameAll::KebabCase;
+    };
+    // Skip any other options
+    (
+        options: [{ $($option:tt)* } $($options:tt)*]
+    ) => {
+        $crate::__impl_public_bitflags_rename_all! {
+            options: [$($options)*]
+        }
+    };
+    (
+        options: []
+    ) => {};
+}
+
+/// Implement `TransparentFlags` on the public (user-facing) bitflags type.
+///
+/// The trait is only implemented if the flags type has a `#[repr(transparent)]` attribute,
+/// or the `transparent` option.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __impl_public_bitflags_transparent {
+    // `transparent`: The public type has `#[repr(transparent)]` added to it,
+    // so also assert its layout matches `$T`
+    (
+        options: [{ transparent } $($options:tt)*],
+        attrs: [$($attrs:tt)*],
+        $PublicBitFlags:ident: $T:ty
+    ) => {
+        const _: [(); $crate::__private::core::mem::size_of::<$T>()] =
+            [(); $crate::__private::core::mem::size_of::<$PublicBitFlags>()];
+        const _: [(); $crate::__private::core::mem::align_of::<$T>()] =
+            [(); $crate::__private::core::mem::align_of::<$PublicBitFlags>()];
+
+        // SAFETY: `$PublicBitFlags` is `#[repr(transparent)]` over its internal type,
+        // which is `#[repr(transparent)]` over `$T`
+        unsafe impl $crate::TransparentFlags for $PublicBitFlags {}
+    };
+    // Skip any other options
+    (
+        options: [{ $($option:tt)* } $($options:tt)*],
+        attrs: [$($attrs:tt)*],
+        $PublicBitFlags:ident: $T:ty
+    ) => {
+        $crate::__impl_public_bitflags_transparent! {
+            options: [$($options)*],
+            attrs: [$($attrs)*],
+            $PublicBitFlags: $T
+        }
+    };
+    // `#[repr(transparent)]`: The public type has the same ABI as its internal type, which
+    // has the same ABI as `$T`
+    (
+        options: [],
+        attrs: [#[repr(transparent)] $($attrs:tt)*],
+        $PublicBitFlags:ident: $T:ty
+    ) => {
+        // SAFETY: `$PublicBitFlags` is `#[repr(transparent)]` over its internal type,
+        // which is `#[repr(transparent)]` over `$T`
+        unsafe impl $crate::TransparentFlags for $PublicBitFlags {}
+    };
+    // Skip any other attributes
+    (
+        options: [],
+        attrs: [#[$other:ident $($args:tt)*] $($attrs:tt)*],
+        $PublicBitFlags:ident: $T:ty
+    ) => {
+        $crate::__impl_public_bitflags_transparent! {
+            options: [],
+            attrs: [$($attrs)*],
+            $PublicBitFlags: $T
+        }
+    };
+    (
+        options: [],
+        attrs: [],
+        $PublicBitFlags:ident: $T:ty
+    ) => {};
+}
+
+/// Implement comparisons between the public (user-facing) bitflags type and its bits type.
+///
+/// This is opted into through the `bits_eq` option.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __impl_public_bitflags_bits_eq {
+    (
+        $(#[$outer:meta])*
+        $PublicBitFlags:ident: $T:ty
+    ) => {
+        $(#[$outer])*
+        impl $crate::__private::core::cmp::PartialEq<$T> for $PublicBitFlags {
+            /// Whether the bits in a flags value are the same as a raw bits value.
+            ///
+            /// This compares all bits, including unknown bits, like `self.bits() == *other`.
+            #[inline]
+            fn eq(&self, other: &$T) -> bool {
+                self.bits() == *other
+            }
+        }
+    };
+}
+
+/// Implement operators between the public (user-facing) bitflags type and its bits type.
+///
+/// All bits are retained, so any unknown bits in either operand can appear in the result.
+/// This is opted into through the `bits_ops` option.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __impl_public_bitflags_bits_ops {
+    (
+        $(#[$outer:meta])*
+        $PublicBitFlags:ident: $T:ty
+    ) => {
+        $(#[$outer])*
+        impl $crate::__private::core::ops::BitOr<$T> for $PublicBitFlags {
+            type Output = Self;
+
+            /// The bitwise or (`|`) of the bits in a flags value and a raw bits value.
+            #[inline]
+            fn bitor(self, other: $T) -> Self {
+                Self::from_bits_retain(self.bits() | other)
+            }
+        }
+
+        $(#[$outer])*
+        impl $crate::__private::core::ops::BitOrAssign<$T> for $PublicBitFlags {
+            /// The bitwise or (`|`) of the bits in a flags value and a raw bits value.
+            #[inline]
+            fn bitor_assign(&mut self, other: $T) {
+                *self = Self::from_bits_retain(self.bits() | other);
+            }
+        }
+
+        $(#[$outer])*
+        impl $crate::__private::core::ops::BitAnd<$T> for $PublicBitFlags {
+            type Output = Self;
+
+            /// The bitwise and (`&`) of the bits in a flags value and a raw bits value.
+            #[inline]
+            fn bitand(self, other: $T) -> Self {
+                Self::from_bits_retain(self.bits() & other)
+            }
+        }
+
+        $(#[$outer])*
+        impl $crate::__private::core::ops::BitAndAssign<$T> for $PublicBitFlags {
+            /// The bitwise and (`&`) of the bits in a flags value and a raw bits value.
+            #[inline]
+            fn bitand_assign(&mut self, other: $T) {
+                *self = Self::from_bits_retain(self.bits() & other);
+            }
+        }
+
+        $(#[$outer])*
+        impl $crate::__private::core::ops::BitXor<$T> for $PublicBitFlags {
+            type Output = Self;
+
+            /// The bitwise exclusive-or (`^`) of the bits in a flags value and a raw bits value.
+            #[inline]
+            fn bitxor(self, other: $T) -> Self {
+                Self::from_bits_retain(self.bits() ^ other)
+            }
+        }
+
+        $(#[$outer])*
+        impl $crate::__private::core::ops::BitXorAssign<$T> for $PublicBitFlags {
+            /// The bitwise exclusive-or (`^`) of the bits in a flags value and a raw bits value.
+            #[inline]
+            fn bitxor_assign(&mut self, other: $T) {
+                *self = Self::from_bits_retain(self.bits() ^ other);
+            }
+        }
+
+        $(#[$outer])*
+        impl $crate::__private::core::ops::Sub<$T> for $PublicBitFlags {
+            type Output = Self;
+
+            /// The intersection of a flags value with the complement of a raw bits value (`&!`).
+            #[inline]
+            fn sub(self, other: $T) -> Self {
+                Self::from_bits_retain(self.bits() & !other)
+            }
+        }
+
+        $(#[$outer])*
+        impl $crate::__private::core::ops::SubAssign<$T> for $PublicBitFlags {
+            /// The intersection of a flags value with the complement of a raw bits value (`&!`).
+            #[inline]
+            fn sub_assign(&mut self, other: $T) {
+                *self = Self::from_bits_retain(self.bits() & !other);
+            }
+        }
+    };
+}
+
+/// Implement `Extend` between the public (user-facing) bitflags type and its bits type.
+///
+/// All bits are retained, so any unknown bits in the raw values accumulate.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __impl_public_bitflags_bits_extend {
+    (
+        $(#[$outer:meta])*
+        $PublicBitFlags:ident: $T:ty
+    ) => {
+        $(#[$outer])*
+        impl $crate::__private::core::iter::Extend<$T> for $PublicBitFlags {
+            /// The bitwise or (`|`) of each raw bits value.
+            ///
+            /// All bits are retained, so any unknown bits in the raw values accumulate.
+            fn extend<T: $crate::__private::core::iter::IntoIterator<Item = $T>>(
+                &mut self,
+                iterator: T,
+            ) {
+                for bits in iterator {
+                    *self = Self::from_bits_retain(self.bits() | bits);
+                }
+            }
+        }
+    };
+}
+
+/// Implement traits on the public (user-facing) bitflags type.
 #[macro_export]
 #[doc(hidden)]
 macro_rules! __impl_public_bitflags_ops {
@@ -503,7 +1604,53 @@ macro_rules! __impl_public_bitflags_ops {
 #[macro_export]
 #[doc(hidden)]
 macro_rules! __impl_public_bitflags_consts {
+    // Entrypoint: Check whether the `docs` option is used
+    // before generating any constants
     (
+        options: [$($options:tt)*],
+        $(#[$outer:meta])*
+        $PublicBitFlags:ident: $T:ty {
+            $($flags:tt)*
+        }
+    ) => {
+        $crate::__impl_public_bitflags_consts! {
+            docs: false,
+            unprocessed: [$($options)*],
+            options: [$($options)*],
+            $(#[$outer])*
+            $PublicBitFlags: $T {
+                $($flags)*
+            }
+        }
+    };
+    // `docs`: Capture the doc comments on flags
+    (
+        docs: $docs:tt,
+        unprocessed: [{ docs } $($unprocessed:tt)*],
+        $($rest:tt)*
+    ) => {
+        $crate::__impl_public_bitflags_consts! {
+            docs: true,
+            unprocessed: [$($unprocessed)*],
+            $($rest)*
+        }
+    };
+    // Skip any other options
+    (
+        docs: $docs:tt,
+        unprocessed: [{ $($option:tt)* } $($unprocessed:tt)*],
+        $($rest:tt)*
+    ) => {
+        $crate::__impl_public_bitflags_consts! {
+            docs: $docs,
+            unprocessed: [$($unprocessed)*],
+            $($rest)*
+        }
+    };
+    (
+        docs: $docs:tt,
+        unprocessed: [],
+        options: [$($options:tt)*],
         $(#[$outer:meta])*
         $PublicBitFlags:ident: $T:ty {
             $(
@@ -518,12 +1665,16 @@ macro_rules! __impl_public_bitflags_consts {
                 $crate::__bitflags_flag!({
                     name: $Flag,
                     named: {
-                        $(#[$inner $($args)*])*
-                        #[allow(
-                            deprecated,
-                            non_upper_case_globals,
-                        )]
-                        pub const $Flag: Self = Self::from_bits_retain($value);
+                        $crate::__bitflags_item_attrs! {
+                            $(#[$inner $($args)*])*
+                            {
+                                #[allow(
+                                    deprecated,
+                                    non_upper_case_globals,
+                                )]
+                                pub const $Flag: Self = Self::from_bits_retain($value);
+                            }
+                        }
                     },
                     unnamed: {},
                 });
@@ -544,7 +1695,12 @@ macro_rules! __impl_public_bitflags_consts {
                                         deprecated,
                                         non_upper_case_globals,
                                     )]
-                                    $crate::Flag::new($crate::__private::core::stringify!($Flag), $PublicBitFlags::$Flag)
+                                    $crate::Flag::__private_const_new(
+                                        $crate::__private::core::stringify!($Flag),
+                                        $PublicBitFlags::$Flag,
+                                        $crate::__bitflags_doc!(docs: $docs, $(#[$inner $($args)*])*),
+                                        $crate::__bitflags_group!($(#[$inner $($args)*])*),
+                                    )
                                 }
                             )
                         },
@@ -556,7 +1712,12 @@ macro_rules! __impl_public_bitflags_consts {
                                         deprecated,
                                         non_upper_case_globals,
                                     )]
-                                    $crate::Flag::new("", $PublicBitFlags::from_bits_retain($value))
+                                    $crate::Flag::__private_const_new(
+                                        "",
+                                        $PublicBitFlags::from_bits_retain($value),
+                                        $crate::__bitflags_doc!(docs: $docs, $(#[$inner $($args)*])*),
+                                        $crate::__bitflags_group!($(#[$inner $($args)*])*),
+                                    )
                                 }
                             )
                         },
@@ -564,6 +1725,10 @@ macro_rules! __impl_public_bitflags_consts {
                 )*
             ];
 
+            $crate::__impl_public_bitflags_rename_all! {
+                options: [$($options)*]
+            }
+
             type Bits = $T;
 
             fn bits(&self) -> $T {
@@ -1,30 +1,78 @@
+mod alias;
 mod all;
+mod all_bits;
+mod bit;
+mod bit_positions;
 mod bitflags_match;
 mod bits;
+mod bits_ops;
+mod bits_range;
+mod by_ref;
+mod bytes;
+mod canonical;
+mod cfg_attr;
+mod chain;
+mod changed;
+mod combine_bits;
 mod complement;
+mod const_fn;
 mod contains;
+mod contains_name;
+mod default;
+mod defined_bit;
 mod difference;
+mod disjoint;
 mod empty;
 mod eq;
 mod extend;
 mod flags;
+mod flags_macro;
 mod fmt;
+mod fmt_parts;
+mod from_bit_index;
 mod from_bits;
+mod from_bits_checked;
 mod from_bits_retain;
 mod from_bits_truncate;
+mod from_bits_with_mask;
+mod from_enum;
 mod from_name;
+mod from_names;
+mod group;
 mod insert;
+mod insert_bits;
+mod intersect;
 mod intersection;
 mod intersects;
 mod is_all;
+mod is_all_bits;
 mod is_empty;
 mod iter;
+mod keep_unknown;
+mod kind;
+mod minimal;
+mod new;
+mod parse;
 mod parser;
+mod present_of;
+mod raw_ops;
 mod remove;
+mod remove_bits;
+mod remove_exact;
+mod remove_unknown;
+mod rename_all;
+mod shrink_to_known;
+mod subset_ord;
 mod symmetric_difference;
+#[cfg(feature = "std")]
+mod to_text;
+mod transparent;
 mod truncate;
 mod union;
 mod unknown;
+mod valid_mask;
+mod validate;
+mod wide_bits;
 
 bitflags! {
     #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
@@ -0,0 +1,68 @@
+use crate::Flags;
+
+// Changing this alias changes the bits type of every flags type below
+type Word = u16;
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct TestAliasA: Word {
+        const A = 1;
+        const B = 1 << 1;
+        const HIGH = 1 << (Word::BITS - 1);
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct TestAliasB: self::Word {
+        const A = 1;
+        const AB = 1 | 1 << 1;
+
+        const _ = !0;
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TestAliasImpl(Word);
+
+bitflags! {
+    impl TestAliasImpl: Word {
+        const A = 1;
+    }
+}
+
+#[test]
+fn cases() {
+    assert_eq!(1 | 1 << 1 | 1 << 15, TestAliasA::all().bits());
+    assert_eq!(!0, TestAliasB::all().bits());
+    assert_eq!(1, TestAliasImpl::all().bits());
+
+    assert_eq!(
+        Some(TestAliasA::HIGH),
+        TestAliasA::from_bits(1 << (Word::BITS - 1))
+    );
+    assert_eq!(TestAliasA::A, TestAliasA::from_bits_truncate(1 | 1 << 2));
+
+    assert_eq!(
+        TestAliasA::HIGH | TestAliasA::A,
+        TestAliasA::from_le_bytes((1 as Word | 1 << 15).to_le_bytes())
+    );
+
+    let mut formatted = String::new();
+    crate::parser::to_writer(&(TestAliasA::A | TestAliasA::HIGH), &mut formatted).unwrap();
+    assert_eq!("A | HIGH", formatted);
+    assert_eq!(
+        TestAliasB::AB,
+        crate::parser::from_str::<TestAliasB>("AB").unwrap()
+    );
+}
+
+#[test]
+fn generic_width() {
+    assert_eq!(Word::BITS, width::<TestAliasA>());
+    assert_eq!(Word::BITS, width::<TestAliasB>());
+    assert_eq!(Word::BITS, width::<TestAliasImpl>());
+}
+
+// The bits type seen through the `Flags` trait is the aliased type
+fn width<F: Flags<Bits = Word>>() -> u32 {
+    F::Bits::BITS
+}
@@ -21,3 +21,64 @@ where
     assert_eq!(expected, inherent().bits(), "T::all()");
     assert_eq!(expected, T::all().bits(), "Flags::all()");
 }
+
+#[test]
+fn zero_flags() {
+    // Zero-valued flags never contribute bits to `all`
+    zero_case(0, TestZero::all());
+    zero_case(1, TestZeroOne::all());
+    zero_case(0, TestEmpty::all());
+    zero_case(1 | 1 << 1 | 1 << 2, TestFlags::all());
+    zero_case(!0, TestExternal::all());
+
+    // A zero-valued flag is contained in every flags value, including `all`
+    assert!(TestZeroOne::all().contains(TestZeroOne::ZERO));
+    assert!(TestZeroOne::empty().contains(TestZeroOne::ZERO));
+    assert!(TestZero::all().is_empty());
+}
+
+#[track_caller]
+fn zero_case<T: Flags<Bits = u8> + std::fmt::Debug>(expected: u8, all: T) {
+    assert_eq!(expected, all.bits(), "{:?} is all()", all);
+    assert_eq!(
+        expected,
+        T::all_nonzero().bits(),
+        "{:?} is all_nonzero()",
+        all
+    );
+
+    let nonzero = T::FLAGS
+        .iter()
+        .filter(|flag| flag.value().bits() != 0)
+        .fold(0, |bits, flag| bits | flag.value().bits());
+    assert_eq!(
+        nonzero,
+        all.bits(),
+        "{:?} is the union of nonzero flags",
+        all
+    );
+}
+
+#[test]
+fn all_flags() {
+    all_flags_case(TestFlags::ALL_FLAGS);
+    all_flags_case(TestZero::ALL_FLAGS);
+    all_flags_case(TestEmpty::ALL_FLAGS);
+    all_flags_case(TestOverlapping::ALL_FLAGS);
+    all_flags_case(TestExternal::ALL_FLAGS);
+}
+
+#[track_caller]
+fn all_flags_case<T: Flags<Bits = u8> + std::fmt::Debug>(value: T) {
+    let expected = T::FLAGS
+        .iter()
+        .fold(0, |bits, flag| bits | flag.value().bits());
+
+    assert_eq!(
+        expected,
+        value.bits(),
+        "{:?} is the union of all flags",
+        value
+    );
+    assert_eq!(T::all().bits(), value.bits(), "{:?} is all()", value);
+}
@@ -0,0 +1,23 @@
+use super::*;
+
+use crate::Flags;
+
+#[test]
+fn cases() {
+    case::<TestFlags>(1 | 1 << 1 | 1 << 2);
+    case::<TestFlagsInvert>(1 | 1 << 1 | 1 << 2);
+    case::<TestZero>(0);
+    case::<TestEmpty>(0);
+    case::<TestExternal>(!0);
+
+    assert_eq!(1 << 3, (1 << 1 | 1 << 3) & !TestFlags::all_bits());
+}
+
+#[track_caller]
+fn case<T: Flags>(expected: T::Bits)
+where
+    T::Bits: std::fmt::Debug + PartialEq,
+{
+    assert_eq!(expected, T::all_bits(), "T::all_bits()");
+    assert_eq!(expected, T::all().bits(), "T::all().bits()");
+}
@@ -0,0 +1,32 @@
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct TestBit: u32 {
+        const A = bit!(0);
+        const B = bit!(1);
+        const C = bit!(31);
+
+        const AB = Self::A.bits() | Self::B.bits();
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct TestBitSigned: i8 {
+        const A = bit!(0);
+        const B = bit!(7);
+    }
+}
+
+#[test]
+fn cases() {
+    assert_eq!(1, TestBit::A.bits());
+    assert_eq!(1 << 1, TestBit::B.bits());
+    assert_eq!(1 << 31, TestBit::C.bits());
+    assert_eq!(1 | 1 << 1, TestBit::AB.bits());
+
+    assert_eq!(1, TestBitSigned::A.bits());
+    assert_eq!(i8::MIN, TestBitSigned::B.bits());
+
+    assert_eq!(TestBit::B, TestBit::from_bits_retain(bit!(1)));
+
+    const POSITION: u32 = 4;
+    assert_eq!(16u64, bit!(POSITION));
+}
@@ -0,0 +1,87 @@
+use super::*;
+
+use crate::Flags;
+
+#[test]
+fn cases() {
+    case(&[], TestFlags::empty());
+
+    case(&[0], TestFlags::A);
+    case(&[0, 1, 2], TestFlags::all());
+    case(
+        &[1, 3, 7],
+        TestFlags::B | TestFlags::from_bits_retain(1 << 3 | 1 << 7),
+    );
+
+    case(&[], TestZero::ZERO);
+
+    // Multi-bit flags yield a position for each of their bits
+    case(&[0, 1], TestOverlapping::AB);
+    case(&[0, 1, 2], TestOverlapping::AB | TestOverlapping::BC);
+
+    case(&[0, 1, 2, 3, 4, 5, 6, 7], TestExternal::all());
+}
+
+#[track_caller]
+fn case<T: Flags + std::fmt::Debug + IntoBitPositions>(expected: &[u32], value: T) {
+    assert_eq!(
+        expected,
+        &*value.iter_bit_positions_vec(),
+        "{:?}.iter_bit_positions()",
+        value
+    );
+
+    #[cfg(feature = "alloc")]
+    assert_eq!(
+        expected,
+        &*value.bit_positions_vec(),
+        "{:?}.bit_positions()",
+        value
+    );
+}
+
+#[test]
+fn signed() {
+    bitflags! {
+        #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+        pub struct TestSigned: i8 {
+            const MIN = i8::MIN;
+            const A = 1;
+        }
+    }
+
+    assert_eq!(
+        vec![0, 7],
+        TestSigned::all().iter_bit_positions().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![7],
+        TestSigned::MIN.iter_bit_positions().collect::<Vec<_>>()
+    );
+}
+
+trait IntoBitPositions {
+    fn iter_bit_positions_vec(&self) -> Vec<u32>;
+
+    #[cfg(feature = "alloc")]
+    fn bit_positions_vec(&self) -> Vec<u32>;
+}
+
+macro_rules! impl_into_bit_positions {
+    ($($ty:ty),*) => {
+        $(
+            impl IntoBitPositions for $ty {
+                fn iter_bit_positions_vec(&self) -> Vec<u32> {
+                    self.iter_bit_positions().collect()
+                }
+
+                #[cfg(feature = "alloc")]
+                fn bit_positions_vec(&self) -> Vec<u32> {
+                    self.bit_positions()
+                }
+            }
+        )*
+    };
+}
+
+impl_into_bit_positions!(TestFlags, TestZero, TestOverlapping, TestExternal);
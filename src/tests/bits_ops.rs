@@ -0,0 +1,122 @@
+use crate::{Bits, BitsCount};
+
+#[test]
+fn cases() {
+    case(0u8, 0u8);
+    case(0b1010u8, 0b0110u8);
+    case(!0u8, 1u8);
+    case(0x1234u16, 0xff00u16);
+    case(-1i32, i32::MIN);
+    case(1u128 << 100, !0u128);
+}
+
+#[track_caller]
+fn case<B>(a: B, b: B)
+where
+    B: Bits + std::fmt::Debug,
+{
+    assert_eq!(
+        a & b,
+        Bits::bits_and(a, b),
+        "Bits::bits_and({:?}, {:?})",
+        a,
+        b
+    );
+    assert_eq!(
+        a | b,
+        Bits::bits_or(a, b),
+        "Bits::bits_or({:?}, {:?})",
+        a,
+        b
+    );
+    assert_eq!(
+        a ^ b,
+        Bits::bits_xor(a, b),
+        "Bits::bits_xor({:?}, {:?})",
+        a,
+        b
+    );
+    assert_eq!(!a, Bits::bits_not(a), "Bits::bits_not({:?})", a);
+}
+
+#[test]
+fn zeros() {
+    zeros_case(8, 8, 0u8);
+    zeros_case(0, 0, u8::MAX);
+    zeros_case(7, 0, 1u8);
+    zeros_case(0, 7, 1u8 << 7);
+    zeros_case(3, 1, 0b0001_1010u8);
+
+    zeros_case(128, 128, 0u128);
+    zeros_case(0, 0, u128::MAX);
+    zeros_case(27, 100, 1u128 << 100);
+
+    zeros_case(32, 32, 0i32);
+    zeros_case(0, 0, -1i32);
+    zeros_case(0, 31, i32::MIN);
+    zeros_case(1, 0, i32::MAX);
+}
+
+#[track_caller]
+fn zeros_case<B>(leading: u32, trailing: u32, value: B)
+where
+    B: BitsCount + std::fmt::Debug,
+{
+    assert_eq!(
+        leading,
+        BitsCount::leading_zeros(value),
+        "BitsCount::leading_zeros({:?})",
+        value
+    );
+    assert_eq!(
+        trailing,
+        BitsCount::trailing_zeros(value),
+        "BitsCount::trailing_zeros({:?})",
+        value
+    );
+}
+
+#[test]
+fn count_ones() {
+    assert_eq!(0, BitsCount::count_ones(0u8));
+    assert_eq!(8, BitsCount::count_ones(u8::MAX));
+    assert_eq!(3, BitsCount::count_ones(0b0001_1010u8));
+    assert_eq!(128, BitsCount::count_ones(u128::MAX));
+    assert_eq!(1, BitsCount::count_ones(i32::MIN));
+    assert_eq!(32, BitsCount::count_ones(-1i32));
+}
+
+#[test]
+fn generic_lowest_highest() {
+    assert_eq!(None, lowest_highest(0u8));
+    assert_eq!(Some((0, 7)), lowest_highest(u8::MAX));
+    assert_eq!(Some((1, 4)), lowest_highest(0b0001_1010u8));
+    assert_eq!(Some((31, 31)), lowest_highest(i32::MIN));
+}
+
+// The positions of the lowest and highest set bits of any bits type
+fn lowest_highest<B: BitsCount>(bits: B) -> Option<(u32, u32)> {
+    if bits == B::EMPTY {
+        None
+    } else {
+        Some((
+            BitsCount::trailing_zeros(bits),
+            B::BITS - 1 - BitsCount::leading_zeros(bits),
+        ))
+    }
+}
+
+#[test]
+fn operator_methods() {
+    use crate::prelude::*;
+    use std::ops::{BitAnd, Not};
+
+    // The methods of the operator traits aren't ambiguous with `Bits` in scope
+    fn not<B: Bits>(bits: B) -> B {
+        bits.not()
+    }
+
+    assert_eq!(!1u8, not(1u8));
+    assert_eq!(!1u8, 1u8.not());
+    assert_eq!(1u8, 3u8.bitand(1));
+}
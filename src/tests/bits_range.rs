@@ -0,0 +1,38 @@
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct TestBitsRange: u32 {
+        const LOW = bits_range!(0..4);
+        const MID = bits_range!(4..=7);
+        const HIGH = bits_range!(16..32);
+        const TOP = bits_range!(31..=31);
+        const NONE = bits_range!(8..8);
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct TestBitsRangeSigned: i8 {
+        const ALL = bits_range!(0..8);
+        const SIGN = bits_range!(7..=7);
+    }
+}
+
+#[test]
+fn cases() {
+    assert_eq!(0x0000_000f, TestBitsRange::LOW.bits());
+    assert_eq!(0x0000_00f0, TestBitsRange::MID.bits());
+    assert_eq!(0xffff_0000, TestBitsRange::HIGH.bits());
+    assert_eq!(1 << 31, TestBitsRange::TOP.bits());
+    assert_eq!(0, TestBitsRange::NONE.bits());
+    assert_eq!(0xffff_00ff, TestBitsRange::all().bits());
+
+    assert_eq!(-1, TestBitsRangeSigned::ALL.bits());
+    assert_eq!(i8::MIN, TestBitsRangeSigned::SIGN.bits());
+
+    // Inclusive and exclusive ranges over the same bits are equal
+    assert_eq!(0b0011_1100u8, bits_range!(2..6));
+    assert_eq!(0b0011_1100u8, bits_range!(2..=5));
+    assert_eq!(u64::MAX, bits_range!(0..64));
+
+    const START: u32 = 60;
+    assert_eq!(0xf000_0000_0000_0000u64, bits_range!(START..64));
+    assert_eq!(0u64, bits_range!(START..START));
+}
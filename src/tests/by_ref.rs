@@ -0,0 +1,60 @@
+use super::*;
+
+use crate::Flags;
+
+bitflags! {
+    // This flags type deliberately doesn't derive `Clone` or `Copy`
+    #[derive(Debug)]
+    pub struct TestNotCopy: u8 {
+        const A = 1;
+        const B = 1 << 1;
+        const C = 1 << 2;
+    }
+}
+
+#[test]
+fn cases() {
+    let value = TestNotCopy::A | TestNotCopy::B;
+
+    assert!(value.intersects_ref(&TestNotCopy::A));
+    assert!(value.intersects_ref(&(TestNotCopy::A | TestNotCopy::C)));
+    assert!(!value.intersects_ref(&TestNotCopy::C));
+    assert!(!value.intersects_ref(&TestNotCopy::empty()));
+
+    assert!(value.contains_ref(&TestNotCopy::A));
+    assert!(value.contains_ref(&TestNotCopy::empty()));
+    assert!(!value.contains_ref(&(TestNotCopy::A | TestNotCopy::C)));
+    assert!(!value.contains_ref(&TestNotCopy::from_bits_retain(1 << 7)));
+
+    // The value is still usable, because nothing was moved
+    assert_eq!(1 | 1 << 1, value.bits());
+}
+
+#[test]
+fn generic() {
+    assert_eq!(
+        vec![0, 1],
+        contained(
+            &(TestNotCopy::A | TestNotCopy::B),
+            &[TestNotCopy::A, TestNotCopy::B, TestNotCopy::C]
+        )
+    );
+    assert_eq!(
+        vec![1, 2],
+        contained(
+            &TestFlags::all(),
+            &[TestFlags::empty(), TestFlags::A, TestFlags::ABC]
+        )
+    );
+}
+
+// Find which of a set of flags values intersect with and are contained in a source,
+// without needing to copy any of them
+fn contained<F: Flags>(value: &F, candidates: &[F]) -> Vec<usize> {
+    candidates
+        .iter()
+        .enumerate()
+        .filter(|(_, candidate)| value.intersects_ref(candidate) && value.contains_ref(candidate))
+        .map(|(i, _)| i)
+        .collect()
+}
@@ -0,0 +1,103 @@
+use super::*;
+
+use crate::{BitsBytes, Flags};
+
+#[test]
+fn cases() {
+    case(&[0], TestFlags::empty());
+    case(&[1 | 1 << 1 | 1 << 2], TestFlags::all());
+    case(
+        &[1 | 1 << 3],
+        TestFlags::A | TestFlags::from_bits_retain(1 << 3),
+    );
+
+    assert_eq!([0x04, 0x03, 0x02, 0x01], 0x01020304u32.to_le_bytes());
+    assert_eq!([0xff, 0xff], BitsBytes::to_le_bytes(&-1i16));
+    assert_eq!(
+        [1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x80],
+        BitsBytes::to_le_bytes(&(1u128 | 1 << 127))
+    );
+}
+
+#[track_caller]
+fn case<T: Flags + std::fmt::Debug>(expected: &[u8], value: T)
+where
+    T::Bits: BitsBytes,
+{
+    assert_eq!(
+        expected,
+        value.bits().to_le_bytes().as_ref(),
+        "{:?}.bits().to_le_bytes()",
+        value
+    );
+}
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct TestBytes: u32 {
+        const A = 1;
+        const B = 1 << 8;
+        const C = 1 << 31;
+    }
+}
+
+#[test]
+fn byte_order() {
+    let value = TestBytes::A | TestBytes::C | TestBytes::from_bits_retain(0x00ab_0000);
+
+    assert_eq!([0x80, 0xab, 0x00, 0x01], value.to_be_bytes());
+    assert_eq!([0x01, 0x00, 0xab, 0x80], value.to_le_bytes());
+
+    assert_eq!(value, TestBytes::from_be_bytes([0x80, 0xab, 0x00, 0x01]));
+    assert_eq!(value, TestBytes::from_le_bytes([0x01, 0x00, 0xab, 0x80]));
+
+    assert_eq!([0x00, 0x00, 0x01, 0x00], TestBytes::B.to_be_bytes());
+    assert_eq!([0x00, 0x01, 0x00, 0x00], TestBytes::B.to_le_bytes());
+
+    assert_eq!(
+        value.bits().to_be_bytes(),
+        BitsBytes::to_be_bytes(&value.bits())
+    );
+    assert_eq!(
+        value.bits(),
+        <u32 as BitsBytes>::from_be_bytes([0x80, 0xab, 0x00, 0x01])
+    );
+    assert_eq!(
+        value.bits(),
+        <u32 as BitsBytes>::from_le_bytes([0x01, 0x00, 0xab, 0x80])
+    );
+    assert_eq!(-2i16, <i16 as BitsBytes>::from_be_bytes([0xff, 0xfe]));
+}
+
+#[test]
+fn generic_roundtrip() {
+    roundtrip(TestBytes::all());
+    roundtrip(TestBytes::B | TestBytes::from_bits_retain(1 << 20));
+    roundtrip(TestFlags::A | TestFlags::from_bits_retain(1 << 7));
+}
+
+#[track_caller]
+fn roundtrip<T: Flags + std::fmt::Debug + PartialEq + Copy>(value: T)
+where
+    T::Bits: BitsBytes,
+{
+    let be = value.bits().to_be_bytes();
+    let le = value.bits().to_le_bytes();
+
+    let mut reversed = be.as_ref().to_vec();
+    reversed.reverse();
+    assert_eq!(le.as_ref(), &*reversed, "{:?} byte order", value);
+
+    assert_eq!(
+        value,
+        T::from_bits_retain(BitsBytes::from_be_bytes(be)),
+        "from_be_bytes({:?})",
+        value
+    );
+    assert_eq!(
+        value,
+        T::from_bits_retain(BitsBytes::from_le_bytes(le)),
+        "from_le_bytes({:?})",
+        value
+    );
+}
@@ -0,0 +1,85 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeSet, HashSet},
+};
+
+bitflags! {
+    #[derive(Debug, Clone, Copy)]
+    #[bitflags(canonical)]
+    pub struct TestCanonical: u8 {
+        const A = 1;
+        const B = 1 << 1;
+        const C = 1 << 2;
+    }
+}
+
+#[test]
+fn cases() {
+    case(
+        Ordering::Equal,
+        TestCanonical::A,
+        TestCanonical::A | TestCanonical::from_bits_retain(1 << 3),
+    );
+    case(
+        Ordering::Equal,
+        TestCanonical::empty(),
+        TestCanonical::from_bits_retain(1 << 7),
+    );
+    case(
+        Ordering::Less,
+        TestCanonical::A | TestCanonical::from_bits_retain(1 << 7),
+        TestCanonical::B,
+    );
+    case(
+        Ordering::Greater,
+        TestCanonical::all(),
+        TestCanonical::C | TestCanonical::from_bits_retain(!0 << 3),
+    );
+
+    // Method-call `eq` is `PartialEq::eq`, so it agrees with `==`
+    let unknown = TestCanonical::A | TestCanonical::from_bits_retain(1 << 3);
+    assert!(TestCanonical::A.eq(&unknown));
+    assert!(TestCanonical::A == unknown);
+
+    // The inherent `const_eq` still compares all bits
+    assert!(!TestCanonical::A.const_eq(&unknown));
+}
+
+#[test]
+fn dedup() {
+    let values = [
+        TestCanonical::A,
+        TestCanonical::A | TestCanonical::from_bits_retain(1 << 3),
+        TestCanonical::A | TestCanonical::from_bits_retain(1 << 4),
+        TestCanonical::B,
+    ];
+
+    assert_eq!(2, values.iter().copied().collect::<HashSet<_>>().len());
+    assert_eq!(2, values.iter().copied().collect::<BTreeSet<_>>().len());
+}
+
+#[track_caller]
+fn case(expected: Ordering, a: TestCanonical, b: TestCanonical) {
+    assert_eq!(expected, a.cmp(&b), "{:?}.cmp({:?})", a, b);
+    assert_eq!(
+        Some(expected),
+        a.partial_cmp(&b),
+        "{:?}.partial_cmp({:?})",
+        a,
+        b
+    );
+    assert_eq!(expected.reverse(), b.cmp(&a), "{:?}.cmp({:?})", b, a);
+    assert_eq!(expected == Ordering::Equal, a == b, "{:?} == {:?}", a, b);
+
+    if expected == Ordering::Equal {
+        assert_eq!(hash(&a), hash(&b), "hash({:?}) == hash({:?})", a, b);
+    }
+}
+
+fn hash(value: &TestCanonical) -> u64 {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,44 @@
+use crate::Flags;
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct TestCfgAttr: u8 {
+        // These compile whether or not the `std` feature is enabled
+        #[cfg_attr(feature = "std", deprecated)]
+        const A = 1;
+        #[cfg_attr(not(feature = "std"), deprecated(note = "no std"))]
+        const B = 1 << 1;
+
+        // A `cfg` inside a `cfg_attr` also removes the flag from the generated methods
+        #[cfg_attr(any(), cfg(any()))]
+        const C = 1 << 2;
+        #[cfg_attr(all(), doc = "Not present", cfg(any()), allow(dead_code))]
+        const D = 1 << 3;
+        #[cfg_attr(all(), cfg_attr(all(), cfg(any())))]
+        const E = 1 << 4;
+        #[cfg_attr(all(), cfg_attr(any(), cfg(any())))]
+        const F = 1 << 5;
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn cases() {
+    assert_eq!(
+        vec![
+            ("A", TestCfgAttr::A),
+            ("B", TestCfgAttr::B),
+            ("C", TestCfgAttr::C),
+            ("F", TestCfgAttr::F),
+        ],
+        TestCfgAttr::all().iter_names().collect::<Vec<_>>()
+    );
+    assert_eq!(4, TestCfgAttr::FLAGS.len());
+    assert_eq!(1 | 1 << 1 | 1 << 2 | 1 << 5, TestCfgAttr::all().bits());
+    assert_eq!(
+        TestCfgAttr::from_bits_retain(1 << 1),
+        TestCfgAttr::from_bits_truncate(1 << 1 | 1 << 3 | 1 << 4)
+    );
+    assert_eq!(None, TestCfgAttr::from_name("D"));
+    assert_eq!(Some(TestCfgAttr::F), TestCfgAttr::from_name("F"));
+}
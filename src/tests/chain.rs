@@ -0,0 +1,90 @@
+use super::*;
+
+use crate::Flags;
+
+#[test]
+fn cases() {
+    let mut flags = TestFlags::empty();
+    flags
+        .with_inserted(TestFlags::A)
+        .with_inserted(TestFlags::B | TestFlags::C)
+        .with_removed(TestFlags::B)
+        .with_toggled(TestFlags::A | TestFlags::from_bits_retain(1 << 3));
+
+    assert_eq!(TestFlags::C | TestFlags::from_bits_retain(1 << 3), flags);
+
+    // Each chained method is the same as its unchained form
+    case(
+        TestFlags::A,
+        TestFlags::B | TestFlags::C,
+        TestFlags::with_inserted,
+        TestFlags::with_removed,
+        TestFlags::with_toggled,
+    );
+    case(
+        TestFlags::ABC,
+        TestFlags::B,
+        TestFlags::with_inserted,
+        TestFlags::with_removed,
+        TestFlags::with_toggled,
+    );
+    case(
+        TestFlags::A | TestFlags::from_bits_retain(1 << 3),
+        TestFlags::from_bits_retain(1 << 3 | 1 << 4),
+        TestFlags::with_inserted,
+        TestFlags::with_removed,
+        TestFlags::with_toggled,
+    );
+    case(
+        TestOverlapping::AB,
+        TestOverlapping::BC,
+        TestOverlapping::with_inserted,
+        TestOverlapping::with_removed,
+        TestOverlapping::with_toggled,
+    );
+}
+
+#[track_caller]
+fn case<T: Flags + Copy + std::fmt::Debug + PartialEq>(
+    value: T,
+    other: T,
+    with_inserted: impl FnOnce(&mut T, T) -> &mut T,
+    with_removed: impl FnOnce(&mut T, T) -> &mut T,
+    with_toggled: impl FnOnce(&mut T, T) -> &mut T,
+) {
+    let mut expected = value;
+    expected.insert(other);
+    let mut chained = value;
+    assert_eq!(
+        expected,
+        *with_inserted(&mut chained, other),
+        "{:?}.with_inserted({:?})",
+        value,
+        other
+    );
+    assert_eq!(expected, chained);
+
+    let mut expected = value;
+    expected.remove(other);
+    let mut chained = value;
+    assert_eq!(
+        expected,
+        *with_removed(&mut chained, other),
+        "{:?}.with_removed({:?})",
+        value,
+        other
+    );
+    assert_eq!(expected, chained);
+
+    let mut expected = value;
+    expected.toggle(other);
+    let mut chained = value;
+    assert_eq!(
+        expected,
+        *with_toggled(&mut chained, other),
+        "{:?}.with_toggled({:?})",
+        value,
+        other
+    );
+    assert_eq!(expected, chained);
+}
@@ -0,0 +1,69 @@
+use super::*;
+
+use crate::Flags;
+
+#[test]
+fn cases() {
+    case(0, TestFlags::empty(), TestFlags::empty());
+    case(0, TestFlags::ABC, TestFlags::all());
+    case(
+        1 | 1 << 2,
+        TestFlags::A | TestFlags::B,
+        TestFlags::B | TestFlags::C,
+    );
+    case(
+        1 << 1 | 1 << 3,
+        TestFlags::A | TestFlags::from_bits_retain(1 << 3),
+        TestFlags::A | TestFlags::B,
+    );
+
+    case(0, TestZero::ZERO, TestZero::empty());
+
+    case(1 | 1 << 2, TestOverlapping::AB, TestOverlapping::BC);
+
+    case(
+        1 << 7,
+        TestExternal::empty(),
+        TestExternal::from_bits_retain(1 << 7),
+    );
+}
+
+#[test]
+fn generic_diff() {
+    let before = TestFlags::A | TestFlags::B;
+    let after = TestFlags::B | TestFlags::C;
+
+    let changed = before.changed(&after);
+
+    // Changed flags are either set or unset
+    assert_eq!(TestFlags::C, changed & after);
+    assert_eq!(TestFlags::A, changed & before);
+}
+
+#[track_caller]
+fn case<T: Flags + std::fmt::Debug + Copy>(expected: T::Bits, value: T, other: T)
+where
+    T::Bits: std::fmt::Debug + PartialEq,
+{
+    assert_eq!(
+        expected,
+        value.changed(&other).bits(),
+        "{:?}.changed({:?})",
+        value,
+        other
+    );
+    assert_eq!(
+        expected,
+        other.changed(&value).bits(),
+        "{:?}.changed({:?})",
+        other,
+        value
+    );
+    assert_eq!(
+        expected,
+        Flags::symmetric_difference(value, other).bits(),
+        "Flags::symmetric_difference({:?}, {:?})",
+        value,
+        other
+    );
+}
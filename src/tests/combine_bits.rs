@@ -0,0 +1,61 @@
+use super::*;
+
+use crate::Flags;
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct TestHigh: u8 {
+        const D = 1 << 3;
+        const E = 1 << 4;
+    }
+}
+
+#[test]
+fn cases() {
+    case(0, TestFlags::empty(), TestHigh::empty());
+    case(1 | 1 << 3, TestFlags::A, TestHigh::D);
+    case(
+        1 | 1 << 1 | 1 << 2 | 1 << 3 | 1 << 4,
+        TestFlags::all(),
+        TestHigh::all(),
+    );
+    case(1 << 1, TestFlags::B, TestHigh::from_bits_retain(1 << 1));
+    case(
+        1 << 3 | 1 << 7,
+        TestFlags::from_bits_retain(1 << 3),
+        TestHigh::from_bits_retain(1 << 7),
+    );
+
+    case(1, TestFlags::A, TestZero::ZERO);
+
+    assert_eq!(
+        TestFlags::A | TestFlags::from_bits_retain(1 << 3),
+        TestFlags::from_bits_retain(TestFlags::A.combine_bits(&TestHigh::D))
+    );
+    assert_eq!(
+        TestFlags::A,
+        TestFlags::from_bits_truncate(TestFlags::A.combine_bits(&TestHigh::D))
+    );
+}
+
+#[track_caller]
+fn case<T: Flags<Bits = u8> + std::fmt::Debug, U: Flags<Bits = u8> + std::fmt::Debug>(
+    expected: u8,
+    value: T,
+    other: U,
+) {
+    assert_eq!(
+        expected,
+        value.combine_bits(&other),
+        "{:?}.combine_bits({:?})",
+        value,
+        other
+    );
+    assert_eq!(
+        expected,
+        other.combine_bits(&value),
+        "{:?}.combine_bits({:?})",
+        other,
+        value
+    );
+}
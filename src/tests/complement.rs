@@ -51,3 +51,71 @@ fn case<T: Flags + std::fmt::Debug + std::ops::Not<Output = T> + Copy>(
     );
     assert_eq!(expected, (!value).bits(), "!{:?}", value);
 }
+
+#[test]
+fn generic_others() {
+    // The defined flags other than `flag`, using only the `Flags` trait
+    fn others<T: Flags>(flag: T) -> T {
+        Flags::complement(flag)
+    }
+
+    assert_eq!(TestFlags::A | TestFlags::B, others(TestFlags::C));
+    assert_eq!(
+        TestFlags::A | TestFlags::B,
+        others(TestFlags::C | TestFlags::from_bits_retain(1 << 3))
+    );
+    assert_eq!(TestFlags::all(), others(TestFlags::empty()));
+    assert_eq!(
+        TestOverlapping::from_bits_retain(1 << 2),
+        others(TestOverlapping::AB)
+    );
+}
+
+#[test]
+fn complement_retain() {
+    case_retain(!0, TestFlags::empty(), TestFlags::complement_retain);
+    case_retain(!1, TestFlags::A, TestFlags::complement_retain);
+    case_retain(
+        1 << 3,
+        TestFlags::from_bits_retain(!(1 << 3)),
+        TestFlags::complement_retain,
+    );
+
+    // Unknown bits are kept, where `complement` would drop them
+    let value = TestFlags::C | TestFlags::from_bits_retain(1 << 3);
+    assert_eq!(1 | 1 << 1, value.complement().bits());
+    assert_eq!(!(1 << 2 | 1 << 3), value.complement_retain().bits());
+
+    case_retain(
+        0,
+        TestZero::from_bits_retain(!0),
+        TestZero::complement_retain,
+    );
+    case_retain(
+        !(1 | 1 << 1),
+        TestOverlapping::AB,
+        TestOverlapping::complement_retain,
+    );
+}
+
+#[track_caller]
+fn case_retain<T: Flags + std::fmt::Debug + Copy>(
+    expected: T::Bits,
+    value: T,
+    inherent: impl FnOnce(T) -> T,
+) where
+    T::Bits: std::fmt::Debug + PartialEq,
+{
+    assert_eq!(
+        expected,
+        inherent(value).bits(),
+        "{:?}.complement_retain()",
+        value
+    );
+    assert_eq!(
+        expected,
+        T::from_bits_retain(!value.bits()).bits(),
+        "from_bits_retain(!{:?}.bits())",
+        value
+    );
+}
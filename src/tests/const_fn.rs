@@ -0,0 +1,179 @@
+use super::*;
+
+// Every set operation on flags values has an inherent `const` form
+const EMPTY: TestFlags = TestFlags::empty();
+const ALL: TestFlags = TestFlags::all();
+static ALL_FLAGS: TestFlags = TestFlags::ALL_FLAGS;
+const BITS: u8 = TestFlags::ABC.bits();
+const FROM_BITS: Option<TestFlags> = TestFlags::from_bits(1 << 3);
+const FROM_BITS_TRUNCATE: TestFlags = TestFlags::from_bits_truncate(1 | 1 << 3);
+const FROM_BITS_RETAIN: TestFlags = TestFlags::from_bits_retain(1 << 3);
+const FROM_BIT_INDEX: Option<TestFlags> = TestFlags::from_bit_index(1);
+const MAX_DEFINED_BIT: u32 = TestFlags::max_defined_bit();
+const MIN_DEFINED_BIT: u32 = TestFlags::min_defined_bit();
+const IS_EMPTY: bool = TestFlags::A.is_empty();
+const IS_ALL: bool = TestFlags::ABC.is_all();
+const EQ: bool = TestFlags::A.const_eq(&TestFlags::B);
+const INTERSECTS: bool = TestFlags::ABC.intersects(TestFlags::B);
+const CONTAINS: bool = TestFlags::A.contains(TestFlags::ABC);
+const INTERSECTION: TestFlags = TestFlags::ABC.intersection(TestFlags::B);
+const UNION: TestFlags = TestFlags::A.union(TestFlags::C);
+const DIFFERENCE: TestFlags = TestFlags::ABC.difference(TestFlags::B);
+const SYMMETRIC_DIFFERENCE: TestFlags = TestFlags::A.symmetric_difference(TestFlags::ABC);
+const COMPLEMENT: TestFlags = TestFlags::B.complement();
+const COMPLEMENT_RETAIN: TestFlags = TestFlags::B.complement_retain();
+const INSERTED: TestFlags = TestFlags::A.inserted(TestFlags::B).inserted(TestFlags::C);
+const REMOVED: TestFlags = TestFlags::ABC.removed(TestFlags::A);
+const TOGGLED: TestFlags = TestFlags::A.toggled(TestFlags::ABC);
+const TRUNCATED: TestFlags = TestFlags::from_bits_retain(1 | 1 << 3).truncated();
+
+#[test]
+fn cases() {
+    assert_eq!(0, EMPTY.bits());
+    assert_eq!(1 | 1 << 1 | 1 << 2, ALL.bits());
+    assert_eq!(ALL, ALL_FLAGS);
+    assert_eq!(1 | 1 << 1 | 1 << 2, BITS);
+    assert_eq!(None, FROM_BITS);
+    assert_eq!(TestFlags::A, FROM_BITS_TRUNCATE);
+    assert_eq!(1 << 3, FROM_BITS_RETAIN.bits());
+    assert_eq!(Some(TestFlags::B), FROM_BIT_INDEX);
+    assert_eq!(2, MAX_DEFINED_BIT);
+    assert_eq!(0, MIN_DEFINED_BIT);
+    assert!(!IS_EMPTY);
+    assert!(IS_ALL);
+    assert!(!EQ);
+    assert!(INTERSECTS);
+    assert!(!CONTAINS);
+    assert_eq!(TestFlags::B, INTERSECTION);
+    assert_eq!(TestFlags::A | TestFlags::C, UNION);
+    assert_eq!(TestFlags::A | TestFlags::C, DIFFERENCE);
+    assert_eq!(TestFlags::B | TestFlags::C, SYMMETRIC_DIFFERENCE);
+    assert_eq!(TestFlags::A | TestFlags::C, COMPLEMENT);
+    assert_eq!(!(1 << 1), COMPLEMENT_RETAIN.bits());
+    assert_eq!(TestFlags::ABC, INSERTED);
+    assert_eq!(TestFlags::B | TestFlags::C, REMOVED);
+    assert_eq!(TestFlags::B | TestFlags::C, TOGGLED);
+    assert_eq!(TestFlags::A, TRUNCATED);
+}
+
+#[test]
+fn consuming() {
+    for (value, other) in [
+        (TestFlags::empty(), TestFlags::A),
+        (TestFlags::A, TestFlags::ABC),
+        (TestFlags::ABC, TestFlags::B),
+        (
+            TestFlags::A | TestFlags::from_bits_retain(1 << 3),
+            TestFlags::from_bits_retain(1 << 3 | 1 << 4),
+        ),
+    ] {
+        let mut inserted = value;
+        inserted.insert(other);
+        assert_eq!(
+            inserted,
+            value.inserted(other),
+            "{:?}.inserted({:?})",
+            value,
+            other
+        );
+
+        let mut removed = value;
+        removed.remove(other);
+        assert_eq!(
+            removed,
+            value.removed(other),
+            "{:?}.removed({:?})",
+            value,
+            other
+        );
+
+        let mut toggled = value;
+        toggled.toggle(other);
+        assert_eq!(
+            toggled,
+            value.toggled(other),
+            "{:?}.toggled({:?})",
+            value,
+            other
+        );
+    }
+}
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct TestConstComposite: u8 {
+        const A = 1;
+        const B = 1 << 1;
+        const C = 1 << 2;
+
+        // Composite flags can be built from other flags with `const` methods
+        const AB = Self::A.union(Self::B).bits();
+        const ABC = Self::AB.union(Self::C).bits();
+        const NOT_B = Self::ABC.difference(Self::B).bits();
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TestConstCompositeImpl(u8);
+
+bitflags! {
+    impl TestConstCompositeImpl: u8 {
+        const A = 1;
+        const B = 1 << 1;
+        const AB = Self::A.union(Self::B).bits();
+    }
+}
+
+#[test]
+fn composite_flags() {
+    assert_eq!(1 | 1 << 1, TestConstComposite::AB.bits());
+    assert_eq!(TestConstComposite::all(), TestConstComposite::ABC);
+    assert_eq!(
+        TestConstComposite::A | TestConstComposite::C,
+        TestConstComposite::NOT_B
+    );
+
+    assert_eq!(
+        TestConstCompositeImpl::A | TestConstCompositeImpl::B,
+        TestConstCompositeImpl::AB
+    );
+}
+
+// Unions of constants are evaluated at compile time through the generated newtypes,
+// so these fail to compile if any of the set operations stops being `const`
+const _: [(); 1 | 1 << 1 | 1 << 2] =
+    [(); TestFlags::A.union(TestFlags::B).union(TestFlags::C).bits() as usize];
+const _: [(); 1 | 1 << 2] = [(); TestFlags::ABC.difference(TestFlags::B).bits() as usize];
+const _: [(); 1] = [(); TestFlags::ABC.contains(TestFlags::A.union(TestFlags::C)) as usize];
+const _: [(); 1 | 1 << 1] = [(); TestConstCompositeImpl::A
+    .union(TestConstCompositeImpl::B)
+    .bits() as usize];
+
+#[test]
+fn const_fold() {
+    const UNION: TestFlags = TestFlags::A.union(TestFlags::B).union(TestFlags::C);
+    const DIFFERENCE: TestFlags = TestFlags::ABC.difference(TestFlags::B);
+    const IMPL_UNION: TestConstCompositeImpl =
+        TestConstCompositeImpl::A.union(TestConstCompositeImpl::B);
+
+    // The operators produce the same values as their `const` forms
+    assert_eq!(UNION, TestFlags::A | TestFlags::B | TestFlags::C);
+    assert_eq!(DIFFERENCE, TestFlags::ABC - TestFlags::B);
+    assert_eq!(
+        IMPL_UNION,
+        TestConstCompositeImpl::A | TestConstCompositeImpl::B
+    );
+
+    // So do the `Flags` trait methods used by generic code
+    assert_eq!(
+        UNION,
+        crate::Flags::union(
+            crate::Flags::union(TestFlags::A, TestFlags::B),
+            TestFlags::C
+        )
+    );
+    assert_eq!(
+        DIFFERENCE,
+        crate::Flags::difference(TestFlags::ABC, TestFlags::B)
+    );
+}
@@ -71,6 +71,55 @@ fn cases() {
         TestOverlapping::contains,
     );
 
+    // Multi-bit flags are only contained if all of their bits are set
+    case(
+        TestOverlapping::from_bits_retain(1),
+        &[
+            (TestOverlapping::empty(), true),
+            (TestOverlapping::AB, false),
+            (TestOverlapping::BC, false),
+            (TestOverlapping::AB | TestOverlapping::BC, false),
+            (TestOverlapping::from_bits_retain(1), true),
+        ],
+        TestOverlapping::contains,
+    );
+
+    case(
+        TestOverlapping::from_bits_retain(1 << 1),
+        &[
+            (TestOverlapping::AB, false),
+            (TestOverlapping::BC, false),
+            (TestOverlapping::from_bits_retain(1 << 1), true),
+        ],
+        TestOverlapping::contains,
+    );
+
+    case(
+        TestOverlapping::BC,
+        &[
+            (TestOverlapping::AB, false),
+            (TestOverlapping::BC, true),
+            (TestOverlapping::AB | TestOverlapping::BC, false),
+            (TestOverlapping::from_bits_retain(1 << 2), true),
+        ],
+        TestOverlapping::contains,
+    );
+
+    case(
+        TestOverlapping::AB | TestOverlapping::BC,
+        &[
+            (TestOverlapping::AB, true),
+            (TestOverlapping::BC, true),
+            (TestOverlapping::AB | TestOverlapping::BC, true),
+            (TestOverlapping::from_bits_retain(1 << 3), false),
+            (
+                TestOverlapping::AB | TestOverlapping::from_bits_retain(1 << 3),
+                false,
+            ),
+        ],
+        TestOverlapping::contains,
+    );
+
     case(
         TestExternal::all(),
         &[
@@ -104,5 +153,12 @@ fn case<T: Flags + std::fmt::Debug + Copy>(
             value,
             input
         );
+        assert_eq!(
+            *expected,
+            Flags::contains_ref(&value, input),
+            "Flags::contains_ref({:?}, {:?})",
+            value,
+            input
+        );
     }
 }
@@ -0,0 +1,60 @@
+use super::*;
+
+use crate::Flags;
+
+#[test]
+fn cases() {
+    case(
+        TestFlags::empty(),
+        &[("A", false), ("B", false), ("ABC", false), ("", false)],
+    );
+
+    case(
+        TestFlags::A,
+        &[
+            ("A", true),
+            ("B", false),
+            ("ABC", false),
+            ("a", false),
+            ("D", false),
+            ("", false),
+        ],
+    );
+
+    case(
+        TestFlags::ABC | TestFlags::from_bits_retain(1 << 3),
+        &[
+            ("A", true),
+            ("B", true),
+            ("C", true),
+            ("ABC", true),
+            ("0x8", false),
+            ("", false),
+        ],
+    );
+
+    case(TestZero::empty(), &[("ZERO", true), ("", false)]);
+
+    case(TestZeroOne::empty(), &[("ZERO", true), ("ONE", false)]);
+    case(TestZeroOne::ONE, &[("ZERO", true), ("ONE", true)]);
+
+    case(TestUnicode::一, &[("一", true), ("二", false)]);
+
+    case(
+        TestExternal::all(),
+        &[("A", true), ("_", false), ("", false)],
+    );
+}
+
+#[track_caller]
+fn case<T: Flags + std::fmt::Debug>(value: T, inputs: &[(&str, bool)]) {
+    for (name, expected) in inputs {
+        assert_eq!(
+            *expected,
+            value.contains_name(name),
+            "{:?}.contains_name({:?})",
+            value,
+            name
+        );
+    }
+}
@@ -0,0 +1,36 @@
+bitflags! {
+    #[derive(Debug, PartialEq, Eq)]
+    #[bitflags(default = A | C)]
+    pub struct TestDefault: u8 {
+        const A = 1;
+        const B = 1 << 1;
+        const C = 1 << 2;
+    }
+}
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Default)]
+    pub struct TestDerived: u8 {
+        const A = 1;
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct TestDefaultImpl(u8);
+
+bitflags! {
+    #[bitflags(default = B)]
+    impl TestDefaultImpl: u8 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+}
+
+#[test]
+fn cases() {
+    assert_eq!(TestDefault::A | TestDefault::C, TestDefault::default());
+    assert_eq!(TestDefaultImpl::B, TestDefaultImpl::default());
+
+    // Without the option, `Default` is derived as usual
+    assert_eq!(TestDerived::empty(), TestDerived::default());
+}
@@ -0,0 +1,84 @@
+use super::*;
+
+#[test]
+fn cases() {
+    case(
+        0,
+        2,
+        TestFlags::min_defined_bit(),
+        TestFlags::max_defined_bit(),
+    );
+
+    case(
+        0,
+        0,
+        TestZero::min_defined_bit(),
+        TestZero::max_defined_bit(),
+    );
+    case(
+        0,
+        0,
+        TestEmpty::min_defined_bit(),
+        TestEmpty::max_defined_bit(),
+    );
+    case(
+        0,
+        0,
+        TestZeroOne::min_defined_bit(),
+        TestZeroOne::max_defined_bit(),
+    );
+
+    case(
+        0,
+        2,
+        TestOverlapping::min_defined_bit(),
+        TestOverlapping::max_defined_bit(),
+    );
+
+    case(
+        0,
+        7,
+        TestExternal::min_defined_bit(),
+        TestExternal::max_defined_bit(),
+    );
+
+    case(
+        3,
+        31,
+        TestHigh::min_defined_bit(),
+        TestHigh::max_defined_bit(),
+    );
+    case(
+        1,
+        7,
+        TestSigned::min_defined_bit(),
+        TestSigned::max_defined_bit(),
+    );
+}
+
+#[test]
+fn is_const() {
+    const MAX: u32 = TestFlags::max_defined_bit();
+    const MIN: u32 = TestFlags::min_defined_bit();
+
+    assert_eq!(2, MAX);
+    assert_eq!(0, MIN);
+}
+
+bitflags! {
+    pub struct TestHigh: u32 {
+        const A = 1 << 3;
+        const B = 1 << 31;
+    }
+
+    pub struct TestSigned: i8 {
+        const A = 1 << 1;
+        const B = i8::MIN;
+    }
+}
+
+#[track_caller]
+fn case(expected_min: u32, expected_max: u32, min: u32, max: u32) {
+    assert_eq!(expected_min, min, "min_defined_bit");
+    assert_eq!(expected_max, max, "max_defined_bit");
+}
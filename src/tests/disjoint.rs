@@ -0,0 +1,53 @@
+use crate::Flags;
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[bitflags(disjoint)]
+    pub struct TestDisjoint: u8 {
+        const A = 1;
+        const B = 1 << 1;
+        const C = 1 << 7;
+
+        // Unnamed flags may overlap named ones
+        const _ = 1 << 1 | 1 << 2;
+        const _ = !0;
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[bitflags(disjoint, validate)]
+    pub struct TestDisjointSigned: i8 {
+        const A = 1;
+        const MIN = i8::MIN;
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TestDisjointImpl(u32);
+
+bitflags! {
+    #[bitflags(disjoint)]
+    impl TestDisjointImpl: u32 {
+        const A = 1;
+        const B = 1 << 31;
+        const ZERO = 0;
+    }
+}
+
+#[test]
+fn cases() {
+    assert_eq!(
+        1 | 1 << 1 | 1 << 7,
+        (TestDisjoint::A | TestDisjoint::B | TestDisjoint::C).bits()
+    );
+    assert_eq!(!0, TestDisjoint::all().bits());
+    assert_eq!(5, TestDisjoint::FLAGS.len());
+
+    assert_eq!(1 | i8::MIN, TestDisjointSigned::all().bits());
+    assert!(TestDisjointSigned::all().validate().is_ok());
+
+    assert_eq!(3, TestDisjointImpl::FLAGS.len());
+    assert_eq!(
+        TestDisjointImpl::A | TestDisjointImpl::B,
+        TestDisjointImpl::all()
+    );
+}
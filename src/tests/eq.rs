@@ -8,3 +8,56 @@ fn cases() {
     assert!(TestFlags::from_bits_retain(1) < TestFlags::from_bits_retain(2));
     assert!(TestFlags::from_bits_retain(2) > TestFlags::from_bits_retain(1));
 }
+
+#[test]
+fn const_eq() {
+    const _: () = assert!(TestFlags::A.const_eq(&TestFlags::from_bits_retain(1)));
+    const _: () = assert!(!TestFlags::A.const_eq(&TestFlags::B));
+    const _: () = assert!(TestFlags::ABC.const_eq(&TestFlags::all()));
+    const _: () = assert!(!TestFlags::all().const_eq(&TestFlags::from_bits_retain(!0)));
+    const _: () = assert!(TestZero::ZERO.const_eq(&TestZero::empty()));
+
+    assert!(TestFlags::empty().const_eq(&TestFlags::empty()));
+    assert!((TestFlags::A | TestFlags::B).const_eq(&TestFlags::from_bits_retain(0b11)));
+    assert!(!TestFlags::A.const_eq(&(TestFlags::A | TestFlags::from_bits_retain(1 << 3))));
+    assert!(TestExternal::all().const_eq(&TestExternal::from_bits_retain(!0)));
+
+    // The derived `PartialEq` is still used for `==`
+    assert!(PartialEq::eq(
+        &TestFlags::A,
+        &TestFlags::from_bits_retain(1)
+    ));
+    assert!(TestFlags::A == TestFlags::from_bits_retain(1));
+}
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+    #[bitflags(bits_eq)]
+    pub struct TestBitsEq: u8 {
+        const A = 1;
+        const B = 1 << 1;
+        const C = 1 << 2;
+    }
+}
+
+#[test]
+fn bits_eq() {
+    assert_eq!(TestBitsEq::empty(), 0);
+    assert_eq!(TestBitsEq::A | TestBitsEq::C, 0b101);
+
+    // Unknown bits are compared too
+    assert_ne!(TestBitsEq::A | TestBitsEq::from_bits_retain(1 << 3), 1);
+    assert_eq!(TestBitsEq::from_bits_retain(!0), u8::MAX);
+
+    // Comparisons between flags values still work as before
+    assert_eq!(TestBitsEq::A, TestBitsEq::from_bits_retain(1));
+    assert!(TestBitsEq::A != TestBitsEq::B);
+}
+
+#[test]
+fn bits_eq_one_direction() {
+    // Only the flags value can be on the left-hand side
+    let bits: u8 = 1;
+    assert!(TestBitsEq::A == bits);
+    assert!(bits == TestBitsEq::A.bits());
+}
@@ -40,3 +40,40 @@ mod external {
         );
     }
 }
+
+mod bits {
+    bitflags! {
+        #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+        pub struct TestFlags32: u32 {
+            const A = 1;
+            const B = 1 << 1;
+            const C = 1 << 2;
+        }
+    }
+
+    #[test]
+    fn cases() {
+        let mut flags = TestFlags32::empty();
+
+        flags.extend(Vec::<u32>::new());
+
+        assert_eq!(TestFlags32::empty(), flags);
+
+        flags.extend(vec![1u32, 1 << 2]);
+
+        assert_eq!(TestFlags32::A | TestFlags32::C, flags);
+
+        // Unknown bits accumulate
+        flags.extend(vec![1 << 1, 1 << 20, 1 << 31]);
+
+        assert_eq!(
+            TestFlags32::all() | TestFlags32::from_bits_retain(1 << 20 | 1 << 31),
+            flags
+        );
+
+        // Extending from flags values still works as before
+        flags.extend(TestFlags32::A);
+
+        assert_eq!(1 | 1 << 1 | 1 << 2 | 1 << 20 | 1 << 31, flags.bits());
+    }
+}
@@ -22,6 +22,209 @@ fn cases() {
     assert_eq!(0, TestEmpty::FLAGS.iter().count());
 }
 
+mod generic {
+    use super::*;
+
+    #[test]
+    fn cases() {
+        assert_eq!(
+            vec![
+                ("A", 1u8),
+                ("B", 1 << 1),
+                ("C", 1 << 2),
+                ("ABC", 1 | 1 << 1 | 1 << 2)
+            ],
+            defined::<TestFlags>()
+        );
+        assert_eq!(
+            vec![
+                ("ABC", 1u8 | 1 << 1 | 1 << 2),
+                ("A", 1),
+                ("B", 1 << 1),
+                ("C", 1 << 2)
+            ],
+            defined::<TestFlagsInvert>()
+        );
+        assert_eq!(vec![("ZERO", 0u8)], defined::<TestZero>());
+        assert_eq!(vec![("一", 1u8), ("二", 1 << 1)], defined::<TestUnicode>());
+        assert_eq!(Vec::<(&str, u8)>::new(), defined::<TestEmpty>());
+        assert_eq!(vec![("", !0u8)], defined::<TestExternalFull>());
+    }
+
+    // Enumerate the flags of any flags type without an instance of it
+    fn defined<F: Flags>() -> Vec<(&'static str, F::Bits)> {
+        let mut defined = Vec::new();
+
+        for flag in F::FLAGS {
+            defined.push((flag.name(), flag.value().bits()));
+        }
+
+        defined
+    }
+}
+
+mod set_algebra {
+    use super::*;
+
+    #[test]
+    fn cases() {
+        case(TestFlags::A | TestFlags::B, TestFlags::B | TestFlags::C);
+        case(TestFlags::empty(), TestFlags::all());
+        case(
+            TestFlags::A | TestFlags::from_bits_retain(1 << 3),
+            TestFlags::from_bits_retain(1 << 3 | 1 << 4),
+        );
+        case(TestOverlapping::AB, TestOverlapping::BC);
+        case(
+            TestExternal::A | TestExternal::from_bits_retain(1 << 6),
+            TestExternal::from_bits_retain(!0),
+        );
+    }
+
+    #[track_caller]
+    fn case<F: Flags<Bits = u8> + Copy + std::fmt::Debug>(a: F, b: F) {
+        assert_eq!(
+            a.bits() | b.bits(),
+            Flags::union(a, b).bits(),
+            "Flags::union({:?}, {:?})",
+            a,
+            b
+        );
+        assert_eq!(
+            a.bits() & b.bits(),
+            Flags::intersection(a, b).bits(),
+            "Flags::intersection({:?}, {:?})",
+            a,
+            b
+        );
+        assert_eq!(
+            a.bits() & !b.bits(),
+            Flags::difference(a, b).bits(),
+            "Flags::difference({:?}, {:?})",
+            a,
+            b
+        );
+        assert_eq!(
+            a.bits() ^ b.bits(),
+            Flags::symmetric_difference(a, b).bits(),
+            "Flags::symmetric_difference({:?}, {:?})",
+            a,
+            b
+        );
+        assert_eq!(
+            F::all().bits() & !a.bits(),
+            Flags::complement(a).bits(),
+            "Flags::complement({:?})",
+            a
+        );
+
+        // The operations relate to each other in the usual ways
+        assert_eq!(
+            Flags::symmetric_difference(a, b).bits(),
+            Flags::union(Flags::difference(a, b), Flags::difference(b, a)).bits(),
+            "symmetric_difference({:?}, {:?}) == (a - b) | (b - a)",
+            a,
+            b
+        );
+        assert_eq!(
+            Flags::difference(a, b).bits(),
+            Flags::difference(a, Flags::intersection(a, b)).bits(),
+            "difference({:?}, {:?}) == a - (a & b)",
+            a,
+            b
+        );
+    }
+}
+
+mod classify {
+    use super::*;
+
+    bitflags! {
+        pub struct TestClassify: i16 {
+            const ZERO = 0;
+            const A = 1;
+            const MIN = i16::MIN;
+            const HIGH = 1 << 8 | 1 << 9;
+            const _ = !0;
+        }
+    }
+
+    #[test]
+    fn cases() {
+        let classes = TestClassify::FLAGS
+            .iter()
+            .map(|flag| {
+                (
+                    flag.name(),
+                    flag.is_zero(),
+                    flag.is_single(),
+                    flag.is_composite(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                ("ZERO", true, false, false),
+                ("A", false, true, false),
+                ("MIN", false, true, false),
+                ("HIGH", false, false, true),
+                ("", false, false, true),
+            ],
+            classes
+        );
+
+        assert!(TestZeroOne::FLAGS[0].is_zero());
+        assert!(TestFlags::FLAGS[3].is_composite());
+    }
+}
+
+mod components {
+    use super::*;
+
+    bitflags! {
+        pub struct TestComponents: u8 {
+            const A = 1;
+            const B = 1 << 1;
+            const ALSO_B = 1 << 1;
+            const AB = Self::A.bits() | Self::B.bits();
+            const BC = 1 << 1 | 1 << 2;
+            const NONE = 0;
+            const _ = 1 << 3;
+            const _ = !0;
+        }
+    }
+
+    #[test]
+    fn cases() {
+        case(&TestComponents::FLAGS[0], &["A"]);
+        case(&TestComponents::FLAGS[1], &["B", "ALSO_B"]);
+        case(&TestComponents::FLAGS[3], &["A", "B", "ALSO_B"]);
+        case(&TestComponents::FLAGS[4], &["B", "ALSO_B"]);
+        case(&TestComponents::FLAGS[5], &[]);
+        case(&TestComponents::FLAGS[6], &[]);
+        case(&TestComponents::FLAGS[7], &["A", "B", "ALSO_B"]);
+
+        case(&TestFlags::FLAGS[3], &["A", "B", "C"]);
+        case(&TestOverlapping::FLAGS[0], &[]);
+    }
+
+    #[track_caller]
+    fn case<T: Flags>(flag: &crate::Flag<T>, expected: &[&str])
+    where
+        T::Bits: crate::BitsCount,
+    {
+        assert_eq!(
+            expected,
+            flag.components()
+                .map(|component| component.name())
+                .collect::<Vec<_>>(),
+            "{}.components()",
+            flag.name()
+        );
+    }
+}
+
 mod external {
     use super::*;
 
@@ -44,3 +247,53 @@ mod external {
         );
     }
 }
+
+mod doc {
+    use super::*;
+
+    bitflags! {
+        #[bitflags(docs)]
+        pub struct TestDoc: u8 {
+            /// A single line.
+            const A = 1;
+
+            /// Multiple
+            /// lines.
+            #[doc(alias = "bee")]
+            const B = 1 << 1;
+
+            #[doc = "An attribute."]
+            const C = 1 << 2;
+
+            const D = 1 << 3;
+
+            /// An unnamed flag.
+            const _ = !0;
+        }
+    }
+
+    #[test]
+    fn cases() {
+        let docs = TestDoc::FLAGS
+            .iter()
+            .map(|flag| (flag.name(), flag.doc()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                ("A", Some(" A single line.")),
+                ("B", Some(" Multiple\n lines.")),
+                ("C", Some("An attribute.")),
+                ("D", None),
+                ("", Some(" An unnamed flag.")),
+            ],
+            docs,
+        );
+
+        // Doc comments are only captured with the `docs` option
+        assert_eq!(None, TestFlags::FLAGS[1].doc());
+
+        assert_eq!(None, crate::Flag::new("A", 1u8).doc());
+        assert_eq!(Some("A"), crate::Flag::with_doc("A", 1u8, "A").doc());
+    }
+}
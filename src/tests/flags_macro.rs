@@ -0,0 +1,27 @@
+use super::*;
+
+mod nested {
+    bitflags! {
+        #[derive(Debug, PartialEq, Eq)]
+        pub struct TestNested: u8 {
+            const A = 1;
+            const B = 1 << 1;
+        }
+    }
+}
+
+const TEST_AB: TestFlags = flags!(TestFlags: A | B);
+
+#[test]
+fn cases() {
+    assert_eq!(TestFlags::empty(), flags!(TestFlags:));
+    assert_eq!(TestFlags::A, flags!(TestFlags: A));
+    assert_eq!(TestFlags::A | TestFlags::B, TEST_AB);
+    assert_eq!(TestFlags::all(), flags!(TestFlags: A | B | C));
+    assert_eq!(TestFlags::ABC, flags!(TestFlags: A | A | ABC));
+
+    assert_eq!(TestZero::ZERO, flags!(TestZero: ZERO));
+
+    assert_eq!(nested::TestNested::all(), flags!(nested::TestNested: A | B));
+    assert_eq!(nested::TestNested::B, flags!(self::nested::TestNested: B));
+}
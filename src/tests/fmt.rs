@@ -95,3 +95,78 @@ fn case<
     assert_eq!(oct, format!("{:o}", value));
     assert_eq!(bin, format!("{:b}", value));
 }
+
+#[test]
+fn width_and_alternate() {
+    for value in [
+        TestFlags::empty(),
+        TestFlags::A,
+        TestFlags::all(),
+        TestFlags::from_bits_retain(1 << 3),
+        TestFlags::A | TestFlags::from_bits_retain(1 << 7),
+    ] {
+        let bits = value.bits();
+
+        assert_eq!(format!("{:#b}", bits), format!("{:#b}", value));
+        assert_eq!(format!("{:#010b}", bits), format!("{:#010b}", value));
+        assert_eq!(format!("{:08x}", bits), format!("{:08x}", value));
+        assert_eq!(format!("{:#X}", bits), format!("{:#X}", value));
+        assert_eq!(format!("{:#o}", bits), format!("{:#o}", value));
+        assert_eq!(format!("{:>6x}", bits), format!("{:>6x}", value));
+        assert_eq!(format!("{:*<6X}", bits), format!("{:*<6X}", value));
+        assert_eq!(format!("{:^#8b}", bits), format!("{:^#8b}", value));
+    }
+
+    assert_eq!(
+        "0b00000101",
+        format!("{:#010b}", TestFlags::A | TestFlags::C)
+    );
+    assert_eq!("00000007", format!("{:08x}", TestFlags::all()));
+    assert_eq!(
+        "0x80",
+        format!("{:#X}", TestFlags::from_bits_retain(1 << 7))
+    );
+}
+
+mod debug_set {
+    bitflags! {
+        #[derive(Debug)]
+        #[bitflags(debug = "set")]
+        pub struct TestDebugSet: u8 {
+            const A = 1;
+            const B = 1 << 1;
+            const C = 1 << 2;
+
+            const AB = Self::A.bits() | Self::B.bits();
+        }
+    }
+
+    #[test]
+    fn cases() {
+        case(TestDebugSet::empty(), "TestDebugSet({})");
+        case(TestDebugSet::A, "TestDebugSet({A})");
+        case(TestDebugSet::A | TestDebugSet::C, "TestDebugSet({A, C})");
+        case(TestDebugSet::AB, "TestDebugSet({A, B})");
+        case(
+            TestDebugSet::from_bits_retain(1 << 3),
+            "TestDebugSet({0x8})",
+        );
+        case(
+            TestDebugSet::B | TestDebugSet::from_bits_retain(1 << 3 | 1 << 4),
+            "TestDebugSet({B, 0x18})",
+        );
+    }
+
+    #[test]
+    fn pretty() {
+        assert_eq!(
+            "TestDebugSet(\n    {\n        A,\n        B,\n    },\n)",
+            format!("{:#?}", TestDebugSet::AB)
+        );
+    }
+
+    #[track_caller]
+    fn case(value: TestDebugSet, debug: &str) {
+        assert_eq!(debug, format!("{:?}", value));
+    }
+}
@@ -0,0 +1,54 @@
+use super::*;
+
+use std::fmt;
+
+use crate::Flags;
+
+#[test]
+fn cases() {
+    case("", "0x0", TestFlags::empty());
+    case("A", "0x1", TestFlags::A);
+    case("A | B | C", "0x7", TestFlags::all());
+    case(
+        "A",
+        "0x9",
+        TestFlags::A | TestFlags::from_bits_retain(1 << 3),
+    );
+    case("", "0x8", TestFlags::from_bits_retain(1 << 3));
+
+    case("ABC", "0x7", TestFlagsInvert::all());
+
+    case("", "0x0", TestZero::ZERO);
+
+    case("一 | 二", "0x3", TestUnicode::all());
+
+    case("A | B | C", "0xff", TestExternal::from_bits_retain(!0));
+}
+
+#[track_caller]
+fn case<T: Flags + fmt::Debug>(names: &str, bits: &str, value: T)
+where
+    T::Bits: crate::parser::WriteHex,
+{
+    struct Names<'a, T>(&'a T);
+
+    impl<'a, T: Flags> fmt::Display for Names<'a, T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.fmt_names(f)
+        }
+    }
+
+    struct Bits<'a, T>(&'a T);
+
+    impl<'a, T: Flags> fmt::Display for Bits<'a, T>
+    where
+        T::Bits: crate::parser::WriteHex,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.fmt_bits(f)
+        }
+    }
+
+    assert_eq!(names, Names(&value).to_string(), "{:?}.fmt_names()", value);
+    assert_eq!(bits, Bits(&value).to_string(), "{:?}.fmt_bits()", value);
+}
@@ -0,0 +1,51 @@
+use super::*;
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct TestBitIndexSigned: i16 {
+        const A = 1;
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct TestBitIndexU128: u128 {
+        const A = 1;
+    }
+}
+
+#[test]
+fn cases() {
+    assert_eq!(Some(TestFlags::A), TestFlags::from_bit_index(0));
+    assert_eq!(Some(TestFlags::C), TestFlags::from_bit_index(2));
+    assert_eq!(
+        Some(TestFlags::from_bits_retain(1 << 3)),
+        TestFlags::from_bit_index(3)
+    );
+    assert_eq!(
+        Some(TestFlags::from_bits_retain(1 << 7)),
+        TestFlags::from_bit_index(7)
+    );
+    assert_eq!(None, TestFlags::from_bit_index(8));
+    assert_eq!(None, TestFlags::from_bit_index(9));
+    assert_eq!(None, TestFlags::from_bit_index(u32::MAX));
+
+    assert_eq!(
+        Some(TestBitIndexSigned::from_bits_retain(i16::MIN)),
+        TestBitIndexSigned::from_bit_index(15)
+    );
+    assert_eq!(None, TestBitIndexSigned::from_bit_index(16));
+
+    assert_eq!(
+        Some(TestBitIndexU128::from_bits_retain(1 << 127)),
+        TestBitIndexU128::from_bit_index(127)
+    );
+    assert_eq!(None, TestBitIndexU128::from_bit_index(128));
+
+    assert_eq!(None, TestEmpty::from_bit_index(8));
+    assert_eq!(
+        Some(TestEmpty::from_bits_retain(1)),
+        TestEmpty::from_bit_index(0)
+    );
+
+    const B: Option<TestFlags> = TestFlags::from_bit_index(1);
+    assert_eq!(Some(TestFlags::B), B);
+}
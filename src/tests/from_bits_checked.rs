@@ -0,0 +1,43 @@
+use super::*;
+
+use crate::{convert::UnknownBits, Flags};
+
+#[test]
+fn cases() {
+    case::<TestFlags>(Ok(0), 0);
+    case::<TestFlags>(Ok(1 | 1 << 2), 1 | 1 << 2);
+    case::<TestFlags>(Err(1 << 3), 1 << 3);
+    case::<TestFlags>(Err(1 << 3 | 1 << 7), 1 | 1 << 3 | 1 << 7);
+
+    case::<TestZero>(Ok(0), 0);
+    case::<TestZero>(Err(1), 1);
+
+    case::<TestOverlapping>(Ok(1 << 1), 1 << 1);
+    case::<TestOverlapping>(Err(1 << 3), 1 | 1 << 3);
+
+    case::<TestExternal>(Ok(1 << 5), 1 << 5);
+    case::<TestExternalFull>(Ok(!0), !0);
+
+    case::<TestEmpty>(Err(1), 1);
+}
+
+#[track_caller]
+fn case<T: Flags + std::fmt::Debug>(expected: Result<T::Bits, T::Bits>, input: T::Bits)
+where
+    T::Bits: std::fmt::Debug + PartialEq,
+{
+    assert_eq!(
+        expected.map_err(UnknownBits::new),
+        T::from_bits_checked(input).map(|f| f.bits()),
+        "Flags::from_bits_checked({:?})",
+        input
+    );
+
+    // The same values are accepted as `from_bits`
+    assert_eq!(
+        T::from_bits(input).map(|f| f.bits()),
+        T::from_bits_checked(input).ok().map(|f| f.bits()),
+        "Flags::from_bits({:?})",
+        input
+    );
+}
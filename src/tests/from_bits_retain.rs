@@ -36,3 +36,33 @@ where
         input
     );
 }
+
+#[test]
+fn generic_roundtrip() {
+    roundtrip(TestFlags::empty());
+    roundtrip(TestFlags::A | TestFlags::C);
+    roundtrip(TestFlags::all() | TestFlags::from_bits_retain(1 << 7));
+    roundtrip(TestZero::ZERO);
+    roundtrip(TestOverlapping::AB | TestOverlapping::BC);
+    roundtrip(TestExternal::from_bits_retain(!0));
+    roundtrip(TestExternalFull::from_bits_retain(1 << 6));
+}
+
+#[track_caller]
+fn roundtrip<T: Flags<Bits = u8> + std::fmt::Debug + PartialEq>(value: T) {
+    // Serialize and reconstruct using only the `Flags` trait
+    fn encode<T: Flags<Bits = u8>>(value: &T) -> [u8; 1] {
+        [value.bits()]
+    }
+
+    fn decode<T: Flags<Bits = u8>>(bytes: [u8; 1]) -> T {
+        T::from_bits_retain(bytes[0])
+    }
+
+    assert_eq!(
+        value,
+        decode::<T>(encode(&value)),
+        "decode(encode({:?}))",
+        value
+    );
+}
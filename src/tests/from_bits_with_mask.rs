@@ -0,0 +1,72 @@
+use super::*;
+
+use crate::Flags;
+
+#[test]
+fn cases() {
+    case(Some(0), 0, 0, TestFlags::from_bits_with_mask);
+    case(
+        Some(1 | 1 << 2),
+        1 | 1 << 2,
+        0,
+        TestFlags::from_bits_with_mask,
+    );
+
+    // A reserved bit is carried if it's in the allowed mask
+    case(
+        Some(1 | 1 << 7),
+        1 | 1 << 7,
+        1 << 7,
+        TestFlags::from_bits_with_mask,
+    );
+    case(
+        Some(1 << 7),
+        1 << 7,
+        1 << 6 | 1 << 7,
+        TestFlags::from_bits_with_mask,
+    );
+
+    // But fails if it isn't
+    case(None, 1 | 1 << 7, 0, TestFlags::from_bits_with_mask);
+    case(None, 1 | 1 << 7, 1 << 6, TestFlags::from_bits_with_mask);
+    case(
+        None,
+        1 << 6 | 1 << 7,
+        1 << 7,
+        TestFlags::from_bits_with_mask,
+    );
+
+    // Allowing bits that are already defined doesn't change anything
+    case(Some(1), 1, 1, TestFlags::from_bits_with_mask);
+    case(Some(!0), !0, !0, TestFlags::from_bits_with_mask);
+
+    case(None, 1, 0, TestZero::from_bits_with_mask);
+    case(Some(1), 1, 1, TestZero::from_bits_with_mask);
+
+    case(Some(1 << 5), 1 << 5, 0, TestExternal::from_bits_with_mask);
+}
+
+#[track_caller]
+fn case<T: Flags>(
+    expected: Option<T::Bits>,
+    input: T::Bits,
+    allowed_extra: T::Bits,
+    inherent: impl FnOnce(T::Bits, T::Bits) -> Option<T>,
+) where
+    <T as Flags>::Bits: std::fmt::Debug + PartialEq,
+{
+    assert_eq!(
+        expected,
+        inherent(input, allowed_extra).map(|f| f.bits()),
+        "T::from_bits_with_mask({:?}, {:?})",
+        input,
+        allowed_extra
+    );
+    assert_eq!(
+        expected,
+        T::from_bits_with_mask(input, allowed_extra).map(|f| f.bits()),
+        "Flags::from_bits_with_mask({:?}, {:?})",
+        input,
+        allowed_extra
+    );
+}
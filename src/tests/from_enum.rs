@@ -0,0 +1,56 @@
+#[derive(Debug, Clone, Copy)]
+pub enum TestVariant {
+    A,
+    B,
+    C = 5,
+    D,
+}
+
+from_enum! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct TestFromEnum: u8 {
+        const A = TestVariant::A;
+        const B = TestVariant::B;
+        const C = TestVariant::C;
+        const D = TestVariant::D;
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TestFromEnumImpl(u16);
+
+from_enum! {
+    impl TestFromEnumImpl: u16 {
+        const A = TestVariant::A;
+        const D = TestVariant::D;
+    }
+}
+
+#[test]
+fn cases() {
+    case(TestFromEnum::A, TestVariant::A, 1);
+    case(TestFromEnum::B, TestVariant::B, 1 << 1);
+    case(TestFromEnum::C, TestVariant::C, 1 << 5);
+    case(TestFromEnum::D, TestVariant::D, 1 << 6);
+
+    assert_eq!(0b0110_0011, TestFromEnum::all().bits());
+    assert_eq!(
+        Some(TestFromEnum::D),
+        TestFromEnum::from_name("D"),
+        "flags keep their declared names"
+    );
+
+    assert_eq!(1, TestFromEnumImpl::A.bits());
+    assert_eq!(1 << 6, TestFromEnumImpl::D.bits());
+}
+
+#[track_caller]
+fn case(flag: TestFromEnum, variant: TestVariant, expected: u8) {
+    assert_eq!(expected, flag.bits(), "{:?}", variant);
+    assert_eq!(
+        flag,
+        TestFromEnum::from_bits_retain(bit!(variant as u32)),
+        "{:?}",
+        variant
+    );
+}
@@ -0,0 +1,79 @@
+use super::*;
+
+use crate::Flags;
+
+#[test]
+fn cases() {
+    case(Some(0), &[], TestFlags::from_names);
+    case(Some(1), &["A"], TestFlags::from_names);
+    case(Some(1 | 1 << 2), &["A", "C"], TestFlags::from_names);
+    case(
+        Some(1 | 1 << 1 | 1 << 2),
+        &["ABC", "A"],
+        TestFlags::from_names,
+    );
+
+    case(Some(0), &["ZERO"], TestZero::from_names);
+
+    case(Some(1 | 1 << 1), &["一", "二"], TestUnicode::from_names);
+}
+
+#[test]
+fn invalid() {
+    assert!(TestFlags::from_names(["A", "D"])
+        .unwrap_err()
+        .to_string()
+        .starts_with("unrecognized named flag"));
+    assert!(TestFlags::from_names(["A | B"])
+        .unwrap_err()
+        .to_string()
+        .starts_with("unrecognized named flag"));
+    assert!(TestFlags::from_names(["0x1"])
+        .unwrap_err()
+        .to_string()
+        .starts_with("unrecognized named flag"));
+    assert!(TestExternal::from_names(["_"])
+        .unwrap_err()
+        .to_string()
+        .starts_with("unrecognized named flag"));
+
+    assert!(TestFlags::from_names(["A", ""])
+        .unwrap_err()
+        .to_string()
+        .starts_with("encountered empty flag"));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn invalid_reports_first_name() {
+    assert_eq!(
+        "unrecognized named flag `D`",
+        TestFlags::from_names(["A", "D", "E"])
+            .unwrap_err()
+            .to_string()
+    );
+}
+
+#[track_caller]
+fn case<T: Flags>(
+    expected: Option<T::Bits>,
+    input: &'static [&'static str],
+    inherent: impl FnOnce(
+        std::iter::Copied<std::slice::Iter<'static, &'static str>>,
+    ) -> Result<T, crate::parser::ParseError>,
+) where
+    <T as Flags>::Bits: std::fmt::Debug + PartialEq,
+{
+    assert_eq!(
+        expected,
+        inherent(input.iter().copied()).ok().map(|f| f.bits()),
+        "T::from_names({:?})",
+        input
+    );
+    assert_eq!(
+        expected,
+        T::from_names(input.iter().copied()).ok().map(|f| f.bits()),
+        "Flags::from_names({:?})",
+        input
+    );
+}
@@ -0,0 +1,53 @@
+use crate::Flags;
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[bitflags(docs)]
+    pub struct TestGroup: u8 {
+        /// Ready
+        #[bitflags(group = "status")]
+        const READY = 1;
+
+        #[bitflags(group = "status")]
+        #[deprecated]
+        const BUSY = 1 << 1;
+
+        #[bitflags(group = "control")]
+        const RESET = 1 << 2;
+
+        const OTHER = 1 << 3;
+
+        #[cfg(any())]
+        #[bitflags(group = "control")]
+        const NEVER = 1 << 4;
+
+        #[bitflags(group = "reserved")]
+        const _ = 1 << 7;
+    }
+}
+
+#[test]
+fn cases() {
+    let flags = TestGroup::FLAGS
+        .iter()
+        .map(|flag| (flag.name(), flag.group()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        vec![
+            ("READY", Some("status")),
+            ("BUSY", Some("status")),
+            ("RESET", Some("control")),
+            ("OTHER", None),
+            ("", Some("reserved")),
+        ],
+        flags
+    );
+
+    assert_eq!(Some(" Ready"), TestGroup::FLAGS[0].doc());
+    assert_eq!(
+        1 | 1 << 1 | 1 << 2 | 1 << 3 | 1 << 7,
+        TestGroup::all().bits()
+    );
+    assert_eq!(None, crate::Flag::new("A", TestGroup::READY).group());
+}
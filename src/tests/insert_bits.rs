@@ -0,0 +1,63 @@
+use super::*;
+
+use crate::Flags;
+
+#[test]
+fn cases() {
+    case(
+        TestFlags::empty(),
+        &[(1, 1), (1 | 1 << 1, 1 | 1 << 1), (0, 0), (1 << 3, 1 << 3)],
+        TestFlags::insert_bits,
+    );
+
+    case(
+        TestFlags::A | TestFlags::from_bits_retain(1 << 4),
+        &[
+            (1, 1 | 1 << 4),
+            (1 << 1 | 1 << 3, 1 | 1 << 1 | 1 << 3 | 1 << 4),
+        ],
+        TestFlags::insert_bits,
+    );
+
+    case(
+        TestZero::ZERO,
+        &[(1 << 7, 1 << 7), (0, 0)],
+        TestZero::insert_bits,
+    );
+}
+
+#[track_caller]
+fn case<T: Flags + std::fmt::Debug + Copy>(
+    value: T,
+    inputs: &[(T::Bits, T::Bits)],
+    mut inherent: impl FnMut(&mut T, T::Bits),
+) where
+    T::Bits: std::fmt::Debug + PartialEq + Copy,
+{
+    for (input, expected) in inputs {
+        assert_eq!(
+            *expected,
+            {
+                let mut value = value;
+                inherent(&mut value, *input);
+                value
+            }
+            .bits(),
+            "{:?}.insert_bits({:?})",
+            value,
+            input
+        );
+        assert_eq!(
+            *expected,
+            {
+                let mut value = value;
+                Flags::insert(&mut value, T::from_bits_retain(*input));
+                value
+            }
+            .bits(),
+            "Flags::insert({:?}, from_bits_retain({:?}))",
+            value,
+            input
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use super::*;
+
+use crate::Flags;
+
+#[test]
+fn cases() {
+    case(
+        TestFlags::empty(),
+        &[(TestFlags::empty(), 0), (TestFlags::all(), 0)],
+        TestFlags::intersect,
+        TestFlags::retain_only,
+    );
+
+    case(
+        TestFlags::all(),
+        &[
+            (TestFlags::all(), 1 | 1 << 1 | 1 << 2),
+            (TestFlags::A, 1),
+            (TestFlags::A | TestFlags::C, 1 | 1 << 2),
+            (TestFlags::from_bits_retain(1 << 3), 0),
+        ],
+        TestFlags::intersect,
+        TestFlags::retain_only,
+    );
+
+    case(
+        TestFlags::A | TestFlags::from_bits_retain(1 << 3),
+        &[
+            (TestFlags::from_bits_retain(1 << 3), 1 << 3),
+            (TestFlags::all(), 1),
+        ],
+        TestFlags::intersect,
+        TestFlags::retain_only,
+    );
+
+    case(
+        TestOverlapping::AB,
+        &[(TestOverlapping::BC, 1 << 1)],
+        TestOverlapping::intersect,
+        TestOverlapping::retain_only,
+    );
+}
+
+#[track_caller]
+fn case<T: Flags + std::fmt::Debug + Copy>(
+    value: T,
+    inputs: &[(T, T::Bits)],
+    mut inherent_intersect: impl FnMut(&mut T, T),
+    mut inherent_retain_only: impl FnMut(&mut T, T),
+) where
+    T::Bits: std::fmt::Debug + PartialEq + Copy,
+{
+    for (input, expected) in inputs {
+        // The in-place forms match the consuming `intersection`
+        assert_eq!(
+            *expected,
+            Flags::intersection(value, *input).bits(),
+            "Flags::intersection({:?}, {:?})",
+            value,
+            input
+        );
+
+        assert_eq!(
+            *expected,
+            {
+                let mut value = value;
+                inherent_intersect(&mut value, *input);
+                value
+            }
+            .bits(),
+            "{:?}.intersect({:?})",
+            value,
+            input
+        );
+        assert_eq!(
+            *expected,
+            {
+                let mut value = value;
+                Flags::intersect(&mut value, *input);
+                value
+            }
+            .bits(),
+            "Flags::intersect({:?}, {:?})",
+            value,
+            input
+        );
+
+        assert_eq!(
+            *expected,
+            {
+                let mut value = value;
+                inherent_retain_only(&mut value, *input);
+                value
+            }
+            .bits(),
+            "{:?}.retain_only({:?})",
+            value,
+            input
+        );
+        assert_eq!(
+            *expected,
+            {
+                let mut value = value;
+                Flags::retain_only(&mut value, *input);
+                value
+            }
+            .bits(),
+            "Flags::retain_only({:?}, {:?})",
+            value,
+            input
+        );
+    }
+}
@@ -64,6 +64,28 @@ fn cases() {
         ],
         TestOverlapping::intersects,
     );
+
+    // Multi-bit flags intersect if any of their bits are set
+    case(
+        TestOverlapping::from_bits_retain(1),
+        &[
+            (TestOverlapping::empty(), false),
+            (TestOverlapping::AB, true),
+            (TestOverlapping::BC, false),
+            (TestOverlapping::AB | TestOverlapping::BC, true),
+        ],
+        TestOverlapping::intersects,
+    );
+
+    case(
+        TestOverlapping::from_bits_retain(1 << 2 | 1 << 3),
+        &[
+            (TestOverlapping::AB, false),
+            (TestOverlapping::BC, true),
+            (TestOverlapping::from_bits_retain(1 << 3), true),
+        ],
+        TestOverlapping::intersects,
+    );
 }
 
 #[track_caller]
@@ -87,5 +109,12 @@ fn case<T: Flags + std::fmt::Debug + Copy>(
             value,
             input
         );
+        assert_eq!(
+            *expected,
+            Flags::intersects_ref(&value, input),
+            "Flags::intersects_ref({:?}, {:?})",
+            value,
+            input
+        );
     }
 }
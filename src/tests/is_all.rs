@@ -16,6 +16,16 @@ fn cases() {
     );
 
     case(true, TestZero::empty(), TestZero::is_all);
+    case(true, TestZero::ZERO, TestZero::is_all);
+
+    case(false, TestZeroOne::empty(), TestZeroOne::is_all);
+    case(false, TestZeroOne::ZERO, TestZeroOne::is_all);
+    case(true, TestZeroOne::ONE, TestZeroOne::is_all);
+    case(
+        true,
+        TestZeroOne::ZERO | TestZeroOne::ONE,
+        TestZeroOne::is_all,
+    );
 
     case(true, TestEmpty::empty(), TestEmpty::is_all);
 }
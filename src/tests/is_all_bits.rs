@@ -0,0 +1,70 @@
+use super::*;
+
+use crate::Flags;
+
+bitflags! {
+    #[derive(Debug)]
+    pub struct TestSigned: i8 {
+        const A = 1;
+        const MIN = i8::MIN;
+    }
+}
+
+#[test]
+fn cases() {
+    case(false, TestFlags::empty(), TestFlags::is_all_bits);
+    case(false, TestFlags::ABC, TestFlags::is_all_bits);
+    case(
+        false,
+        TestFlags::ABC | TestFlags::from_bits_retain(1 << 3),
+        TestFlags::is_all_bits,
+    );
+
+    case(
+        true,
+        TestFlags::from_bits_retain(u8::MAX),
+        TestFlags::is_all_bits,
+    );
+
+    case(false, TestZero::empty(), TestZero::is_all_bits);
+    case(false, TestEmpty::empty(), TestEmpty::is_all_bits);
+    case(
+        true,
+        TestEmpty::from_bits_retain(u8::MAX),
+        TestEmpty::is_all_bits,
+    );
+
+    case(true, TestExternalFull::all(), TestExternalFull::is_all_bits);
+
+    case(false, TestSigned::all(), TestSigned::is_all_bits);
+    case(
+        true,
+        TestSigned::from_bits_retain(-1),
+        TestSigned::is_all_bits,
+    );
+
+    const IS_ALL_BITS: bool = TestFlags::from_bits_retain(u8::MAX).is_all_bits();
+    assert!(IS_ALL_BITS);
+}
+
+#[test]
+fn differs_from_is_all() {
+    assert!(TestFlags::all().is_all());
+    assert!(!TestFlags::all().is_all_bits());
+
+    let max = TestFlags::from_bits_retain(u8::MAX);
+    assert!(max.is_all());
+    assert!(max.is_all_bits());
+    assert_ne!(TestFlags::all(), max);
+}
+
+#[track_caller]
+fn case<T: Flags + std::fmt::Debug>(expected: bool, value: T, inherent: impl FnOnce(&T) -> bool) {
+    assert_eq!(expected, inherent(&value), "{:?}.is_all_bits()", value);
+    assert_eq!(
+        expected,
+        Flags::is_all_bits(&value),
+        "Flags::is_all_bits({:?})",
+        value
+    );
+}
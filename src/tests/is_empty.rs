@@ -15,6 +15,10 @@ fn cases() {
     );
 
     case(true, TestZero::empty(), TestZero::is_empty);
+    case(true, TestZero::ZERO, TestZero::is_empty);
+
+    case(true, TestZeroOne::ZERO, TestZeroOne::is_empty);
+    case(false, TestZeroOne::ONE, TestZeroOne::is_empty);
 
     case(true, TestEmpty::empty(), TestEmpty::is_empty);
 }
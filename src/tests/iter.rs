@@ -22,6 +22,40 @@ fn roundtrip() {
     }
 }
 
+#[test]
+fn fused() {
+    fn assert_fused<I: std::iter::FusedIterator>(iter: I) -> I {
+        iter
+    }
+
+    for f in [
+        TestFlags::empty(),
+        TestFlags::A | TestFlags::C,
+        TestFlags::all() | TestFlags::from_bits_retain(1 << 3),
+    ] {
+        let mut iter = assert_fused(f.iter());
+        while iter.next().is_some() {}
+        assert!(iter.next().is_none());
+        assert!(iter.next().is_none());
+
+        let mut iter = assert_fused(f.iter_names());
+        while iter.next().is_some() {}
+        assert!(iter.next().is_none());
+        assert!(iter.next().is_none());
+
+        let mut iter = assert_fused(f.iter_with_unknown());
+        while iter.next().is_some() {}
+        assert!(iter.next().is_none());
+        assert!(iter.next().is_none());
+
+        // `fuse` is a no-op for fused iterators
+        assert_eq!(
+            f.iter().collect::<Vec<_>>(),
+            f.iter().fuse().collect::<Vec<_>>()
+        );
+    }
+}
+
 mod collect {
     use super::*;
 
@@ -206,4 +240,413 @@ mod iter_names {
             value
         );
     }
+
+    #[test]
+    fn generic_log() {
+        assert_eq!("", log(&TestFlags::empty()));
+        assert_eq!("A, C", log(&(TestFlags::A | TestFlags::C)));
+        assert_eq!("A, B, C", log(&TestFlags::ABC));
+        assert_eq!("ABC", log(&TestFlagsInvert::all()));
+        assert_eq!("一, 二", log(&TestUnicode::all()));
+        assert_eq!(
+            "A",
+            log(&(TestExternal::A | TestExternal::from_bits_retain(1 << 7)))
+        );
+    }
+
+    // Log the names of any flags value through the `Flags` trait
+    fn log<F: Flags>(flags: &F) -> String {
+        flags
+            .iter_names()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+mod iter_with_unknown {
+    use super::*;
+
+    use crate::iter::FlagOrBits;
+
+    #[test]
+    fn cases() {
+        case(&[], TestFlags::empty(), TestFlags::iter_with_unknown);
+
+        case(
+            &[(Some("A"), 1), (Some("C"), 1 << 2)],
+            TestFlags::A | TestFlags::C,
+            TestFlags::iter_with_unknown,
+        );
+        case(
+            &[(Some("A"), 1), (Some("B"), 1 << 1), (None, 1 << 3 | 1 << 7)],
+            TestFlags::A | TestFlags::B | TestFlags::from_bits_retain(1 << 3 | 1 << 7),
+            TestFlags::iter_with_unknown,
+        );
+        case(
+            &[(None, 1 << 3)],
+            TestFlags::from_bits_retain(1 << 3),
+            TestFlags::iter_with_unknown,
+        );
+
+        // Bits of a multi-bit flag that isn't fully contained are remaining bits
+        case(
+            &[(None, 1 << 1)],
+            TestOverlapping::from_bits_retain(1 << 1),
+            TestOverlapping::iter_with_unknown,
+        );
+
+        case(&[], TestZero::ZERO, TestZero::iter_with_unknown);
+
+        case(
+            &[(Some("A"), 1), (None, 1 << 7)],
+            TestExternal::A | TestExternal::from_bits_retain(1 << 7),
+            TestExternal::iter_with_unknown,
+        );
+    }
+
+    #[track_caller]
+    fn case<T: Flags + std::fmt::Debug>(
+        expected: &[(Option<&'static str>, T::Bits)],
+        value: T,
+        inherent: impl FnOnce(&T) -> crate::iter::IterWithUnknown<T>,
+    ) where
+        T::Bits: std::fmt::Debug + PartialEq,
+    {
+        // Named flags are compared as `(Some(name), bits)`, and remaining bits as `(None, bits)`
+        fn flatten<T: Flags>(item: FlagOrBits<T>) -> (Option<&'static str>, T::Bits) {
+            match item {
+                FlagOrBits::Flag(name, flag) => (Some(name), flag.bits()),
+                FlagOrBits::Unknown(bits) => (None, bits),
+            }
+        }
+
+        assert_eq!(
+            expected,
+            inherent(&value).map(flatten).collect::<Vec<_>>(),
+            "{:?}.iter_with_unknown()",
+            value
+        );
+        assert_eq!(
+            expected,
+            Flags::iter_with_unknown(&value)
+                .map(flatten)
+                .collect::<Vec<_>>(),
+            "Flags::iter_with_unknown({:?})",
+            value
+        );
+    }
+
+    #[test]
+    fn covers_all_bits() {
+        for bits in [0, 1, 1 << 3, 1 | 1 << 1 | 1 << 7, !0] {
+            let value = TestFlags::from_bits_retain(bits);
+
+            let mut covered = 0;
+            for item in value.iter_with_unknown() {
+                match item {
+                    FlagOrBits::Flag(_, flag) => covered |= flag.bits(),
+                    FlagOrBits::Unknown(unknown) => covered |= unknown,
+                }
+            }
+
+            assert_eq!(bits, covered, "{:?}.iter_with_unknown()", value);
+        }
+    }
+}
+
+mod iter_positions {
+    use super::*;
+
+    #[test]
+    fn cases() {
+        assert_eq!(
+            Vec::<(u32, TestFlags)>::new(),
+            TestFlags::empty().iter_positions().collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            vec![(0, TestFlags::A)],
+            TestFlags::A.iter_positions().collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            vec![(0, TestFlags::A), (1, TestFlags::B), (2, TestFlags::C)],
+            TestFlags::all().iter_positions().collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            vec![
+                (1, TestFlags::B),
+                (3, TestFlags::from_bits_retain(1 << 3 | 1 << 7))
+            ],
+            (TestFlags::B | TestFlags::from_bits_retain(1 << 3 | 1 << 7))
+                .iter_positions()
+                .collect::<Vec<_>>()
+        );
+
+        // Multi-bit flags yield the position of their lowest set bit
+        assert_eq!(
+            vec![(0, TestOverlapping::AB), (1, TestOverlapping::BC)],
+            (TestOverlapping::AB | TestOverlapping::BC)
+                .iter_positions()
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            vec![(1, TestOverlapping::BC)],
+            TestOverlapping::BC.iter_positions().collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            Vec::<(u32, TestZero)>::new(),
+            TestZero::ZERO.iter_positions().collect::<Vec<_>>()
+        );
+    }
+}
+
+mod iter_coalesced {
+    use super::*;
+
+    #[test]
+    fn cases() {
+        case(TestFlags::empty(), &[]);
+        case(TestFlags::A, &[TestFlags::A]);
+        case(TestFlags::A | TestFlags::C, &[TestFlags::A, TestFlags::C]);
+        case(TestFlags::all(), &[TestFlags::ABC]);
+        case(
+            TestFlags::all() | TestFlags::from_bits_retain(1 << 3),
+            &[TestFlags::ABC, TestFlags::from_bits_retain(1 << 3)],
+        );
+        case(
+            TestFlags::from_bits_retain(1 << 3),
+            &[TestFlags::from_bits_retain(1 << 3)],
+        );
+
+        case(TestFlagsInvert::all(), &[TestFlagsInvert::ABC]);
+
+        case(TestZero::ZERO, &[]);
+
+        // Flags with the same number of bits are yielded in declaration order
+        case(
+            TestOverlapping::AB | TestOverlapping::BC,
+            &[TestOverlapping::AB, TestOverlapping::BC],
+        );
+
+        case(
+            TestOverlappingFull::A | TestOverlappingFull::D,
+            &[TestOverlappingFull::A, TestOverlappingFull::D],
+        );
+
+        case(
+            TestCoalesced::all(),
+            &[TestCoalesced::CDE, TestCoalesced::AB, TestCoalesced::F],
+        );
+        case(
+            TestCoalesced::A | TestCoalesced::C | TestCoalesced::D,
+            &[TestCoalesced::A, TestCoalesced::C, TestCoalesced::D],
+        );
+
+        // `iter` is unchanged
+        assert_eq!(
+            vec![TestFlags::A, TestFlags::B, TestFlags::C],
+            TestFlags::all().iter().collect::<Vec<_>>()
+        );
+    }
+
+    bitflags! {
+        #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+        pub struct TestCoalesced: u8 {
+            const A = 1;
+            const B = 1 << 1;
+            const C = 1 << 2;
+            const D = 1 << 3;
+            const E = 1 << 4;
+            const F = 1 << 5;
+
+            const AB = Self::A.bits() | Self::B.bits();
+            const CDE = Self::C.bits() | Self::D.bits() | Self::E.bits();
+        }
+    }
+
+    #[track_caller]
+    fn case<T>(value: T, expected: &[T])
+    where
+        T: Flags + std::fmt::Debug + PartialEq + Copy + IntoCoalesced,
+    {
+        assert_eq!(
+            expected,
+            &*value.iter_coalesced_vec(),
+            "{:?}.iter_coalesced()",
+            value
+        );
+    }
+
+    trait IntoCoalesced: Sized {
+        fn iter_coalesced_vec(&self) -> Vec<Self>;
+    }
+
+    macro_rules! impl_into_coalesced {
+        ($($ty:ty),*) => {
+            $(
+                impl IntoCoalesced for $ty {
+                    fn iter_coalesced_vec(&self) -> Vec<Self> {
+                        self.iter_coalesced().collect()
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_into_coalesced!(
+        TestFlags,
+        TestFlagsInvert,
+        TestZero,
+        TestOverlapping,
+        TestOverlappingFull,
+        TestCoalesced
+    );
+}
+
+mod iter_sorted {
+    use super::*;
+
+    bitflags! {
+        #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+        pub struct TestUnordered: u8 {
+            const C = 1 << 2;
+            const A = 1;
+            const DE = 1 << 3 | 1 << 4;
+            const B = 1 << 1;
+        }
+
+        #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+        pub struct TestSigned: i8 {
+            const MIN = i8::MIN;
+            const A = 1;
+        }
+    }
+
+    #[test]
+    fn cases() {
+        assert_eq!(0, TestUnordered::empty().iter_sorted().count());
+
+        assert_eq!(
+            vec![
+                TestUnordered::A,
+                TestUnordered::B,
+                TestUnordered::C,
+                TestUnordered::DE
+            ],
+            TestUnordered::all().iter_sorted().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![
+                TestUnordered::C,
+                TestUnordered::A,
+                TestUnordered::DE,
+                TestUnordered::B
+            ],
+            TestUnordered::all().iter().collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            vec![
+                TestUnordered::A,
+                TestUnordered::C,
+                TestUnordered::from_bits_retain(1 << 7)
+            ],
+            (TestUnordered::C | TestUnordered::A | TestUnordered::from_bits_retain(1 << 7))
+                .iter_sorted()
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            vec![TestOverlapping::AB, TestOverlapping::BC],
+            (TestOverlapping::BC | TestOverlapping::AB)
+                .iter_sorted()
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            vec![TestSigned::A, TestSigned::MIN],
+            TestSigned::all().iter_sorted().collect::<Vec<_>>()
+        );
+    }
+}
+
+mod declaration_order {
+    use super::*;
+
+    bitflags! {
+        #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+        pub struct TestDeclared: u8 {
+            const E = 1 << 4;
+            const AB = 1 | 1 << 1;
+            const C = 1 << 2;
+            const A = 1;
+            const _ = 1 << 5;
+            const B = 1 << 1;
+            const D = 1 << 3;
+        }
+    }
+
+    #[test]
+    fn cases() {
+        case(
+            TestDeclared::all(),
+            &["E", "AB", "C", "D"],
+            "E | AB | C | D | 0x20",
+        );
+        case(
+            TestDeclared::A | TestDeclared::D | TestDeclared::E,
+            &["E", "A", "D"],
+            "E | A | D",
+        );
+        case(
+            TestDeclared::B | TestDeclared::C | TestDeclared::from_bits_retain(1 << 7),
+            &["C", "B"],
+            "C | B | 0x80",
+        );
+        case(TestDeclared::empty(), &[], "");
+    }
+
+    #[track_caller]
+    fn case(value: TestDeclared, expected: &[&str], text: &str) {
+        assert_eq!(
+            expected,
+            value.iter_names().map(|(name, _)| name).collect::<Vec<_>>(),
+            "{:?}.iter_names()",
+            value
+        );
+        assert_eq!(
+            expected,
+            Flags::iter_names(&value)
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>(),
+            "Flags::iter_names({:?})",
+            value
+        );
+
+        let mut expected_iter = expected
+            .iter()
+            .map(|name| TestDeclared::from_name(name).unwrap())
+            .collect::<Vec<_>>();
+        let unknown = value.difference(expected_iter.iter().copied().collect());
+        if !unknown.is_empty() {
+            expected_iter.push(unknown);
+        }
+
+        assert_eq!(
+            expected_iter,
+            value.iter().collect::<Vec<_>>(),
+            "{:?}.iter()",
+            value
+        );
+
+        let mut written = String::new();
+        crate::parser::to_writer(&value, &mut written).unwrap();
+
+        assert_eq!(text, written, "to_writer({:?})", value);
+    }
 }
@@ -0,0 +1,37 @@
+use super::*;
+
+use crate::Flags;
+
+#[test]
+fn cases() {
+    case(
+        TestFlags::ABC | TestFlags::from_bits_retain(1 << 3),
+        TestFlags::from_bits_retain(1 << 3),
+    );
+
+    case(
+        TestFlags::B | TestFlags::from_bits_retain(1 << 3 | 1 << 7),
+        TestFlags::from_bits_retain(1 << 3 | 1 << 7),
+    );
+
+    case(
+        TestFlags::from_bits_retain(1 << 4),
+        TestFlags::from_bits_retain(1 << 4),
+    );
+
+    case(TestFlags::empty(), TestFlags::empty());
+
+    case(TestZero::empty(), TestZero::empty());
+
+    case(TestExternal::all(), TestExternal::empty());
+}
+
+#[track_caller]
+fn case<T>(mut before: T, after: T)
+where
+    T: Flags + std::fmt::Debug + PartialEq + Copy,
+{
+    let value = before;
+    before.keep_unknown();
+    assert_eq!(before, after, "{:?}.keep_unknown()", value);
+}
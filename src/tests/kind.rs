@@ -0,0 +1,61 @@
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[bitflags(kind = TestKindKind)]
+    pub struct TestKind: u8 {
+        /// A single bit
+        const A = 1;
+        #[bitflags(group = "b")]
+        const B = 1 << 1;
+        const AB = Self::A.bits() | Self::B.bits();
+        #[cfg(any())]
+        const C = 1 << 2;
+        const D = 1 << 3;
+        const ALIAS_D = Self::D.bits();
+        const ZERO = 0;
+
+        const _ = !0;
+    }
+}
+
+#[test]
+fn cases() {
+    case(Some(TestKindKind::A), TestKind::A);
+    case(Some(TestKindKind::B), TestKind::B);
+    case(Some(TestKindKind::D), TestKind::D);
+    case(Some(TestKindKind::D), TestKind::ALIAS_D);
+
+    case(None, TestKind::AB);
+    case(None, TestKind::ZERO);
+    case(None, TestKind::empty());
+    case(None, TestKind::A | TestKind::D);
+    case(None, TestKind::from_bits_retain(1 << 5));
+    case(None, TestKind::A | TestKind::from_bits_retain(1 << 5));
+}
+
+#[test]
+fn exhaustive_match() {
+    fn name(kind: TestKindKind) -> &'static str {
+        match kind {
+            TestKindKind::A => "A",
+            TestKindKind::B => "B",
+            TestKindKind::AB => "AB",
+            TestKindKind::D => "D",
+            TestKindKind::ALIAS_D => "ALIAS_D",
+            TestKindKind::ZERO => "ZERO",
+        }
+    }
+
+    assert_eq!(
+        vec!["A", "B", "D"],
+        [TestKind::A, TestKind::AB, TestKind::B, TestKind::D]
+            .iter()
+            .filter_map(|flag| flag.kind())
+            .map(name)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[track_caller]
+fn case(expected: Option<TestKindKind>, value: TestKind) {
+    assert_eq!(expected, value.kind(), "{:?}.kind()", value);
+}
@@ -0,0 +1,83 @@
+use crate::{parser, Flags};
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[bitflags(minimal)]
+    pub struct TestMinimal: u8 {
+        const A = 1;
+        const B = 1 << 1;
+        const C = 1 << 2;
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[bitflags(minimal, debug = "set")]
+    pub struct TestMinimalDebugSet: u8 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TestMinimalImpl(u8);
+
+bitflags! {
+    #[bitflags(minimal, new)]
+    impl TestMinimalImpl: u8 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+}
+
+#[test]
+fn set_operations() {
+    let flags = TestMinimal::A | TestMinimal::B;
+
+    assert_eq!(1 | 1 << 1, flags.bits());
+    assert_eq!(flags, TestMinimal::from_bits_retain(0b11));
+    assert_eq!(Some(flags), TestMinimal::from_bits(0b11));
+    assert_eq!(TestMinimal::A, flags & TestMinimal::A);
+    assert_eq!(TestMinimal::B, flags - TestMinimal::A);
+    assert_eq!(TestMinimal::C, !flags);
+    assert!(flags.contains(TestMinimal::A));
+    assert!(!flags.is_all());
+
+    assert_eq!(
+        TestMinimalImpl::A | TestMinimalImpl::B,
+        TestMinimalImpl::new(0b11)
+    );
+}
+
+#[test]
+fn debug() {
+    assert_eq!("TestMinimal(0x0)", format!("{:?}", TestMinimal::empty()));
+    assert_eq!(
+        "TestMinimal(0x83)",
+        format!(
+            "{:?}",
+            TestMinimal::A | TestMinimal::B | TestMinimal::from_bits_retain(1 << 7)
+        )
+    );
+
+    // An explicit `debug` option still applies
+    assert_eq!(
+        "TestMinimalDebugSet({A, B})",
+        format!("{:?}", TestMinimalDebugSet::all())
+    );
+}
+
+#[test]
+fn generic() {
+    // The `Flags` trait and `parser` still work with minimal flags types
+    assert_eq!(
+        vec![TestMinimal::A, TestMinimal::C],
+        Flags::iter(&(TestMinimal::A | TestMinimal::C)).collect::<Vec<_>>()
+    );
+
+    let mut text = String::new();
+    parser::to_writer(&TestMinimal::all(), &mut text).unwrap();
+    assert_eq!("A | B | C", text);
+    assert_eq!(
+        TestMinimal::all(),
+        parser::from_str::<TestMinimal>("A | B | C").unwrap()
+    );
+}
@@ -0,0 +1,45 @@
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[bitflags(new)]
+    pub struct TestNew: u8 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TestNewImpl(u8);
+
+bitflags! {
+    #[bitflags(new, default = A)]
+    impl TestNewImpl: u8 {
+        const A = 1;
+    }
+}
+
+#[test]
+fn cases() {
+    case(0, TestNew::empty());
+    case(1, TestNew::A);
+    case(1 | 1 << 1, TestNew::all());
+    case(1 << 7, TestNew::from_bits_retain(1 << 7));
+    case(!0, TestNew::all() | TestNew::from_bits_retain(!0));
+
+    assert_eq!(TestNewImpl::A, TestNewImpl::new(1));
+    assert_eq!(TestNewImpl(1 << 3), TestNewImpl::new(1 << 3));
+    assert_eq!(TestNewImpl::A, TestNewImpl::default());
+
+    const NEW: TestNew = TestNew::new(1 << 1);
+    assert_eq!(TestNew::B, NEW);
+}
+
+#[track_caller]
+fn case(bits: u8, expected: TestNew) {
+    assert_eq!(expected, TestNew::new(bits), "TestNew::new({:#x})", bits);
+    assert_eq!(
+        TestNew::from_bits_retain(bits),
+        TestNew::new(bits),
+        "TestNew::new({:#x})",
+        bits
+    );
+}
@@ -0,0 +1,44 @@
+use super::*;
+
+use crate::Flags;
+
+#[test]
+fn cases() {
+    case::<TestFlags>(0, 0, "");
+    case::<TestFlags>(1, 1, "A");
+    case::<TestFlags>(1 | 1 << 1 | 1 << 2, 1 | 1 << 1 | 1 << 2, "A | B | C");
+    case::<TestFlags>(1 << 3, 0, "0x8");
+    case::<TestFlags>(1 | 1 << 3, 1, "A | 0x8");
+
+    case::<TestZero>(0, 0, "ZERO");
+
+    case::<TestExternal>(1 << 7, 1 << 7, "0x80");
+}
+
+#[test]
+fn invalid() {
+    assert!(TestFlags::parse("D").is_err());
+    assert!(TestFlags::parse_truncate("D").is_err());
+
+    assert!(TestFlags::parse("A | 0xx").is_err());
+    assert!(TestFlags::parse_truncate("A | 0xx").is_err());
+}
+
+#[track_caller]
+fn case<T: Flags>(expected: T::Bits, expected_truncate: T::Bits, input: &str)
+where
+    T::Bits: crate::parser::ParseHex + std::fmt::Debug + PartialEq,
+{
+    assert_eq!(
+        expected,
+        T::parse(input).unwrap().bits(),
+        "Flags::parse({:?})",
+        input
+    );
+    assert_eq!(
+        expected_truncate,
+        T::parse_truncate(input).unwrap().bits(),
+        "Flags::parse_truncate({:?})",
+        input
+    );
+}
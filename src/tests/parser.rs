@@ -19,6 +19,51 @@ fn roundtrip() {
     }
 }
 
+#[test]
+#[cfg(not(miri))] // Very slow in miri
+fn roundtrip_examples() {
+    bitflags! {
+        #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+        pub struct TestSigned: i8 {
+            const A = 1;
+            const B = 1 << 1;
+        }
+    }
+
+    for bits in 0u8..=255 {
+        roundtrip_case(TestFlags::from_bits_retain(bits));
+        roundtrip_case(TestFlagsInvert::from_bits_retain(bits));
+        roundtrip_case(TestZero::from_bits_retain(bits));
+        roundtrip_case(TestZeroOne::from_bits_retain(bits));
+        roundtrip_case(TestUnicode::from_bits_retain(bits));
+        roundtrip_case(TestEmpty::from_bits_retain(bits));
+        roundtrip_case(TestOverlapping::from_bits_retain(bits));
+        roundtrip_case(TestOverlappingFull::from_bits_retain(bits));
+        roundtrip_case(TestExternal::from_bits_retain(bits));
+        roundtrip_case(TestExternalFull::from_bits_retain(bits));
+        roundtrip_case(TestSigned::from_bits_retain(bits as i8));
+    }
+}
+
+#[track_caller]
+fn roundtrip_case<F: Flags + std::fmt::Debug + PartialEq>(value: F)
+where
+    F::Bits: ParseHex + WriteHex,
+{
+    for options in [WriteOptions::new(), WriteOptions::new().pad_hex(true)] {
+        let mut s = String::new();
+        to_writer_with_options(&value, &mut s, options).unwrap();
+
+        assert_eq!(
+            value,
+            from_str::<F>(&s).unwrap(),
+            "from_str(to_writer({:?})) with {:?}",
+            value,
+            options
+        );
+    }
+}
+
 #[test]
 #[cfg(not(miri))] // Very slow in miri
 fn roundtrip_truncate() {
@@ -64,6 +109,16 @@ fn roundtrip_strict() {
     }
 }
 
+#[test]
+#[cfg(feature = "core_error")]
+fn core_error() {
+    fn assert_error<E: core::error::Error>() {}
+
+    assert_error::<ParseError>();
+    assert_error::<crate::convert::NotPresent>();
+    assert_error::<crate::convert::UnknownBits<u8>>();
+}
+
 mod from_str {
     use super::*;
 
@@ -115,9 +170,308 @@ mod from_str {
             .to_string()
             .starts_with("invalid hex flag"));
         assert!(from_str::<TestFlags>("0xffffffffffff")
+            .unwrap_err()
+            .to_string()
+            .starts_with("flag overflows the bits type"));
+    }
+}
+
+mod parse_prefix {
+    use super::*;
+
+    #[test]
+    fn cases() {
+        case(0, "", "");
+        case(0, "   ", "   ");
+        case(0, "rest", "rest");
+        case(0, "| A", "| A");
+
+        case(1, "A", "");
+        case(1, " A ", " ");
+        case(1 | 1 << 1 | 1 << 2, "A | B | C", "");
+        case(1 | 1 << 1 | 1 << 2, "A|B|C", "");
+        case(1 | 1 << 3, "A | 0x8", "");
+
+        case(1 | 1 << 1, "A | B; rest", "; rest");
+        case(1 | 1 << 1, "A | B rest", " rest");
+        case(1, "A | rest", " | rest");
+        case(1, "A |", " |");
+        case(1, "A | | B", " | | B");
+        case(1, "A & B", " & B");
+        case(0, "AB", "AB");
+        case(1, "A.B", ".B");
+        case(1 << 3, "0x8g", "g");
+        case(1 << 3, "0x8 | ", " | ");
+
+        assert_eq!(
+            (TestUnicode::一 | TestUnicode::二, ")"),
+            parse_prefix::<TestUnicode>("一 | 二)").unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(parse_prefix::<TestFlags>("0xg")
             .unwrap_err()
             .to_string()
             .starts_with("invalid hex flag"));
+        assert!(parse_prefix::<TestFlags>("A | 0x")
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid hex flag"));
+        assert!(parse_prefix::<TestFlags>("0xffffffffffff")
+            .unwrap_err()
+            .to_string()
+            .starts_with("flag overflows the bits type"));
+    }
+
+    #[track_caller]
+    fn case(expected: u8, input: &str, rest: &str) {
+        let (flags, actual_rest) = parse_prefix::<TestFlags>(input).unwrap();
+
+        assert_eq!(expected, flags.bits(), "parse_prefix({:?})", input);
+        assert_eq!(rest, actual_rest, "parse_prefix({:?})", input);
+    }
+}
+
+mod from_str_negative {
+    use super::*;
+
+    bitflags! {
+        #[derive(Debug, PartialEq, Eq)]
+        pub struct TestSigned: i32 {
+            const A = 1;
+            const B = 1 << 1;
+        }
+
+        #[derive(Debug, PartialEq, Eq)]
+        pub struct TestSignedI8: i8 {
+            const A = 1;
+        }
+    }
+
+    #[test]
+    fn valid() {
+        assert_eq!(-1, from_str::<TestSigned>("-1").unwrap().bits());
+        assert_eq!(-2, from_str::<TestSigned>("-2").unwrap().bits());
+        assert_eq!(-2, from_str::<TestSigned>(" -2 ").unwrap().bits());
+        assert_eq!(-1, from_str::<TestSigned>("A | -2").unwrap().bits());
+        assert_eq!(
+            i32::MIN | 1,
+            from_str::<TestSigned>("-2147483648 | A").unwrap().bits()
+        );
+        assert_eq!(1 << 1, from_str::<TestSigned>("0x2").unwrap().bits());
+        assert_eq!(-128, from_str::<TestSignedI8>("-128").unwrap().bits());
+
+        assert_eq!(
+            TestSigned::A | TestSigned::B,
+            from_str_truncate::<TestSigned>("-1").unwrap()
+        );
+
+        assert_eq!(
+            (TestSigned::from_bits_retain(-2), " rest"),
+            parse_prefix::<TestSigned>("-2 rest").unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(from_str::<TestSigned>("-")
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid negative flag"));
+        assert!(from_str::<TestSigned>("-0x1")
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid negative flag"));
+        assert!(from_str::<TestSigned>("--1")
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid negative flag"));
+        assert!(from_str::<TestSignedI8>("-129")
+            .unwrap_err()
+            .to_string()
+            .starts_with("flag overflows the bits type"));
+
+        assert!(from_str::<TestFlags>("-1")
+            .unwrap_err()
+            .to_string()
+            .starts_with("negative flag for unsigned bits"));
+        assert!(parse_prefix::<TestFlags>("-1")
+            .unwrap_err()
+            .to_string()
+            .starts_with("negative flag for unsigned bits"));
+
+        assert!(from_str_strict::<TestSigned>("-1")
+            .unwrap_err()
+            .to_string()
+            .starts_with("unrecognized named flag"));
+    }
+}
+
+mod tokenize {
+    use super::*;
+
+    use std::ops::Range;
+
+    // Either a token, or the span of an empty flag error
+    type Expected = Result<(TokenKind, Range<usize>), Range<usize>>;
+
+    #[test]
+    fn valid() {
+        case("", &[]);
+        case("  ", &[]);
+
+        case("A", &[Ok((TokenKind::Name, 0..1))]);
+        case(
+            "  A | B  |0x0c|-1 ",
+            &[
+                Ok((TokenKind::Name, 2..3)),
+                Ok((TokenKind::Separator, 4..5)),
+                Ok((TokenKind::Name, 6..7)),
+                Ok((TokenKind::Separator, 9..10)),
+                Ok((TokenKind::Number, 10..14)),
+                Ok((TokenKind::Separator, 14..15)),
+                Ok((TokenKind::Number, 15..17)),
+            ],
+        );
+
+        // Tokens aren't resolved, so unknown names and invalid numbers are still tokens
+        case(
+            "not a flag|0xzz",
+            &[
+                Ok((TokenKind::Name, 0..10)),
+                Ok((TokenKind::Separator, 10..11)),
+                Ok((TokenKind::Number, 11..15)),
+            ],
+        );
+
+        // Spans are in bytes
+        case(
+            "🚩 | A",
+            &[
+                Ok((TokenKind::Name, 0..4)),
+                Ok((TokenKind::Separator, 5..6)),
+                Ok((TokenKind::Name, 7..8)),
+            ],
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        case(
+            "A || B",
+            &[
+                Ok((TokenKind::Name, 0..1)),
+                Ok((TokenKind::Separator, 2..3)),
+                Err(3..3),
+                Ok((TokenKind::Separator, 3..4)),
+                Ok((TokenKind::Name, 5..6)),
+            ],
+        );
+        case(
+            "| A |  ",
+            &[
+                Err(0..0),
+                Ok((TokenKind::Separator, 0..1)),
+                Ok((TokenKind::Name, 2..3)),
+                Ok((TokenKind::Separator, 4..5)),
+                Err(7..7),
+            ],
+        );
+        case(
+            "|",
+            &[Err(0..0), Ok((TokenKind::Separator, 0..1)), Err(1..1)],
+        );
+    }
+
+    #[track_caller]
+    fn case(input: &str, expected: &[Expected]) {
+        let actual = tokenize(input)
+            .map(|token| match token {
+                Ok(token) => Ok((token.kind(), token.span())),
+                Err(e) => {
+                    assert!(
+                        e.to_string().starts_with("encountered empty flag"),
+                        "{:?}",
+                        e
+                    );
+                    Err(e.span().unwrap())
+                }
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(expected, &*actual, "tokenize({:?})", input);
+
+        for (kind, span) in actual.iter().flatten() {
+            assert_eq!(
+                input[span.clone()].trim(),
+                &input[span.clone()],
+                "{:?} isn't trimmed",
+                kind
+            );
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod parse_collecting_errors {
+    use super::*;
+
+    #[test]
+    fn valid() {
+        assert_eq!(0, parse_collecting_errors::<TestFlags>("").unwrap().bits());
+        assert_eq!(
+            0,
+            parse_collecting_errors::<TestFlags>("  ").unwrap().bits()
+        );
+
+        assert_eq!(
+            1 | 1 << 1 | 1 << 3,
+            parse_collecting_errors::<TestFlags>("A | B | 0x8")
+                .unwrap()
+                .bits()
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        case("a", &[("unrecognized named flag", 0..1)]);
+        case(
+            "A | X | B | Y",
+            &[
+                ("unrecognized named flag", 4..5),
+                ("unrecognized named flag", 12..13),
+            ],
+        );
+        case(
+            "  foo|0xg|  C  |bar  ",
+            &[
+                ("unrecognized named flag", 2..5),
+                ("invalid hex flag", 6..9),
+                ("unrecognized named flag", 16..19),
+            ],
+        );
+        case("A || B", &[("encountered empty flag", 3..3)]);
+        case("A | -1", &[("negative flag for unsigned bits", 4..6)]);
+    }
+
+    #[track_caller]
+    fn case(input: &str, expected: &[(&str, std::ops::Range<usize>)]) {
+        let errors = parse_collecting_errors::<TestFlags>(input).unwrap_err();
+
+        assert_eq!(expected.len(), errors.len(), "{:?}", errors);
+
+        for (error, (message, span)) in errors.iter().zip(expected) {
+            assert!(
+                error.to_string().starts_with(message),
+                "{:?} should start with {:?}",
+                error.to_string(),
+                message
+            );
+            assert_eq!(Some(span.clone()), error.span(), "{:?}", error);
+            assert_eq!(&input[span.clone()].trim(), &&input[span.clone()]);
+        }
     }
 }
 
@@ -159,6 +513,89 @@ mod to_writer {
     }
 }
 
+#[cfg(feature = "std")]
+mod to_io_writer {
+    use super::*;
+
+    #[test]
+    fn cases() {
+        assert_eq!(&b""[..], write(TestFlags::empty()));
+        assert_eq!(&b"A | B | C"[..], write(TestFlags::all()));
+        assert_eq!(
+            &b"A | 0x8"[..],
+            write(TestFlags::A | TestFlags::from_bits_retain(1 << 3))
+        );
+        assert_eq!(&b"ABC"[..], write(TestFlagsInvert::all()));
+    }
+
+    #[test]
+    fn matches_to_writer() {
+        let value = TestOverlappingFull::C | TestOverlappingFull::D;
+
+        let mut s = String::new();
+        to_writer(&value, &mut s).unwrap();
+
+        assert_eq!(s.as_bytes(), write(value));
+    }
+
+    #[test]
+    fn error() {
+        struct Failing;
+
+        impl std::io::Write for Failing {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::new(
+                    std::io::ErrorKind::BrokenPipe,
+                    "broken",
+                ))
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        assert_eq!(
+            std::io::ErrorKind::BrokenPipe,
+            to_io_writer(&TestFlags::A, Failing).unwrap_err().kind()
+        );
+    }
+
+    fn write<F: Flags>(value: F) -> Vec<u8>
+    where
+        F::Bits: crate::parser::WriteHex,
+    {
+        let mut v = Vec::new();
+
+        to_io_writer(&value, &mut v).unwrap();
+        v
+    }
+}
+
+#[cfg(feature = "heapless")]
+mod to_heapless {
+    use super::*;
+
+    #[test]
+    fn cases() {
+        assert_eq!("", &*to_heapless::<_, 0>(&TestFlags::empty()).unwrap());
+        assert_eq!(
+            "A | B | C",
+            &*to_heapless::<_, 9>(&TestFlags::all()).unwrap()
+        );
+        assert_eq!(
+            "A | 0x8",
+            &*to_heapless::<_, 16>(&(TestFlags::A | TestFlags::from_bits_retain(1 << 3))).unwrap()
+        );
+    }
+
+    #[test]
+    fn overflow() {
+        assert!(to_heapless::<_, 8>(&TestFlags::all()).is_err());
+        assert!(to_heapless::<_, 0>(&TestFlags::A).is_err());
+    }
+}
+
 mod from_str_truncate {
     use super::*;
 
@@ -199,6 +636,106 @@ mod from_str_truncate {
     }
 }
 
+mod to_writer_with_options {
+    use super::*;
+
+    bitflags! {
+        #[derive(Debug)]
+        pub struct TestU16: u16 {
+            const A = 1;
+        }
+
+        #[derive(Debug)]
+        pub struct TestU32: u32 {
+            const A = 1;
+        }
+
+        #[derive(Debug)]
+        pub struct TestU64: u64 {
+            const A = 1;
+        }
+
+        #[derive(Debug)]
+        pub struct TestU128: u128 {
+            const A = 1;
+        }
+
+        #[derive(Debug)]
+        pub struct TestI8: i8 {
+            const A = 1;
+        }
+
+        #[derive(Debug)]
+        pub struct TestI32: i32 {
+            const A = 1;
+        }
+    }
+
+    #[test]
+    fn default() {
+        assert_eq!("", write(TestFlags::empty(), WriteOptions::new()));
+        assert_eq!(
+            "A | 0x8",
+            write(TestFlags::from_bits_retain(1 | 1 << 3), WriteOptions::new())
+        );
+        assert_eq!(
+            "0xf0",
+            write(TestU32::from_bits_retain(0xf0), WriteOptions::new())
+        );
+    }
+
+    #[test]
+    fn pad_hex() {
+        let options = WriteOptions::new().pad_hex(true);
+
+        assert_eq!("", write(TestFlags::empty(), options));
+        assert_eq!("A | B", write(TestFlags::A | TestFlags::B, options));
+
+        assert_eq!("0x08", write(TestFlags::from_bits_retain(1 << 3), options));
+        assert_eq!(
+            "A | B | C | 0xf8",
+            write(TestFlags::from_bits_retain(!0), options)
+        );
+        assert_eq!("0x0010", write(TestU16::from_bits_retain(1 << 4), options));
+        assert_eq!(
+            "A | 0x0000000e",
+            write(TestU32::from_bits_retain(0xf), options)
+        );
+        assert_eq!(
+            "0x80000000",
+            write(TestU32::from_bits_retain(1 << 31), options)
+        );
+        assert_eq!(
+            "0x0000000000000100",
+            write(TestU64::from_bits_retain(1 << 8), options)
+        );
+        assert_eq!(
+            "0x00000000000000000000000000000002",
+            write(TestU128::from_bits_retain(1 << 1), options)
+        );
+        assert_eq!("0x80", write(TestI8::from_bits_retain(i8::MIN), options));
+        assert_eq!(
+            "A | 0xfffffffe",
+            write(TestI32::from_bits_retain(-1), options)
+        );
+
+        assert_eq!(8, TestFlags::bits_width());
+        assert_eq!(16, TestU16::bits_width());
+        assert_eq!(128, TestU128::bits_width());
+        assert_eq!(8, TestI8::bits_width());
+    }
+
+    fn write<F: Flags>(value: F, options: WriteOptions) -> String
+    where
+        F::Bits: crate::parser::WriteHex,
+    {
+        let mut s = String::new();
+
+        to_writer_with_options(&value, &mut s, options).unwrap();
+        s
+    }
+}
+
 mod to_writer_truncate {
     use super::*;
 
@@ -330,3 +867,415 @@ mod to_writer_strict {
         s
     }
 }
+
+mod from_str_with_options {
+    use super::*;
+
+    use crate::Flag;
+
+    #[test]
+    fn default() {
+        let options = ParseOptions::new();
+
+        assert_eq!(
+            1 | 1 << 1,
+            from_str_with_options::<TestFlags>("A | B", options)
+                .unwrap()
+                .bits()
+        );
+
+        assert!(from_str_with_options::<TestFlags>("A # B", options)
+            .unwrap_err()
+            .to_string()
+            .starts_with("unrecognized named flag"));
+        assert!(from_str_with_options::<TestFlags>("A\\|B", options)
+            .unwrap_err()
+            .to_string()
+            .starts_with("unrecognized named flag"));
+    }
+
+    #[test]
+    fn empty_is_empty() {
+        for input in ["", "   ", "\t\n"] {
+            assert_eq!(
+                TestFlags::empty(),
+                from_str_with_options::<TestFlags>(input, ParseOptions::new()).unwrap(),
+                "{:?}",
+                input
+            );
+            assert_eq!(
+                TestFlags::empty(),
+                from_str_with_options::<TestFlags>(input, ParseOptions::new().empty_is_empty(true))
+                    .unwrap(),
+                "{:?}",
+                input
+            );
+
+            assert!(
+                from_str_with_options::<TestFlags>(
+                    input,
+                    ParseOptions::new().empty_is_empty(false)
+                )
+                .unwrap_err()
+                .to_string()
+                .starts_with("encountered empty flag"),
+                "{:?}",
+                input
+            );
+        }
+
+        let options = ParseOptions::new()
+            .empty_is_empty(false)
+            .strip_line_comment('#');
+
+        assert!(from_str_with_options::<TestFlags>(" # A", options).is_err());
+        assert_eq!(
+            1,
+            from_str_with_options::<TestFlags>("A # B", options)
+                .unwrap()
+                .bits()
+        );
+    }
+
+    #[test]
+    fn comment() {
+        let options = ParseOptions::new().strip_line_comment('#');
+
+        assert_eq!(
+            0,
+            from_str_with_options::<TestFlags>("# A | B", options)
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            1,
+            from_str_with_options::<TestFlags>("A # | B", options)
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            1 | 1 << 3,
+            from_str_with_options::<TestFlags>("A | 0x8 #0x10", options)
+                .unwrap()
+                .bits()
+        );
+
+        assert!(from_str_with_options::<TestFlags>("A | # B", options)
+            .unwrap_err()
+            .to_string()
+            .starts_with("encountered empty flag"));
+    }
+
+    #[test]
+    fn escapes() {
+        let options = ParseOptions::new().escapes(true);
+
+        assert_eq!(
+            1 | 1 << 1,
+            from_str_with_options::<TestFlags>("A | B", options)
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            1 << 1,
+            from_str_with_options::<TestEscaped>("A\\|B", options)
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            1 | 1 << 1 | 1 << 2,
+            from_str_with_options::<TestEscaped>("A | A\\|B | C\\\\", options)
+                .unwrap()
+                .bits()
+        );
+
+        assert!(from_str_with_options::<TestEscaped>("A|B", options)
+            .unwrap_err()
+            .to_string()
+            .starts_with("unrecognized named flag"));
+    }
+
+    #[test]
+    fn escaped_comment() {
+        let options = ParseOptions::new().strip_line_comment('#').escapes(true);
+
+        assert_eq!(
+            1 << 3,
+            from_str_with_options::<TestEscaped>("D\\# # A", options)
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            1 << 1,
+            from_str_with_options::<TestEscaped>("A\\|B # | D\\#", options)
+                .unwrap()
+                .bits()
+        );
+    }
+
+    #[test]
+    fn quoted() {
+        let options = ParseOptions::new().allow_quoted(true);
+
+        assert_eq!(
+            1 << 1,
+            from_str_with_options::<TestEscaped>("\"A|B\"", options)
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            1 | 1 << 1 | 1 << 4,
+            from_str_with_options::<TestEscaped>(" A | \"A|B\" | \"E \\\"F\\\"\" ", options)
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            1 << 2,
+            from_str_with_options::<TestEscaped>("\"C\\\\\"", options)
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            1 | 1 << 3,
+            from_str_with_options::<TestEscaped>("\"A\" | \"D#\"", options)
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            1 << 3,
+            from_str_with_options::<TestEscaped>("\"D#\" # A", options.strip_line_comment('#'))
+                .unwrap()
+                .bits()
+        );
+
+        for invalid in ["\"A", "\"A\"B", "\"A\\\"", "\"A|B", "\"\""] {
+            assert!(
+                from_str_with_options::<TestEscaped>(invalid, options).is_err(),
+                "{}",
+                invalid
+            );
+        }
+
+        // Quotes aren't special by default
+        assert!(
+            from_str_with_options::<TestEscaped>("\"A|B\"", ParseOptions::new())
+                .unwrap_err()
+                .to_string()
+                .starts_with("unrecognized named flag")
+        );
+    }
+
+    #[test]
+    fn keywords() {
+        let options = ParseOptions::new().keywords(true);
+
+        assert_eq!(
+            1 | 1 << 1 | 1 << 2,
+            from_str_with_options::<TestFlags>("all", options)
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            0,
+            from_str_with_options::<TestFlags>("none", options)
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            1,
+            from_str_with_options::<TestFlags>(" none | A ", options)
+                .unwrap()
+                .bits()
+        );
+        assert_eq!(
+            1 | 1 << 1 | 1 << 2 | 1 << 3,
+            from_str_with_options::<TestFlags>("A | all | 0x8", options)
+                .unwrap()
+                .bits()
+        );
+
+        // Keywords are case-sensitive and can't be quoted
+        assert!(from_str_with_options::<TestFlags>("ALL", options).is_err());
+        assert!(from_str_with_options::<TestFlags>("\"all\"", options.allow_quoted(true)).is_err());
+
+        // Keywords aren't recognized by default
+        assert!(
+            from_str_with_options::<TestFlags>("all", ParseOptions::new())
+                .unwrap_err()
+                .to_string()
+                .starts_with("unrecognized named flag")
+        );
+        assert!(from_str::<TestFlags>("none").is_err());
+    }
+
+    #[test]
+    fn keywords_shadowed() {
+        let options = ParseOptions::new().keywords(true);
+
+        // Flags defined with the same names as keywords are parsed instead
+        assert_eq!(
+            TestKeywords::ALL,
+            from_str_with_options::<TestKeywords>("all", options).unwrap()
+        );
+        assert_eq!(
+            TestKeywords::NONE,
+            from_str_with_options::<TestKeywords>("none", options).unwrap()
+        );
+        assert_eq!(
+            TestKeywords(1 | 1 << 1),
+            from_str_with_options::<TestKeywords>("all | none", options).unwrap()
+        );
+    }
+
+    // Generated flags types can't define flags named `all`, because they clash with `all()`
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct TestKeywords(u8);
+
+    impl TestKeywords {
+        const ALL: Self = TestKeywords(1);
+        const NONE: Self = TestKeywords(1 << 1);
+    }
+
+    impl Flags for TestKeywords {
+        const FLAGS: &'static [Flag<Self>] = &[
+            Flag::new("all", TestKeywords::ALL),
+            Flag::new("none", TestKeywords::NONE),
+            Flag::new("C", TestKeywords(1 << 2)),
+        ];
+
+        type Bits = u8;
+
+        fn bits(&self) -> u8 {
+            self.0
+        }
+
+        fn from_bits_retain(bits: u8) -> Self {
+            TestKeywords(bits)
+        }
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct TestEscaped(u8);
+
+    impl Flags for TestEscaped {
+        const FLAGS: &'static [Flag<Self>] = &[
+            Flag::new("A", TestEscaped(1)),
+            Flag::new("A|B", TestEscaped(1 << 1)),
+            Flag::new("C\\", TestEscaped(1 << 2)),
+            Flag::new("D#", TestEscaped(1 << 3)),
+            Flag::new("E \"F\"", TestEscaped(1 << 4)),
+        ];
+
+        type Bits = u8;
+
+        fn bits(&self) -> u8 {
+            self.0
+        }
+
+        fn from_bits_retain(bits: u8) -> Self {
+            TestEscaped(bits)
+        }
+    }
+}
+
+mod overflow {
+    use super::*;
+
+    bitflags! {
+        #[derive(Debug, PartialEq, Eq)]
+        pub struct TestU16: u16 {
+            const A = 1;
+        }
+
+        #[derive(Debug, PartialEq, Eq)]
+        pub struct TestU32: u32 {
+            const A = 1;
+        }
+
+        #[derive(Debug, PartialEq, Eq)]
+        pub struct TestU64: u64 {
+            const A = 1;
+        }
+
+        #[derive(Debug, PartialEq, Eq)]
+        pub struct TestI8: i8 {
+            const A = 1;
+        }
+
+        #[derive(Debug, PartialEq, Eq)]
+        pub struct TestI32: i32 {
+            const A = 1;
+        }
+    }
+
+    #[test]
+    fn cases() {
+        assert_eq!(u8::MAX, from_str::<TestFlags>("0xff").unwrap().bits());
+        assert_overflow(from_str::<TestFlags>("0x100"));
+
+        assert_eq!(u16::MAX, from_str::<TestU16>("0xffff").unwrap().bits());
+        assert_overflow(from_str::<TestU16>("0x10000"));
+
+        assert_eq!(u32::MAX, from_str::<TestU32>("0xffffffff").unwrap().bits());
+        assert_overflow(from_str::<TestU32>("0x100000000"));
+        assert_overflow(from_str::<TestU32>("A | 0xfffffffff"));
+
+        assert_eq!(
+            u64::MAX,
+            from_str::<TestU64>("0xffffffffffffffff").unwrap().bits()
+        );
+        assert_overflow(from_str::<TestU64>("0x10000000000000000"));
+
+        // Leading zeros don't overflow
+        assert_eq!(1, from_str::<TestFlags>("0x0000000001").unwrap().bits());
+
+        // Signed hex flags are the two's complement bits of the value
+        assert_eq!(-1, from_str::<TestI8>("0xff").unwrap().bits());
+        assert_overflow(from_str::<TestI8>("0x100"));
+
+        assert_eq!(i8::MIN, from_str::<TestI8>("-128").unwrap().bits());
+        assert_overflow(from_str::<TestI8>("-129"));
+
+        assert_eq!(i32::MIN, from_str::<TestI32>("-2147483648").unwrap().bits());
+        assert_overflow(from_str::<TestI32>("-2147483649"));
+
+        assert_overflow(parse_prefix::<TestU16>("0x10000 rest").map(|(flags, _)| flags));
+    }
+
+    #[test]
+    fn invalid_digits() {
+        // Invalid digits are still reported as invalid, not as overflowing
+        assert!(from_str::<TestU32>("0xfg")
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid hex flag"));
+        assert!(from_str::<TestI8>("-1x")
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid negative flag"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn fmt() {
+        assert_eq!(
+            "flag overflows the bits type `fffffffff`",
+            from_str::<TestU32>("0xfffffffff").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "flag overflows the bits type `-129`",
+            from_str::<TestI8>("-129").unwrap_err().to_string()
+        );
+    }
+
+    #[track_caller]
+    fn assert_overflow<B: std::fmt::Debug>(result: Result<B, ParseError>) {
+        let err = result.unwrap_err();
+
+        assert!(
+            err.to_string().starts_with("flag overflows the bits type"),
+            "{}",
+            err
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use super::*;
+
+use crate::Flags;
+
+#[test]
+fn cases() {
+    case(
+        TestFlags::A | TestFlags::C,
+        TestFlags::B | TestFlags::C,
+        TestFlags::C,
+        TestFlags::present_of,
+    );
+    case(
+        TestFlags::ABC,
+        TestFlags::empty(),
+        TestFlags::empty(),
+        TestFlags::present_of,
+    );
+    case(
+        TestFlags::A,
+        TestFlags::B | TestFlags::C,
+        TestFlags::empty(),
+        TestFlags::present_of,
+    );
+    case(
+        TestFlags::A | TestFlags::from_bits_retain(1 << 3),
+        TestFlags::all() | TestFlags::from_bits_retain(1 << 3),
+        TestFlags::A | TestFlags::from_bits_retain(1 << 3),
+        TestFlags::present_of,
+    );
+
+    case(
+        TestOverlapping::AB,
+        TestOverlapping::BC,
+        TestOverlapping::from_bits_retain(1 << 1),
+        TestOverlapping::present_of,
+    );
+
+    const PRESENT: TestFlags = TestFlags::ABC.present_of(TestFlags::A.union(TestFlags::B));
+    assert_eq!(TestFlags::A | TestFlags::B, PRESENT);
+}
+
+#[track_caller]
+fn case<T: Flags + std::fmt::Debug + PartialEq + Copy>(
+    value: T,
+    group: T,
+    expected: T,
+    inherent: impl FnOnce(&T, T) -> T,
+) {
+    assert_eq!(
+        expected,
+        inherent(&value, group),
+        "{:?}.present_of({:?})",
+        value,
+        group
+    );
+    assert_eq!(
+        expected,
+        Flags::present_of(&value, group),
+        "Flags::present_of({:?}, {:?})",
+        value,
+        group
+    );
+    assert_eq!(
+        value.intersects(group),
+        !Flags::present_of(&value, group).is_empty(),
+        "{:?}.intersects({:?})",
+        value,
+        group
+    );
+}
@@ -0,0 +1,111 @@
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[bitflags(bits_ops)]
+    pub struct TestBitsOps: u8 {
+        const A = 1;
+        const B = 1 << 1;
+        const C = 1 << 2;
+        const ABC = Self::A.bits() | Self::B.bits() | Self::C.bits();
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[bitflags(bits_ops)]
+    pub struct TestBitsOpsZero: u8 {
+        const ZERO = 0;
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TestBitsOpsExternal(u8);
+
+bitflags! {
+    #[bitflags(bits_ops)]
+    impl TestBitsOpsExternal: u8 {
+        const A = 1;
+        const B = 1 << 1;
+        const C = 1 << 2;
+
+        const _ = !0;
+    }
+}
+
+#[test]
+fn cases() {
+    case(
+        TestBitsOps::A,
+        1 << 1 | 1 << 3,
+        1 | 1 << 1 | 1 << 3,
+        0,
+        1 | 1 << 1 | 1 << 3,
+        1,
+    );
+    case(
+        TestBitsOps::all(),
+        1 | 1 << 7,
+        1 | 1 << 1 | 1 << 2 | 1 << 7,
+        1,
+        1 << 1 | 1 << 2 | 1 << 7,
+        1 << 1 | 1 << 2,
+    );
+    case(
+        TestBitsOps::A | TestBitsOps::from_bits_retain(1 << 3),
+        0,
+        1 | 1 << 3,
+        0,
+        1 | 1 << 3,
+        1 | 1 << 3,
+    );
+    case(TestBitsOps::empty(), !0, !0, 0, !0, 0);
+
+    case(TestBitsOpsZero::ZERO, 1, 1, 0, 1, 0);
+
+    case(TestBitsOpsExternal::A, 1 << 5, 1 | 1 << 5, 0, 1 | 1 << 5, 1);
+}
+
+#[test]
+fn literal() {
+    assert_eq!(0x81, (TestBitsOps::A | 0x80).bits());
+    assert_eq!(0x80, ((TestBitsOps::A | 0x80) & 0x80).bits());
+    assert_eq!(0, (TestBitsOps::A ^ 0x1).bits());
+    assert_eq!(TestBitsOps::ABC - 0b110, TestBitsOps::A);
+
+    // Operators between flags values still work as before
+    assert_eq!(0b11, (TestBitsOps::A | TestBitsOps::B).bits());
+}
+
+#[track_caller]
+fn case<T>(value: T, raw: u8, or: u8, and: u8, xor: u8, sub: u8)
+where
+    T: crate::Flags<Bits = u8>
+        + std::fmt::Debug
+        + Copy
+        + std::ops::BitOr<u8, Output = T>
+        + std::ops::BitOrAssign<u8>
+        + std::ops::BitAnd<u8, Output = T>
+        + std::ops::BitAndAssign<u8>
+        + std::ops::BitXor<u8, Output = T>
+        + std::ops::BitXorAssign<u8>
+        + std::ops::Sub<u8, Output = T>
+        + std::ops::SubAssign<u8>,
+{
+    assert_eq!(or, (value | raw).bits(), "{:?} | {:?}", value, raw);
+    assert_eq!(and, (value & raw).bits(), "{:?} & {:?}", value, raw);
+    assert_eq!(xor, (value ^ raw).bits(), "{:?} ^ {:?}", value, raw);
+    assert_eq!(sub, (value - raw).bits(), "{:?} - {:?}", value, raw);
+
+    let mut assigned = value;
+    assigned |= raw;
+    assert_eq!(or, assigned.bits(), "{:?} |= {:?}", value, raw);
+
+    let mut assigned = value;
+    assigned &= raw;
+    assert_eq!(and, assigned.bits(), "{:?} &= {:?}", value, raw);
+
+    let mut assigned = value;
+    assigned ^= raw;
+    assert_eq!(xor, assigned.bits(), "{:?} ^= {:?}", value, raw);
+
+    let mut assigned = value;
+    assigned -= raw;
+    assert_eq!(sub, assigned.bits(), "{:?} -= {:?}", value, raw);
+}
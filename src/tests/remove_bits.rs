@@ -0,0 +1,66 @@
+use super::*;
+
+use crate::Flags;
+
+#[test]
+fn cases() {
+    case(
+        TestFlags::all() | TestFlags::from_bits_retain(1 << 3 | 1 << 4),
+        &[
+            (1, 1 << 1 | 1 << 2 | 1 << 3 | 1 << 4),
+            (1 << 3, 1 | 1 << 1 | 1 << 2 | 1 << 4),
+            (1 | 1 << 4, 1 << 1 | 1 << 2 | 1 << 3),
+            (0, 1 | 1 << 1 | 1 << 2 | 1 << 3 | 1 << 4),
+            (!0, 0),
+        ],
+        TestFlags::remove_bits,
+    );
+
+    case(
+        TestFlags::A,
+        &[(1 << 1, 1), (1 << 7, 1)],
+        TestFlags::remove_bits,
+    );
+
+    case(
+        TestZero::from_bits_retain(1 << 7),
+        &[(1 << 7, 0), (1, 1 << 7)],
+        TestZero::remove_bits,
+    );
+}
+
+#[track_caller]
+fn case<T: Flags + std::fmt::Debug + Copy>(
+    value: T,
+    inputs: &[(T::Bits, T::Bits)],
+    mut inherent: impl FnMut(&mut T, T::Bits),
+) where
+    T::Bits: std::fmt::Debug + PartialEq + Copy,
+{
+    for (input, expected) in inputs {
+        assert_eq!(
+            *expected,
+            {
+                let mut value = value;
+                inherent(&mut value, *input);
+                value
+            }
+            .bits(),
+            "{:?}.remove_bits({:?})",
+            value,
+            input
+        );
+        assert_eq!(
+            *expected,
+            {
+                let mut value = value;
+                Flags::remove(&mut value, T::from_bits_retain(*input));
+                value
+            }
+            .bits(),
+            "Flags::remove({:?}, from_bits_retain({:?}))",
+            value,
+            input
+        );
+    }
+}
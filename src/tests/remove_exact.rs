@@ -0,0 +1,108 @@
+use super::*;
+
+use crate::{convert::NotPresent, Flags};
+
+#[test]
+fn cases() {
+    case(TestFlags::ABC, TestFlags::A, Ok(1 << 1 | 1 << 2));
+    case(TestFlags::ABC, TestFlags::ABC, Ok(0));
+    case(TestFlags::A, TestFlags::empty(), Ok(1));
+    case(TestFlags::empty(), TestFlags::empty(), Ok(0));
+    case(
+        TestFlags::A | TestFlags::from_bits_retain(1 << 3),
+        TestFlags::from_bits_retain(1 << 3),
+        Ok(1),
+    );
+
+    case(TestFlags::empty(), TestFlags::A, Err(NotPresent::new()));
+    case(
+        TestFlags::A,
+        TestFlags::A | TestFlags::B,
+        Err(NotPresent::new()),
+    );
+    case(
+        TestFlags::A,
+        TestFlags::from_bits_retain(1 << 3),
+        Err(NotPresent::new()),
+    );
+
+    case(
+        TestOverlapping::AB,
+        TestOverlapping::BC,
+        Err(NotPresent::new()),
+    );
+    case(
+        TestOverlapping::AB | TestOverlapping::BC,
+        TestOverlapping::BC,
+        Ok(1),
+    );
+
+    assert_eq!(
+        "the flags to remove aren't all set",
+        NotPresent::new().to_string()
+    );
+}
+
+#[track_caller]
+fn case<T: Flags + std::fmt::Debug + Copy + RemoveExact>(
+    value: T,
+    other: T,
+    expected: Result<T::Bits, NotPresent>,
+) where
+    T::Bits: std::fmt::Debug + PartialEq + Copy,
+{
+    // On error, the value is left unchanged
+    let expected_bits = match expected {
+        Ok(bits) => bits,
+        Err(_) => value.bits(),
+    };
+    let expected = expected.map(|_| ());
+
+    let mut inherent = value;
+    assert_eq!(
+        expected,
+        inherent.inherent_remove_exact(other),
+        "{:?}.remove_exact({:?})",
+        value,
+        other
+    );
+    assert_eq!(
+        expected_bits,
+        inherent.bits(),
+        "{:?}.remove_exact({:?})",
+        value,
+        other
+    );
+
+    let mut trait_value = value;
+    assert_eq!(
+        expected,
+        Flags::remove_exact(&mut trait_value, other),
+        "Flags::remove_exact({:?}, {:?})",
+        value,
+        other
+    );
+    assert_eq!(
+        expected_bits,
+        trait_value.bits(),
+        "Flags::remove_exact({:?}, {:?})",
+        value,
+        other
+    );
+}
+
+trait RemoveExact: Sized {
+    fn inherent_remove_exact(&mut self, other: Self) -> Result<(), NotPresent>;
+}
+
+impl RemoveExact for TestFlags {
+    fn inherent_remove_exact(&mut self, other: Self) -> Result<(), NotPresent> {
+        self.remove_exact(other)
+    }
+}
+
+impl RemoveExact for TestOverlapping {
+    fn inherent_remove_exact(&mut self, other: Self) -> Result<(), NotPresent> {
+        self.remove_exact(other)
+    }
+}
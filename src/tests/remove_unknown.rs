@@ -0,0 +1,34 @@
+use super::*;
+
+use crate::Flags;
+
+#[test]
+fn cases() {
+    case(
+        TestFlags::ABC | TestFlags::from_bits_retain(1 << 3),
+        TestFlags::ABC,
+    );
+
+    case(
+        TestFlags::B | TestFlags::from_bits_retain(1 << 3 | 1 << 7),
+        TestFlags::B,
+    );
+
+    case(TestFlags::from_bits_retain(1 << 4), TestFlags::empty());
+
+    case(TestFlags::empty(), TestFlags::empty());
+
+    case(TestZero::empty(), TestZero::empty());
+
+    case(TestExternal::all(), TestExternal::all());
+}
+
+#[track_caller]
+fn case<T>(mut before: T, after: T)
+where
+    T: Flags + std::fmt::Debug + PartialEq + Copy,
+{
+    let value = before;
+    before.remove_unknown();
+    assert_eq!(before, after, "{:?}.remove_unknown()", value);
+}
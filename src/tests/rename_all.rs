@@ -0,0 +1,164 @@
+use super::*;
+
+use crate::{parser, parser::RenameAll, Flags};
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[bitflags(rename_all = "lowercase")]
+    pub struct TestLowercase: u8 {
+        const READ_WRITE = 1;
+        const EXECUTE = 1 << 1;
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[bitflags(rename_all = "kebab-case")]
+    pub struct TestKebabCase: u8 {
+        const READ_WRITE = 1;
+        const EXECUTE = 1 << 1;
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TestKebabCaseImpl(u8);
+
+bitflags! {
+    #[bitflags(rename_all = "kebab-case")]
+    impl TestKebabCaseImpl: u8 {
+        const READ_WRITE = 1;
+    }
+}
+
+bitflags! {
+    #[derive(Debug)]
+    #[bitflags(debug = "set", rename_all = "kebab-case")]
+    pub struct TestKebabCaseDebugSet: u8 {
+        const READ_WRITE = 1;
+        const EXECUTE = 1 << 1;
+    }
+}
+
+#[test]
+fn rename_all() {
+    assert_eq!(RenameAll::Verbatim, <TestFlags as Flags>::RENAME_ALL);
+    assert_eq!(RenameAll::Lowercase, <TestLowercase as Flags>::RENAME_ALL);
+    assert_eq!(RenameAll::KebabCase, <TestKebabCase as Flags>::RENAME_ALL);
+    assert_eq!(
+        RenameAll::KebabCase,
+        <TestKebabCaseImpl as Flags>::RENAME_ALL
+    );
+}
+
+#[test]
+fn lowercase() {
+    roundtrip(TestLowercase::empty(), "");
+    roundtrip(TestLowercase::READ_WRITE, "read_write");
+    roundtrip(TestLowercase::all(), "read_write | execute");
+    roundtrip(
+        TestLowercase::EXECUTE | TestLowercase::from_bits_retain(1 << 7),
+        "execute | 0x80",
+    );
+
+    assert_eq!(
+        TestLowercase::all(),
+        parser::from_str::<TestLowercase>("READ_WRITE | execute").unwrap()
+    );
+    assert!(parser::from_str::<TestLowercase>("read-write").is_err());
+}
+
+#[test]
+fn kebab_case() {
+    roundtrip(TestKebabCase::READ_WRITE, "read-write");
+    roundtrip(TestKebabCase::all(), "read-write | execute");
+    roundtrip(TestKebabCaseImpl::READ_WRITE, "read-write");
+
+    assert_eq!(
+        TestKebabCase::all(),
+        parser::from_str::<TestKebabCase>("READ_WRITE | execute").unwrap()
+    );
+    assert!(parser::from_str::<TestKebabCase>("read_write").is_err());
+
+    assert_eq!(
+        (TestKebabCase::all(), "; rest"),
+        parser::parse_prefix::<TestKebabCase>("read-write | execute; rest").unwrap()
+    );
+    assert_eq!(
+        TestKebabCase::READ_WRITE,
+        parser::from_str_with_options::<TestKebabCase>(
+            "\"read-write\"",
+            parser::ParseOptions::new().allow_quoted(true)
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn debug() {
+    assert_eq!(
+        "TestKebabCase(read-write | execute)",
+        format!("{:?}", TestKebabCase::all())
+    );
+    assert_eq!(
+        "TestKebabCaseDebugSet({read-write, execute, 0x80})",
+        format!(
+            "{:?}",
+            TestKebabCaseDebugSet::all() | TestKebabCaseDebugSet::from_bits_retain(1 << 7)
+        )
+    );
+}
+
+#[test]
+fn from_name() {
+    assert_eq!(
+        Some(TestLowercase::READ_WRITE),
+        TestLowercase::from_name("read_write")
+    );
+    assert_eq!(
+        Some(TestLowercase::READ_WRITE),
+        TestLowercase::from_name("READ_WRITE")
+    );
+    assert_eq!(None, TestLowercase::from_name("Read_Write"));
+
+    assert_eq!(
+        Some(TestKebabCase::READ_WRITE),
+        TestKebabCase::from_name("read-write")
+    );
+    assert_eq!(
+        Some(TestKebabCase::READ_WRITE),
+        <TestKebabCase as Flags>::from_name("read-write")
+    );
+    assert_eq!(
+        Some(TestKebabCaseImpl::READ_WRITE),
+        TestKebabCaseImpl::from_name("READ_WRITE")
+    );
+    assert_eq!(
+        Some(TestKebabCaseImpl::READ_WRITE),
+        TestKebabCaseImpl::from_name("read-write")
+    );
+    assert_eq!(None, TestKebabCase::from_name(""));
+}
+
+#[test]
+fn names_unchanged() {
+    assert_eq!("READ_WRITE", TestKebabCase::FLAGS[0].name());
+    assert_eq!(
+        Some("READ_WRITE"),
+        TestKebabCase::READ_WRITE
+            .iter_names()
+            .next()
+            .map(|(name, _)| name)
+    );
+}
+
+#[track_caller]
+fn roundtrip<T: Flags<Bits = u8> + std::fmt::Debug + PartialEq>(value: T, expected: &str) {
+    let mut text = String::new();
+    parser::to_writer(&value, &mut text).unwrap();
+
+    assert_eq!(expected, text, "to_writer({:?})", value);
+    assert_eq!(
+        value,
+        parser::from_str::<T>(&text).unwrap(),
+        "from_str({:?})",
+        text
+    );
+}
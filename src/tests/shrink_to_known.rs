@@ -0,0 +1,45 @@
+use super::*;
+
+use crate::{BitsCount, Flags};
+
+#[test]
+fn cases() {
+    case(0, TestFlags::empty(), TestFlags::empty());
+    case(0, TestFlags::ABC, TestFlags::ABC);
+
+    case(1, TestFlags::from_bits_retain(1 << 3), TestFlags::empty());
+    case(
+        1,
+        TestFlags::A | TestFlags::from_bits_retain(1 << 7),
+        TestFlags::A,
+    );
+
+    case(
+        5,
+        TestFlags::all() | TestFlags::from_bits_retain(0b1111_1000),
+        TestFlags::all(),
+    );
+    case(5, TestFlags::from_bits_retain(!0), TestFlags::all());
+
+    case(0, TestZero::ZERO, TestZero::ZERO);
+    case(8, TestZero::from_bits_retain(!0), TestZero::empty());
+
+    case(0, TestExternal::from_bits_retain(!0), TestExternal::all());
+}
+
+#[track_caller]
+fn case<T>(expected: u32, mut value: T, after: T)
+where
+    T: Flags + std::fmt::Debug + PartialEq + Copy,
+    T::Bits: BitsCount,
+{
+    let before = value;
+
+    assert_eq!(
+        expected,
+        value.shrink_to_known(),
+        "{:?}.shrink_to_known()",
+        before
+    );
+    assert_eq!(after, value, "{:?}.shrink_to_known()", before);
+}
@@ -0,0 +1,92 @@
+use std::cmp::Ordering;
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[bitflags(subset_ord)]
+    pub struct TestSubsetOrd: u8 {
+        const A = 1;
+        const B = 1 << 1;
+        const C = 1 << 2;
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TestSubsetOrdImpl(u8);
+
+bitflags! {
+    #[bitflags(subset_ord)]
+    impl TestSubsetOrdImpl: u8 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+}
+
+#[test]
+fn cases() {
+    case(
+        Some(Ordering::Equal),
+        TestSubsetOrd::empty(),
+        TestSubsetOrd::empty(),
+    );
+    case(Some(Ordering::Equal), TestSubsetOrd::A, TestSubsetOrd::A);
+
+    case(
+        Some(Ordering::Less),
+        TestSubsetOrd::empty(),
+        TestSubsetOrd::A,
+    );
+    case(
+        Some(Ordering::Less),
+        TestSubsetOrd::A,
+        TestSubsetOrd::A | TestSubsetOrd::B,
+    );
+    case(
+        Some(Ordering::Greater),
+        TestSubsetOrd::all(),
+        TestSubsetOrd::C,
+    );
+
+    // Incomparable sets
+    case(None, TestSubsetOrd::A, TestSubsetOrd::B);
+    case(
+        None,
+        TestSubsetOrd::A | TestSubsetOrd::B,
+        TestSubsetOrd::B | TestSubsetOrd::C,
+    );
+
+    // Unknown bits are compared too
+    case(
+        Some(Ordering::Less),
+        TestSubsetOrd::A,
+        TestSubsetOrd::A | TestSubsetOrd::from_bits_retain(1 << 7),
+    );
+    case(
+        None,
+        TestSubsetOrd::all(),
+        TestSubsetOrd::from_bits_retain(1 << 7),
+    );
+
+    assert_eq!(
+        Some(Ordering::Less),
+        TestSubsetOrdImpl::A.partial_cmp(&TestSubsetOrdImpl::all())
+    );
+    assert_eq!(
+        None,
+        TestSubsetOrdImpl::A.partial_cmp(&TestSubsetOrdImpl::B)
+    );
+}
+
+#[track_caller]
+fn case(expected: Option<Ordering>, a: TestSubsetOrd, b: TestSubsetOrd) {
+    assert_eq!(expected, a.partial_cmp(&b), "{:?}.partial_cmp({:?})", a, b);
+    assert_eq!(
+        expected.map(Ordering::reverse),
+        b.partial_cmp(&a),
+        "{:?}.partial_cmp({:?})",
+        b,
+        a
+    );
+
+    assert_eq!(b.contains(a), a <= b, "{:?} <= {:?}", a, b);
+    assert_eq!(a.contains(b), a >= b, "{:?} >= {:?}", a, b);
+}
@@ -0,0 +1,60 @@
+use super::*;
+
+use crate::Flags;
+
+#[test]
+fn cases() {
+    case("", "", TestFlags::empty());
+    case("A", "A", TestFlags::A);
+    case("A | B | C", "A | B | C", TestFlags::all());
+    case("0x8", "", TestFlags::from_bits_retain(1 << 3));
+    case(
+        "A | 0x8",
+        "A",
+        TestFlags::A | TestFlags::from_bits_retain(1 << 3),
+    );
+
+    case("", "", TestZero::ZERO);
+
+    case("ABC", "ABC", TestFlagsInvert::all());
+
+    case(
+        "A | 0xf8",
+        "A | 0xf8",
+        TestExternal::from_bits_retain(!0) - TestExternal::B - TestExternal::C,
+    );
+}
+
+#[test]
+fn display_to_string() {
+    bitflags! {
+        #[derive(Debug)]
+        pub struct TestDisplay: u8 {
+            const A = 1;
+        }
+    }
+
+    impl std::fmt::Display for TestDisplay {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            crate::parser::to_writer(self, f)
+        }
+    }
+
+    // `ToString` isn't ambiguous with `Flags` in scope
+    assert_eq!("A", TestDisplay::A.to_string());
+    assert_eq!("A", TestDisplay::A.to_text());
+}
+
+#[track_caller]
+fn case<T: Flags + std::fmt::Debug>(expected: &str, expected_truncate: &str, value: T)
+where
+    T::Bits: crate::parser::WriteHex,
+{
+    assert_eq!(expected, value.to_text(), "{:?}.to_text()", value);
+    assert_eq!(
+        expected_truncate,
+        value.to_text_truncate(),
+        "{:?}.to_text_truncate()",
+        value
+    );
+}
@@ -0,0 +1,55 @@
+use crate::TransparentFlags;
+
+bitflags! {
+    /// A flags type with a doc comment before its `repr`.
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[repr(transparent)]
+    pub struct TestTransparent: u16 {
+        const A = 1;
+        const B = 1 << 1;
+        const C = 1 << 8;
+    }
+}
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[bitflags(transparent)]
+    pub struct TestTransparentOption: u32 {
+        const A = 1;
+        const B = 1 << 31;
+    }
+}
+
+#[test]
+fn cases() {
+    case(TestTransparentOption::A | TestTransparentOption::B);
+    case(TestTransparentOption::from_bits_retain(1 << 7));
+
+    case(TestTransparent::empty());
+    case(TestTransparent::A | TestTransparent::C);
+    case(TestTransparent::all());
+    case(TestTransparent::from_bits_retain(1 << 15));
+}
+
+#[track_caller]
+fn case<T: TransparentFlags + std::fmt::Debug + PartialEq + Copy>(value: T)
+where
+    T::Bits: std::fmt::Debug + PartialEq,
+{
+    assert_eq!(
+        core::mem::size_of::<T::Bits>(),
+        core::mem::size_of::<T>(),
+        "size_of {:?}",
+        value
+    );
+    assert_eq!(
+        core::mem::align_of::<T::Bits>(),
+        core::mem::align_of::<T>(),
+        "align_of {:?}",
+        value
+    );
+
+    // SAFETY: `T` has the same ABI as `T::Bits`
+    let bits = unsafe { *(&value as *const T).cast::<T::Bits>() };
+    assert_eq!(value.bits(), bits, "{:?} as bits", value);
+}
@@ -7,23 +7,34 @@ fn cases() {
     case(
         TestFlags::ABC | TestFlags::from_bits_retain(1 << 3),
         TestFlags::ABC,
+        TestFlags::truncated,
     );
 
-    case(TestZero::empty(), TestZero::empty());
+    case(TestZero::empty(), TestZero::empty(), TestZero::truncated);
 
-    case(TestZero::all(), TestZero::all());
+    case(TestZero::all(), TestZero::all(), TestZero::truncated);
 
     case(
         TestFlags::from_bits_retain(1 << 3) | TestFlags::all(),
         TestFlags::all(),
+        TestFlags::truncated,
+    );
+
+    case(
+        TestExternal::from_bits_retain(1 << 3 | 1 << 7),
+        TestExternal::from_bits_retain(1 << 3 | 1 << 7),
+        TestExternal::truncated,
     );
 }
 
 #[track_caller]
-fn case<T: Flags + std::fmt::Debug>(mut before: T, after: T)
+fn case<T: Flags + std::fmt::Debug>(before: T, after: T, inherent: impl FnOnce(T) -> T)
 where
     T: std::fmt::Debug + PartialEq + Copy,
 {
-    before.truncate();
-    assert_eq!(before, after, "{:?}.truncate()", before);
+    assert_eq!(after, inherent(before), "{:?}.truncated()", before);
+
+    let mut truncated = before;
+    truncated.truncate();
+    assert_eq!(after, truncated, "{:?}.truncate()", before);
 }
@@ -23,6 +23,7 @@ fn cases() {
 
     case(false, TestZero::empty(), TestZero::contains_unknown_bits);
 }
+
 #[track_caller]
 fn case<T: Flags + std::fmt::Debug>(expected: bool, value: T, inherent: impl FnOnce(&T) -> bool) {
     assert_eq!(
@@ -38,3 +39,27 @@ fn case<T: Flags + std::fmt::Debug>(expected: bool, value: T, inherent: impl FnO
         value
     );
 }
+
+#[test]
+fn generic_stray_high_bit() {
+    assert_eq!(None, validate::<TestFlags>(1 | 1 << 7));
+    assert_eq!(None, validate::<TestZero>(1 << 7));
+
+    assert_eq!(Some(TestFlags::A), validate::<TestFlags>(1));
+    // Bits covered by an unnamed flag aren't unknown
+    assert_eq!(
+        Some(TestExternal::from_bits_retain(1 << 7)),
+        validate::<TestExternal>(1 << 7)
+    );
+}
+
+// Reject any values with reserved bits, using only the `Flags` trait
+fn validate<F: Flags + std::fmt::Debug>(bits: F::Bits) -> Option<F> {
+    let value = F::from_bits_retain(bits);
+
+    if value.contains_unknown_bits() {
+        None
+    } else {
+        Some(value)
+    }
+}
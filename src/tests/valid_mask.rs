@@ -0,0 +1,50 @@
+use crate::Flags;
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[bitflags(valid_mask = 0xFF)]
+    pub struct TestValidMask: u32 {
+        const A = 1;
+        const B = 1 << 1;
+        const HIGH = 1 << 7;
+        const AB = Self::A.bits() | Self::B.bits();
+
+        const _ = 0xFF;
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[bitflags(valid_mask = i8::MIN | 1, disjoint)]
+    pub struct TestValidMaskSigned: i8 {
+        const A = 1;
+        const MIN = i8::MIN;
+    }
+}
+
+const LOW: u16 = 0x0F;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TestValidMaskImpl(u16);
+
+bitflags! {
+    #[bitflags(valid_mask = LOW << 4 | LOW)]
+    impl TestValidMaskImpl: u16 {
+        const A = 1;
+        const B = 1 << 7;
+        const ZERO = 0;
+    }
+}
+
+#[test]
+fn cases() {
+    assert_eq!(0xFF, TestValidMask::all().bits());
+    assert_eq!(TestValidMask::A | TestValidMask::B, TestValidMask::AB);
+    assert_eq!(5, TestValidMask::FLAGS.len());
+
+    assert_eq!(1 | i8::MIN, TestValidMaskSigned::all().bits());
+
+    assert_eq!(3, TestValidMaskImpl::FLAGS.len());
+    assert_eq!(
+        TestValidMaskImpl::A | TestValidMaskImpl::B,
+        TestValidMaskImpl::all()
+    );
+}
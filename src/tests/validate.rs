@@ -0,0 +1,101 @@
+use crate::convert::UnknownBits;
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[bitflags(validate)]
+    pub struct TestValidate: u8 {
+        const A = 1;
+        const B = 1 << 1;
+        const C = 1 << 2;
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[bitflags(validate)]
+    pub struct TestValidateSigned: i8 {
+        const MIN = i8::MIN;
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[bitflags(validate)]
+    pub struct TestValidateExternal: u8 {
+        const A = 1;
+
+        const _ = !0;
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TestValidateImpl(u8);
+
+bitflags! {
+    #[bitflags(validate)]
+    impl TestValidateImpl: u8 {
+        const A = 1;
+    }
+}
+
+#[test]
+fn cases() {
+    case(None, TestValidate::empty());
+    case(None, TestValidate::all());
+    case(None, TestValidate::from_bits_retain(1 | 1 << 2));
+    case(Some(1 << 3), TestValidate::from_bits_retain(1 << 3));
+    case(
+        Some(1 << 3 | 1 << 7),
+        TestValidate::A | TestValidate::from_bits_retain(1 << 3 | 1 << 7),
+    );
+
+    assert_eq!(Ok(()), TestValidateSigned::MIN.validate());
+    assert_eq!(
+        Err(UnknownBits::new(1)),
+        TestValidateSigned::from_bits_retain(i8::MIN | 1).validate()
+    );
+
+    assert_eq!(
+        Ok(()),
+        TestValidateExternal::from_bits_retain(!0).validate()
+    );
+
+    assert_eq!(Ok(()), TestValidateImpl::A.validate());
+    assert_eq!(
+        Err(UnknownBits::new(1 << 1)),
+        TestValidateImpl::from_bits_retain(1 << 1).validate()
+    );
+}
+
+#[test]
+fn const_validate() {
+    const VALID: bool = TestValidate::A.validate().is_ok();
+    const INVALID: bool = TestValidate::from_bits_retain(1 << 7).validate().is_ok();
+
+    assert!(VALID);
+    assert!(!INVALID);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn fmt() {
+    assert_eq!(
+        "the flags value contains unknown bits `0x88`",
+        TestValidate::from_bits_retain(1 << 3 | 1 << 7)
+            .validate()
+            .unwrap_err()
+            .to_string()
+    );
+}
+
+#[track_caller]
+fn case(expected: Option<u8>, value: TestValidate) {
+    assert_eq!(
+        expected,
+        value.validate().err().map(UnknownBits::into_bits),
+        "{:?}.validate()",
+        value
+    );
+    assert_eq!(
+        expected.is_some(),
+        crate::Flags::contains_unknown_bits(&value),
+        "Flags::contains_unknown_bits({:?})",
+        value
+    );
+}
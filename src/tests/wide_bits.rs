@@ -0,0 +1,196 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    ops::{BitAnd, BitOr, BitXor, Not},
+};
+
+use crate::{
+    parser::{self, WriteHex},
+    Bits, Flag, Flags,
+};
+
+// A 256-bit wide bits type backed by an array
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct WideBits([u64; 4]);
+
+impl WideBits {
+    const fn bit(index: usize) -> Self {
+        let mut words = [0; 4];
+        words[index / 64] = 1 << (index % 64);
+
+        WideBits(words)
+    }
+}
+
+impl Bits for WideBits {
+    const EMPTY: Self = WideBits([0; 4]);
+    const ALL: Self = WideBits([!0; 4]);
+    const BITS: u32 = 256;
+}
+
+impl WriteHex for WideBits {
+    fn write_hex<W: fmt::Write>(&self, mut writer: W) -> fmt::Result {
+        let mut words = self.0.iter().rev().skip_while(|word| **word == 0);
+
+        match words.next() {
+            Some(word) => write!(writer, "{:x}", word)?,
+            None => return write!(writer, "0"),
+        }
+
+        for word in words {
+            write!(writer, "{:016x}", word)?;
+        }
+
+        Ok(())
+    }
+}
+
+macro_rules! impl_wide_op {
+    ($Op:ident :: $op:ident) => {
+        impl $Op for WideBits {
+            type Output = Self;
+
+            fn $op(self, other: Self) -> Self {
+                let mut words = self.0;
+                for (word, other) in words.iter_mut().zip(other.0) {
+                    *word = $Op::$op(*word, other);
+                }
+
+                WideBits(words)
+            }
+        }
+    };
+}
+
+impl_wide_op!(BitAnd::bitand);
+impl_wide_op!(BitOr::bitor);
+impl_wide_op!(BitXor::bitxor);
+
+impl Not for WideBits {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        WideBits([!self.0[0], !self.0[1], !self.0[2], !self.0[3]])
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct WideFlags(WideBits);
+
+impl WideFlags {
+    const A: Self = WideFlags(WideBits::bit(0));
+    const B: Self = WideFlags(WideBits::bit(100));
+    const C: Self = WideFlags(WideBits::bit(255));
+}
+
+impl Flags for WideFlags {
+    const FLAGS: &'static [Flag<Self>] = &[
+        Flag::new("A", Self::A),
+        Flag::new("B", Self::B),
+        Flag::new("C", Self::C),
+    ];
+
+    type Bits = WideBits;
+
+    fn bits(&self) -> WideBits {
+        self.0
+    }
+
+    fn from_bits_retain(bits: WideBits) -> Self {
+        WideFlags(bits)
+    }
+}
+
+// Format the same way as the `Debug` impl on generated flags types
+impl fmt::Debug for WideFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("WideFlags(")?;
+
+        if self.is_empty() {
+            write!(f, "{:#x}", 0)?;
+        } else {
+            parser::to_writer(self, &mut *f)?;
+        }
+
+        f.write_str(")")
+    }
+}
+
+#[test]
+fn cases() {
+    case("WideFlags(0x0)", WideFlags::empty());
+    case("WideFlags(A)", WideFlags::A);
+    case("WideFlags(A | B | C)", WideFlags::all());
+    case(
+        "WideFlags(B | 0x10000000000000000)",
+        WideFlags::B.union(WideFlags::from_bits_retain(WideBits::bit(64))),
+    );
+    case(
+        "WideFlags(0x2000000000000000000000000000000000000000000000000000000000000000)",
+        WideFlags::from_bits_retain(WideBits::bit(253)),
+    );
+}
+
+#[test]
+fn pad_hex() {
+    let mut text = String::new();
+    parser::to_writer_with_options(
+        &WideFlags::from_bits_retain(WideBits::bit(64)),
+        &mut text,
+        parser::WriteOptions::new().pad_hex(true),
+    )
+    .unwrap();
+
+    assert_eq!(
+        format!("0x{:0>64}", "10000000000000000"),
+        text,
+        "hex numbers are padded to `Bits::BITS`"
+    );
+}
+
+#[test]
+fn iter() {
+    assert_eq!(
+        vec![WideFlags::A, WideFlags::C],
+        WideFlags::A.union(WideFlags::C).iter().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec!["B", "C"],
+        WideFlags::B
+            .union(WideFlags::C)
+            .iter_names()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn hash() {
+    let mut map = HashMap::new();
+
+    map.insert(WideFlags::A, "A");
+    map.insert(WideFlags::B, "B");
+    map.insert(WideFlags::A.union(WideFlags::C), "A | C");
+
+    // Values that only differ in a high word are still distinct keys
+    map.insert(WideFlags::from_bits_retain(WideBits::bit(192)), "192");
+    map.insert(WideFlags::from_bits_retain(WideBits::bit(193)), "193");
+
+    assert_eq!(5, map.len());
+    assert_eq!(Some(&"A"), map.get(&WideFlags::A));
+    assert_eq!(Some(&"A | C"), map.get(&WideFlags::C.union(WideFlags::A)));
+    assert_eq!(
+        Some(&"193"),
+        map.get(&WideFlags::from_bits_retain(WideBits::bit(193)))
+    );
+    assert_eq!(None, map.get(&WideFlags::all()));
+
+    map.insert(WideFlags::A, "A again");
+    assert_eq!(5, map.len());
+    assert_eq!(Some(&"A again"), map.get(&WideFlags::A));
+}
+
+#[track_caller]
+fn case(expected: &str, value: WideFlags) {
+    assert_eq!(expected, format!("{:?}", value));
+}
@@ -4,8 +4,9 @@ use core::{
 };
 
 use crate::{
+    convert::{NotPresent, UnknownBits},
     iter,
-    parser::{ParseError, ParseHex, WriteHex},
+    parser::{ParseError, ParseHex, RenameAll, WriteHex},
 };
 
 /**
@@ -15,6 +16,8 @@ A defined flags value that may be named or unnamed.
 pub struct Flag<B> {
     name: &'static str,
     value: B,
+    doc: Option<&'static str>,
+    group: Option<&'static str>,
 }
 
 impl<B> Flag<B> {
@@ -24,7 +27,42 @@ impl<B> Flag<B> {
     If `name` is non-empty then the flag is named, otherwise it's unnamed.
     */
     pub const fn new(name: &'static str, value: B) -> Self {
-        Flag { name, value }
+        Flag {
+            name,
+            value,
+            doc: None,
+            group: None,
+        }
+    }
+
+    /**
+    Define a flag with documentation.
+
+    If `name` is non-empty then the flag is named, otherwise it's unnamed.
+    */
+    pub const fn with_doc(name: &'static str, value: B, doc: &'static str) -> Self {
+        Flag {
+            name,
+            value,
+            doc: Some(doc),
+            group: None,
+        }
+    }
+
+    // Used by the `bitflags` macro
+    #[doc(hidden)]
+    pub const fn __private_const_new(
+        name: &'static str,
+        value: B,
+        doc: Option<&'static str>,
+        group: Option<&'static str>,
+    ) -> Self {
+        Flag {
+            name,
+            value,
+            doc,
+            group,
+        }
     }
 
     /**
@@ -43,6 +81,57 @@ impl<B> Flag<B> {
         &self.value
     }
 
+    /**
+    Get the documentation of this flag.
+
+    Flags declared in the [`bitflags`](macro.bitflags.html) macro with the `docs` option are
+    documented using their doc comments. Each line of a doc comment is included exactly as written,
+    so lines will usually start with a space. Multiple lines are joined with `\n`.
+
+    ```
+    # use bitflags::{bitflags, Flags};
+    bitflags! {
+        #[bitflags(docs)]
+        struct MyFlags: u8 {
+            /// The `A` flag.
+            const A = 1;
+            const B = 1 << 1;
+        }
+    }
+
+    assert_eq!(Some(" The `A` flag."), MyFlags::FLAGS[0].doc());
+    assert_eq!(None, MyFlags::FLAGS[1].doc());
+    ```
+    */
+    pub const fn doc(&self) -> Option<&'static str> {
+        self.doc
+    }
+
+    /**
+    Get the group of this flag.
+
+    Flags declared in the [`bitflags`](macro.bitflags.html) macro can be given a group
+    using a `#[bitflags(group = "..")]` attribute. Groups don't change the flags value,
+    but can be used by tooling to organize flags, like in help output.
+
+    ```
+    # use bitflags::{bitflags, Flags};
+    bitflags! {
+        struct MyFlags: u8 {
+            #[bitflags(group = "status")]
+            const READY = 1;
+            const B = 1 << 1;
+        }
+    }
+
+    assert_eq!(Some("status"), MyFlags::FLAGS[0].group());
+    assert_eq!(None, MyFlags::FLAGS[1].group());
+    ```
+    */
+    pub const fn group(&self) -> Option<&'static str> {
+        self.group
+    }
+
     /**
     Whether the flag is named.
 
@@ -62,6 +151,106 @@ impl<B> Flag<B> {
     }
 }
 
+impl<B: Flags> Flag<B> {
+    /**
+    Whether the flag has no bits set.
+
+    ```
+    # use bitflags::{bitflags, Flags};
+    bitflags! {
+        struct MyFlags: u8 {
+            const NONE = 0;
+            const A = 1;
+        }
+    }
+
+    assert!(MyFlags::FLAGS[0].is_zero());
+    assert!(!MyFlags::FLAGS[1].is_zero());
+    ```
+    */
+    pub fn is_zero(&self) -> bool {
+        self.value.bits() == B::Bits::EMPTY
+    }
+}
+
+impl<B: Flags> Flag<B>
+where
+    B::Bits: BitsCount,
+{
+    /**
+    Whether the flag has exactly one bit set.
+
+    ```
+    # use bitflags::{bitflags, Flags};
+    bitflags! {
+        struct MyFlags: u8 {
+            const A = 1;
+            const BC = 1 << 1 | 1 << 2;
+        }
+    }
+
+    assert!(MyFlags::FLAGS[0].is_single());
+    assert!(!MyFlags::FLAGS[1].is_single());
+    ```
+    */
+    pub fn is_single(&self) -> bool {
+        BitsCount::count_ones(self.value.bits()) == 1
+    }
+
+    /**
+    Whether the flag has more than one bit set.
+
+    ```
+    # use bitflags::{bitflags, Flags};
+    bitflags! {
+        struct MyFlags: u8 {
+            const A = 1;
+            const BC = 1 << 1 | 1 << 2;
+        }
+    }
+
+    assert!(!MyFlags::FLAGS[0].is_composite());
+    assert!(MyFlags::FLAGS[1].is_composite());
+    ```
+    */
+    pub fn is_composite(&self) -> bool {
+        BitsCount::count_ones(self.value.bits()) > 1
+    }
+
+    /**
+    Get the named single-bit flags whose bits are set in this flag.
+
+    For a composite flag, this yields the defined flags it's made up of, in the order they're
+    declared. Bits in the flag that don't correspond to any named single-bit flag are skipped,
+    so the union of the components may not cover every bit of the flag.
+    A single-bit flag yields itself, and a flag with no bits set yields nothing.
+
+    ```
+    # use bitflags::{bitflags, Flags};
+    bitflags! {
+        struct MyFlags: u8 {
+            const A = 1;
+            const B = 1 << 1;
+            const C = 1 << 2;
+            const ABC = Self::A.bits() | Self::B.bits() | Self::C.bits();
+        }
+    }
+
+    let names = MyFlags::FLAGS[3]
+        .components()
+        .map(|flag| flag.name())
+        .collect::<Vec<_>>();
+
+    assert_eq!(vec!["A", "B", "C"], names);
+    ```
+    */
+    pub fn components(&self) -> impl Iterator<Item = &'static Flag<B>> + '_ {
+        B::FLAGS.iter().filter(move |flag| {
+            flag.is_named() && flag.is_single() && self.value.contains_ref(&flag.value)
+        })
+    }
+}
+
 /**
 A set of defined flags using a bits type as storage.
 
@@ -133,6 +322,12 @@ pub trait Flags: Sized + 'static {
     /// The set of defined flags.
     const FLAGS: &'static [Flag<Self>];
 
+    /// The case transform applied to flag names in the text format.
+    ///
+    /// Names are used verbatim by default. The `bitflags!` macro sets this through the
+    /// `rename_all` option.
+    const RENAME_ALL: RenameAll = RenameAll::Verbatim;
+
     /// The underlying bits type.
     type Bits: Bits;
 
@@ -142,6 +337,9 @@ pub trait Flags: Sized + 'static {
     }
 
     /// Get a flags value with all known bits set.
+    ///
+    /// This is the union of all defined flags. Flags with a value of zero don't set any bits,
+    /// so they don't affect the result. See [`Flags::all_nonzero`].
     fn all() -> Self {
         let mut truncated = Self::Bits::EMPTY;
 
@@ -152,11 +350,48 @@ pub trait Flags: Sized + 'static {
         Self::from_bits_retain(truncated)
     }
 
+    /// Get a flags value with the bits of all defined flags that aren't zero.
+    ///
+    /// This always returns the same value as [`Flags::all`], because flags with a value of zero
+    /// don't contribute any bits to it. It can be used to make it explicit that any zero-valued
+    /// flags, which are contained in every flags value, are ignored.
+    fn all_nonzero() -> Self {
+        let mut truncated = Self::Bits::EMPTY;
+
+        for flag in Self::FLAGS.iter() {
+            let bits = flag.value().bits();
+
+            if bits != Self::Bits::EMPTY {
+                truncated = truncated | bits;
+            }
+        }
+
+        Self::from_bits_retain(truncated)
+    }
+
+    /// Get the bits of all known flags.
+    ///
+    /// This is the same as `Self::all().bits()`, and can be used to mask unknown bits
+    /// out of a raw bits value, like `bits & F::all_bits()`.
+    fn all_bits() -> Self::Bits {
+        Self::all().bits()
+    }
+
     /// This method will return `true` if any unknown bits are set.
+    ///
+    /// Generic code can use this to reject values carrying bits that don't correspond
+    /// to any defined flag, like reserved bits read from a wire format.
     fn contains_unknown_bits(&self) -> bool {
         Self::all().bits() & self.bits() != self.bits()
     }
 
+    /// Get the number of bits in the underlying bits type.
+    ///
+    /// This is the same as [`Bits::BITS`], regardless of how many flags are defined.
+    fn bits_width() -> u32 {
+        Self::Bits::BITS
+    }
+
     /// Get the underlying bits value.
     ///
     /// The returned value is exactly the bits set in this flags value.
@@ -175,18 +410,52 @@ pub trait Flags: Sized + 'static {
         }
     }
 
+    /// Convert from a bits value, allowing unknown bits only if they're in `allowed_extra`.
+    ///
+    /// This method will return `None` if any bits are set that don't correspond to
+    /// defined flags and aren't in `allowed_extra`. Any allowed unknown bits are retained.
+    fn from_bits_with_mask(bits: Self::Bits, allowed_extra: Self::Bits) -> Option<Self> {
+        let allowed = Bits::bits_or(Self::all().bits(), allowed_extra);
+
+        if Bits::bits_and(bits, Bits::bits_not(allowed)) == Self::Bits::EMPTY {
+            Some(Self::from_bits_retain(bits))
+        } else {
+            None
+        }
+    }
+
+    /// Convert from a bits value, returning any unknown bits as an error.
+    ///
+    /// This method is like [`Flags::from_bits`], but the error carries exactly the bits
+    /// that don't correspond to any defined flag, so generic code like deserializers can
+    /// report them.
+    fn from_bits_checked(bits: Self::Bits) -> Result<Self, UnknownBits<Self::Bits>> {
+        let unknown = Bits::bits_and(bits, Bits::bits_not(Self::all().bits()));
+
+        if unknown == Self::Bits::EMPTY {
+            Ok(Self::from_bits_retain(bits))
+        } else {
+            Err(UnknownBits::new(unknown))
+        }
+    }
+
     /// Convert from a bits value, unsetting any unknown bits.
     fn from_bits_truncate(bits: Self::Bits) -> Self {
         Self::from_bits_retain(bits & Self::all().bits())
     }
 
     /// Convert from a bits value exactly.
+    ///
+    /// Any unknown bits are retained, so this method is the inverse of [`Flags::bits`].
+    /// Generic code can use them together to reconstruct a flags value without knowing its
+    /// concrete type, like `F::from_bits_retain(flags.bits())`.
     fn from_bits_retain(bits: Self::Bits) -> Self;
 
     /// Get a flags value with the bits of a flag with the given name set.
     ///
     /// This method will return `None` if `name` is empty or doesn't
-    /// correspond to any named flag.
+    /// correspond to any named flag. If [`Flags::RENAME_ALL`] transforms names then
+    /// both the original and transformed names are accepted.
     fn from_name(name: &str) -> Option<Self> {
         // Don't parse empty names as empty flags
         if name.is_empty() {
@@ -194,7 +463,7 @@ pub trait Flags: Sized + 'static {
         }
 
         for flag in Self::FLAGS {
-            if flag.name() == name {
+            if Self::RENAME_ALL.matches(flag.name(), name) {
                 return Some(Self::from_bits_retain(flag.value().bits()));
             }
         }
@@ -202,10 +471,35 @@ pub trait Flags: Sized + 'static {
         None
     }
 
+    /// Get a flags value with the bits of all flags with the given names set.
+    ///
+    /// Each name is resolved using [`Flags::from_name`], so names must match exactly and
+    /// can't be hex numbers. This is useful when names come from a source that's already
+    /// split, like command-line arguments, instead of a single string to parse.
+    ///
+    /// This method will fail on the first name that's empty or doesn't correspond to
+    /// any named flag. An empty set of names produces an empty flags value.
+    fn from_names<'a, I: IntoIterator<Item = &'a str>>(names: I) -> Result<Self, ParseError> {
+        let mut parsed = Self::empty();
+
+        for name in names {
+            if name.is_empty() {
+                return Err(ParseError::empty_flag());
+            }
+
+            let flag = Self::from_name(name).ok_or_else(|| ParseError::invalid_named_flag(name))?;
+            parsed.insert(flag);
+        }
+
+        Ok(parsed)
+    }
+
     /// Yield a set of contained flags values.
     ///
     /// Each yielded flags value will correspond to a defined named flag. Any unknown bits
     /// will be yielded together as a final flags value.
+    ///
+    /// Flags are yielded in the order they're declared.
     fn iter(&self) -> iter::Iter<Self> {
         iter::Iter::new(self)
     }
@@ -214,22 +508,76 @@ pub trait Flags: Sized + 'static {
     ///
     /// This method is like [`Flags::iter`], except only yields bits in contained named flags.
     /// Any unknown bits, or bits not corresponding to a contained flag will not be yielded.
+    ///
+    /// This method can be used to write the names of any flags value generically:
+    ///
+    /// ```
+    /// use bitflags::{bitflags, Flags};
+    ///
+    /// fn names<F: Flags>(flags: &F) -> Vec<&'static str> {
+    ///     flags.iter_names().map(|(name, _)| name).collect()
+    /// }
+    ///
+    /// bitflags! {
+    ///     struct MyFlags: u8 {
+    ///         const A = 1;
+    ///         const B = 1 << 1;
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(vec!["A", "B"], names(&MyFlags::all()));
+    /// ```
     fn iter_names(&self) -> iter::IterNames<Self> {
         iter::IterNames::new(self)
     }
 
+    /// Yield a set of contained named flags values, followed by any remaining bits.
+    ///
+    /// This method is like [`Flags::iter`], except named flags are yielded along with their names,
+    /// and any remaining bits are yielded as a final [`FlagOrBits::Unknown`](iter::FlagOrBits::Unknown).
+    fn iter_with_unknown(&self) -> iter::IterWithUnknown<Self> {
+        iter::IterWithUnknown::new(self)
+    }
+
     /// Whether all bits in this flags value are unset.
     fn is_empty(&self) -> bool {
         self.bits() == Self::Bits::EMPTY
     }
 
     /// Whether all known bits in this flags value are set.
+    ///
+    /// Zero-bit flags don't set any bits in [`Flags::all`], so they don't affect the result.
     fn is_all(&self) -> bool {
         // NOTE: We check against `Self::all` here, not `Self::Bits::ALL`
         // because the set of all flags may not use all bits
         Self::all().bits() | self.bits() == self.bits()
     }
 
+    /// Whether every bit of the underlying bits type is set in this flags value.
+    ///
+    /// This is different from [`Flags::is_all`], which only checks the bits of defined flags.
+    /// A value containing [`Flags::all`] is always `is_all`, but it's only `is_all_bits` if any
+    /// unknown bits are also set, like through [`Flags::from_bits_retain`]:
+    ///
+    /// ```
+    /// # use bitflags::{bitflags, Flags};
+    /// bitflags! {
+    ///     struct Permissions: u8 {
+    ///         const READ = 1;
+    ///         const WRITE = 1 << 1;
+    ///     }
+    /// }
+    ///
+    /// assert!(Flags::is_all(&Permissions::all()));
+    /// assert!(!Flags::is_all_bits(&Permissions::all()));
+    ///
+    /// assert!(Flags::is_all(&Permissions::from_bits_retain(u8::MAX)));
+    /// assert!(Flags::is_all_bits(&Permissions::from_bits_retain(u8::MAX)));
+    /// ```
+    fn is_all_bits(&self) -> bool {
+        self.bits() == Self::Bits::ALL
+    }
+
     /// Whether any set bits in a source flags value are also set in a target flags value.
     fn intersects(&self, other: Self) -> bool
     where
@@ -239,6 +587,10 @@ pub trait Flags: Sized + 'static {
     }
 
     /// Whether all set bits in a source flags value are also set in a target flags value.
+    ///
+    /// If `other` has multiple bits set, like a multi-bit flag, then every one of them
+    /// must be set for this method to return `true`. Use [`Flags::intersects`] to check
+    /// whether any of them are set.
     fn contains(&self, other: Self) -> bool
     where
         Self: Sized,
@@ -246,6 +598,67 @@ pub trait Flags: Sized + 'static {
         self.bits() & other.bits() == other.bits()
     }
 
+    /// Get the flags in a group that are set in a flags value.
+    ///
+    /// This method is the same as [`Flags::intersection`], but is named for checking which
+    /// flags from a group are present:
+    ///
+    /// ```
+    /// # use bitflags::{bitflags, Flags};
+    /// bitflags! {
+    ///     #[derive(Debug, PartialEq, Eq)]
+    ///     struct Permissions: u8 {
+    ///         const READ = 1;
+    ///         const WRITE = 1 << 1;
+    ///         const DELETE = 1 << 2;
+    ///         const SHARE = 1 << 3;
+    ///     }
+    /// }
+    ///
+    /// const MODIFY: Permissions = Permissions::WRITE.union(Permissions::DELETE);
+    ///
+    /// let granted = Permissions::READ | Permissions::DELETE;
+    ///
+    /// assert_eq!(Permissions::DELETE, Flags::present_of(&granted, MODIFY));
+    /// assert_eq!(Permissions::empty(), Flags::present_of(&granted, Permissions::SHARE));
+    /// ```
+    fn present_of(&self, group: Self) -> Self
+    where
+        Self: Sized,
+    {
+        Self::from_bits_retain(self.bits() & group.bits())
+    }
+
+    /// Whether any set bits in a source flags value are also set in a target flags value.
+    ///
+    /// This method is the same as [`Flags::intersects`], but takes `other` by reference,
+    /// so it can be used with flags types that don't implement `Copy`.
+    fn intersects_ref(&self, other: &Self) -> bool {
+        self.bits() & other.bits() != Self::Bits::EMPTY
+    }
+
+    /// Whether all set bits in a source flags value are also set in a target flags value.
+    ///
+    /// This method is the same as [`Flags::contains`], but takes `other` by reference,
+    /// so it can be used with flags types that don't implement `Copy`.
+    fn contains_ref(&self, other: &Self) -> bool {
+        self.bits() & other.bits() == other.bits()
+    }
+
+    /// Whether all bits in the flag with the given name are also set in a flags value.
+    ///
+    /// An empty name, or a name that doesn't correspond to any named flag, returns `false`
+    /// rather than an error. A flag whose value is zero is always contained.
+    fn contains_name(&self, name: &str) -> bool
+    where
+        Self: Sized,
+    {
+        match Self::from_name(name) {
+            Some(flag) => self.contains(flag),
+            None => false,
+        }
+    }
+
     /// Remove any unknown bits from the flags.
     fn truncate(&mut self)
     where
@@ -254,6 +667,43 @@ pub trait Flags: Sized + 'static {
         *self = Self::from_bits_truncate(self.bits());
     }
 
+    /// Remove any unknown bits from the flags, returning the number of bits removed.
+    ///
+    /// This method is the same as [`Flags::truncate`], but also counts the unknown bits
+    /// that were dropped. This is useful for reporting on reserved bits that were ignored.
+    /// Counting the bits requires a bits type that implements [`BitsCount`], like the primitive integers.
+    fn shrink_to_known(&mut self) -> u32
+    where
+        Self: Sized,
+        Self::Bits: BitsCount,
+    {
+        let unknown = Bits::bits_and(self.bits(), Bits::bits_not(Self::all().bits()));
+        self.truncate();
+
+        BitsCount::count_ones(unknown)
+    }
+
+    /// Remove any unknown bits from the flags.
+    ///
+    /// This method is the same as [`Flags::truncate`], and is the counterpart of [`Flags::keep_unknown`].
+    fn remove_unknown(&mut self)
+    where
+        Self: Sized,
+    {
+        self.truncate();
+    }
+
+    /// Remove any known bits from the flags, leaving only the unknown bits.
+    ///
+    /// This method is the counterpart of [`Flags::remove_unknown`]. It can be used to extract
+    /// any reserved bits from a flags value so they can be stored separately.
+    fn keep_unknown(&mut self)
+    where
+        Self: Sized,
+    {
+        *self = Self::from_bits_retain(self.bits() & !Self::all().bits());
+    }
+
     /// The bitwise or (`|`) of the bits in two flags values.
     fn insert(&mut self, other: Self)
     where
@@ -273,6 +723,24 @@ pub trait Flags: Sized + 'static {
         *self = Self::from_bits_retain(self.bits()).difference(other);
     }
 
+    /// The intersection of a source flags value with the complement of a target flags value (`&!`),
+    /// if all bits in `other` are set.
+    ///
+    /// This method will return an error, leaving this flags value unchanged, if `self` doesn't
+    /// contain `other`.
+    fn remove_exact(&mut self, other: Self) -> Result<(), NotPresent>
+    where
+        Self: Sized,
+    {
+        if self.bits() & other.bits() == other.bits() {
+            self.remove(other);
+
+            Ok(())
+        } else {
+            Err(NotPresent::new())
+        }
+    }
+
     /// The bitwise exclusive-or (`^`) of the bits in two flags values.
     fn toggle(&mut self, other: Self)
     where
@@ -281,6 +749,26 @@ pub trait Flags: Sized + 'static {
         *self = Self::from_bits_retain(self.bits()).symmetric_difference(other);
     }
 
+    /// The bitwise and (`&`) of the bits in two flags values.
+    ///
+    /// This method is the in-place form of [`Flags::intersection`].
+    fn intersect(&mut self, other: Self)
+    where
+        Self: Sized,
+    {
+        *self = Self::from_bits_retain(self.bits()).intersection(other);
+    }
+
+    /// The bitwise and (`&`) of the bits in two flags values.
+    ///
+    /// This method is the same as [`Flags::intersect`]. It only keeps bits that are also set in `other`.
+    fn retain_only(&mut self, other: Self)
+    where
+        Self: Sized,
+    {
+        self.intersect(other);
+    }
+
     /// Call [`Flags::insert`] when `value` is `true` or [`Flags::remove`] when `value` is `false`.
     fn set(&mut self, other: Self, value: bool)
     where
@@ -296,13 +784,13 @@ pub trait Flags: Sized + 'static {
     /// The bitwise and (`&`) of the bits in two flags values.
     #[must_use]
     fn intersection(self, other: Self) -> Self {
-        Self::from_bits_retain(self.bits() & other.bits())
+        Self::from_bits_retain(Bits::bits_and(self.bits(), other.bits()))
     }
 
     /// The bitwise or (`|`) of the bits in two flags values.
     #[must_use]
     fn union(self, other: Self) -> Self {
-        Self::from_bits_retain(self.bits() | other.bits())
+        Self::from_bits_retain(Bits::bits_or(self.bits(), other.bits()))
     }
 
     /// The intersection of a source flags value with the complement of a target flags value (`&!`).
@@ -311,24 +799,187 @@ pub trait Flags: Sized + 'static {
     /// `difference` won't truncate `other`, but the `!` operator will.
     #[must_use]
     fn difference(self, other: Self) -> Self {
-        Self::from_bits_retain(self.bits() & !other.bits())
+        Self::from_bits_retain(Bits::bits_and(self.bits(), Bits::bits_not(other.bits())))
     }
 
     /// The bitwise exclusive-or (`^`) of the bits in two flags values.
     #[must_use]
     fn symmetric_difference(self, other: Self) -> Self {
-        Self::from_bits_retain(self.bits() ^ other.bits())
+        Self::from_bits_retain(Bits::bits_xor(self.bits(), other.bits()))
+    }
+
+    /// The bits that differ between two flags values.
+    ///
+    /// This is the same as [`Flags::symmetric_difference`], but takes both flags values by
+    /// reference, so it can be used to diff two states of a flags type that doesn't implement
+    /// `Copy`. Any unknown bits that differ are retained.
+    #[must_use]
+    fn changed(&self, other: &Self) -> Self {
+        Self::from_bits_retain(Bits::bits_xor(self.bits(), other.bits()))
     }
 
     /// The bitwise negation (`!`) of the bits in a flags value, truncating the result.
+    ///
+    /// This is the same as the inherent `complement` method on generated flags types, so any
+    /// unknown bits are unset. Generic code can use it to get all the other defined flags,
+    /// since `!` isn't available through the `Flags` trait.
     #[must_use]
     fn complement(self) -> Self {
-        Self::from_bits_truncate(!self.bits())
+        Self::from_bits_truncate(Bits::bits_not(self.bits()))
+    }
+
+    /// The bitwise or (`|`) of the bits in a flags value and a flags value of another type
+    /// with the same bits type.
+    ///
+    /// The result is a raw bits value rather than a flags value, because the bits may not
+    /// correspond to defined flags in either type. It can be converted back into either type
+    /// using its `from_bits_*` methods.
+    #[must_use]
+    fn combine_bits<Other: Flags<Bits = Self::Bits>>(&self, other: &Other) -> Self::Bits {
+        self.bits() | other.bits()
+    }
+
+    /// Get a `clap` value parser for the flags type.
+    ///
+    /// This method is the same as [`clap::value_parser`](crate::clap::value_parser), so a flags
+    /// type can be used as an argument like `#[arg(value_parser = MyFlags::value_parser())]`.
+    #[cfg(feature = "clap")]
+    fn value_parser() -> crate::clap::FlagsValueParser<Self>
+    where
+        Self: Clone + Send + Sync,
+        Self::Bits: ParseHex,
+    {
+        crate::clap::value_parser()
+    }
+
+    /// Format the flags value as text.
+    ///
+    /// This method produces the same output as [`parser::to_writer`](crate::parser::to_writer)
+    /// without needing a `Display` implementation. It's named differently from
+    /// [`ToString::to_string`] so it doesn't conflict with a `Display` implementation on the
+    /// flags type.
+    #[cfg(feature = "std")]
+    fn to_text(&self) -> String
+    where
+        Self::Bits: WriteHex,
+    {
+        let mut s = String::new();
+
+        // Writing to a `String` never fails
+        let _ = crate::parser::to_writer(self, &mut s);
+        s
+    }
+
+    /// Format the flags value as text, ignoring any unknown bits.
+    ///
+    /// This method produces the same output as
+    /// [`parser::to_writer_truncate`](crate::parser::to_writer_truncate).
+    #[cfg(feature = "std")]
+    fn to_text_truncate(&self) -> String
+    where
+        Self::Bits: WriteHex,
+    {
+        let mut s = String::new();
+
+        // Writing to a `String` never fails
+        let _ = crate::parser::to_writer_truncate(self, &mut s);
+        s
+    }
+
+    /// Format the names of contained flags, separated by ` | `.
+    ///
+    /// This is the first part of the text format written by
+    /// [`parser::to_writer`](crate::parser::to_writer), without any unknown bits.
+    /// Nothing is written if no named flags are contained. This method is useful for
+    /// implementing a custom `Display` that reuses the crate's formatting:
+    ///
+    /// ```
+    /// use std::fmt;
+    ///
+    /// use bitflags::Flags;
+    ///
+    /// bitflags::bitflags! {
+    ///     struct Permissions: u8 {
+    ///         const READ = 1;
+    ///         const WRITE = 1 << 1;
+    ///     }
+    /// }
+    ///
+    /// impl fmt::Display for Permissions {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         // Write names followed by all the bits, like `READ | WRITE (0x3)`
+    ///         self.fmt_names(f)?;
+    ///         f.write_str(" (")?;
+    ///         self.fmt_bits(f)?;
+    ///         f.write_str(")")
+    ///     }
+    /// }
+    ///
+    /// assert_eq!("READ | WRITE (0x3)", format!("{}", Permissions::all()));
+    /// assert_eq!("READ (0x81)", format!("{}", Permissions::from_bits_retain(0x81)));
+    /// ```
+    fn fmt_names(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, _)) in self.iter_names().enumerate() {
+            if i > 0 {
+                f.write_str(" | ")?;
+            }
+
+            Self::RENAME_ALL.write_name(name, &mut *f)?;
+        }
+
+        Ok(())
+    }
+
+    /// Format all the bits in the flags value as a hex number, like `0x3`.
+    ///
+    /// Unlike [`parser::to_writer`](crate::parser::to_writer), known and unknown bits are
+    /// written together as a single number. See [`Flags::fmt_names`] for an example.
+    fn fmt_bits(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    where
+        Self::Bits: WriteHex,
+    {
+        f.write_str("0x")?;
+        self.bits().write_hex(f)
+    }
+
+    /// Parse a flags value from text.
+    ///
+    /// This method is the same as [`parser::from_str`](crate::parser::from_str). It will fail
+    /// on any names that don't correspond to defined flags, and any unknown bits given as
+    /// hex numbers are retained.
+    fn parse(input: &str) -> Result<Self, ParseError>
+    where
+        Self::Bits: ParseHex,
+    {
+        crate::parser::from_str(input)
+    }
+
+    /// Parse a flags value from text, ignoring any unknown bits.
+    ///
+    /// This method is the same as
+    /// [`parser::from_str_truncate`](crate::parser::from_str_truncate). Names are parsed
+    /// the same way as [`Flags::parse`], so unrecognized names are still an error, but any
+    /// unknown bits given as hex numbers are dropped from the result.
+    fn parse_truncate(input: &str) -> Result<Self, ParseError>
+    where
+        Self::Bits: ParseHex,
+    {
+        crate::parser::from_str_truncate(input)
     }
 }
 
 /**
 A bits type that can be used as storage for a flags type.
+
+Bits types are always `Copy`. Iterating and formatting flags values copies their bits
+instead of borrowing them, and generic code over [`Flags`] can rely on that bound.
+Bits types wider than the primitive integers can still be built from arrays, which are
+`Copy` as long as their elements are.
+
+Bits types aren't required to implement `Hash`, but flags types are hashed by hashing their
+bits value, so a bits type should implement `Hash` and `Eq` if flags types using it need them.
+A custom bits type, like one backed by an array, must hash every part of its value consistently
+with its `PartialEq` implementation. Deriving `Hash` and `Eq` alongside `PartialEq` does this.
 */
 pub trait Bits:
     Clone
@@ -346,34 +997,287 @@ pub trait Bits:
 
     /// A value with all bits set.
     const ALL: Self;
+
+    /// The number of bits in a value.
+    ///
+    /// For primitive integers, this is the same as their `BITS` constant.
+    ///
+    /// The default is the size of the type in bits, which is only correct if every bit of the
+    /// type's memory is a bit of its value. Custom bits types should override it with their
+    /// actual number of bits. A type backed by `[bool; 3]` has 3 bits, not 24. Formatting
+    /// with [`WriteOptions::pad_hex`](crate::parser::WriteOptions::pad_hex) pads hex numbers
+    /// to this width.
+    const BITS: u32 = (core::mem::size_of::<Self>() * 8) as u32;
+
+    /// Whether the bits type is a signed integer.
+    ///
+    /// Signed bits types can be parsed from negative decimal numbers, like `-1`.
+    /// The default is `false`.
+    const SIGNED: bool = false;
+
+    /// The bitwise and (`&`) of two bits values.
+    ///
+    /// These methods can't be `const`, because trait methods can't be `const` on stable Rust.
+    /// Generated flags types use the operators on their bits type directly in `const` methods.
+    /// They're prefixed with `bits_` so they don't collide with the methods of the operator
+    /// traits, like `Not::not`.
+    #[inline]
+    #[must_use]
+    fn bits_and(self, other: Self) -> Self {
+        self & other
+    }
+
+    /// The bitwise or (`|`) of two bits values.
+    #[inline]
+    #[must_use]
+    fn bits_or(self, other: Self) -> Self {
+        self | other
+    }
+
+    /// The bitwise exclusive-or (`^`) of two bits values.
+    #[inline]
+    #[must_use]
+    fn bits_xor(self, other: Self) -> Self {
+        self ^ other
+    }
+
+    /// The bitwise negation (`!`) of a bits value.
+    #[inline]
+    #[must_use]
+    fn bits_not(self) -> Self {
+        !self
+    }
+}
+
+/**
+A bits type that can count and scan for its set bits.
+
+This trait is implemented for all primitive integer bits types. It's useful for writing generic
+bit-scanning algorithms, like finding the positions of the lowest and highest set bits:
+
+```
+use bitflags::{Bits, BitsCount};
+
+fn lowest_highest<B: BitsCount>(bits: B) -> Option<(u32, u32)> {
+    if bits == B::EMPTY {
+        None
+    } else {
+        Some((bits.trailing_zeros(), B::BITS - 1 - bits.leading_zeros()))
+    }
+}
+
+assert_eq!(Some((1, 4)), lowest_highest(0b0001_1010u8));
+assert_eq!(None, lowest_highest(0u32));
+```
+
+This trait is sealed, so it can't be implemented for custom bits types. This keeps [`Bits`]
+implementable with just its constants and operators.
+*/
+pub trait BitsCount: Bits + Primitive {
+    /// The number of set bits in a bits value.
+    ///
+    /// For primitive integers, this is the same as their inherent `count_ones` method.
+    #[must_use]
+    fn count_ones(self) -> u32;
+
+    /// The number of leading zeros in a bits value, starting from its highest bit.
+    ///
+    /// For primitive integers, this is the same as their inherent `leading_zeros` method.
+    /// An empty bits value has [`Bits::BITS`] leading zeros.
+    #[must_use]
+    fn leading_zeros(self) -> u32;
+
+    /// The number of trailing zeros in a bits value, starting from its lowest bit.
+    ///
+    /// For primitive integers, this is the same as their inherent `trailing_zeros` method.
+    /// An empty bits value has [`Bits::BITS`] trailing zeros.
+    #[must_use]
+    fn trailing_zeros(self) -> u32;
+}
+
+/**
+A bits type that can be converted to and from its raw byte representation.
+
+This trait is implemented for all primitive integer bits types. It's useful for writing binary
+codecs that work with any flags type:
+
+```
+use bitflags::{BitsBytes, Flags};
+
+fn encode<F: Flags>(flags: &F, buf: &mut Vec<u8>)
+where
+    F::Bits: BitsBytes,
+{
+    buf.extend_from_slice(flags.bits().to_le_bytes().as_ref());
+}
+
+bitflags::bitflags! {
+    struct MyFlags: u16 {
+        const A = 1;
+        const B = 1 << 8;
+    }
+}
+
+let mut buf = Vec::new();
+encode(&(MyFlags::A | MyFlags::B), &mut buf);
+
+assert_eq!(vec![1, 1], buf);
+```
+*/
+pub trait BitsBytes {
+    /// The raw byte representation of the bits type.
+    ///
+    /// For primitive integers, this is an array with the same size as the integer.
+    type Bytes: AsRef<[u8]>;
+
+    /// Get the bytes of the value in little-endian order.
+    fn to_le_bytes(&self) -> Self::Bytes;
+
+    /// Get the bytes of the value in big-endian (network) order.
+    fn to_be_bytes(&self) -> Self::Bytes;
+
+    /// Convert from bytes in little-endian order.
+    fn from_le_bytes(bytes: Self::Bytes) -> Self;
+
+    /// Convert from bytes in big-endian (network) order.
+    fn from_be_bytes(bytes: Self::Bytes) -> Self;
 }
 
+/**
+A flags type that has the same ABI as its underlying bits type.
+
+Values of a transparent flags type have the same size, alignment, and ABI as [`Flags::Bits`],
+and any bits value is a valid value of the flags type. This makes it possible for generic code
+to require transparency as a bound, such as for FFI or zero-copy conversions.
+
+## Implementing `TransparentFlags`
+
+This trait is implemented by the [`bitflags`](macro.bitflags.html) macro when the generated
+flags type has a `#[repr(transparent)]` attribute:
+
+```
+use bitflags::{bitflags, TransparentFlags};
+
+bitflags! {
+    #[repr(transparent)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct MyFlags: u8 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+}
+
+fn as_bits_slice<F: TransparentFlags>(flags: &[F]) -> &[F::Bits] {
+    // SAFETY: `F` has the same ABI as `F::Bits`
+    unsafe { core::slice::from_raw_parts(flags.as_ptr().cast(), flags.len()) }
+}
+
+assert_eq!(&[1, 3], as_bits_slice(&[MyFlags::A, MyFlags::A | MyFlags::B]));
+```
+
+# Safety
+
+Implementors must guarantee that the flags type has the same size, alignment, and ABI as
+[`Flags::Bits`], such as by being `#[repr(transparent)]` over it, and that every value of
+`Flags::Bits` is a valid value of the flags type. Generic code can rely on this to reinterpret
+flags values as bits values and back in `unsafe` code, like the example above.
+
+The implementations generated by the `bitflags` macro uphold this, so manual implementations
+are only needed for flags types defined without it.
+*/
+// Declaring the trait `unsafe` doesn't need any unsafe code in this crate
+#[allow(unsafe_code)]
+pub unsafe trait TransparentFlags: Flags {}
+
 // Not re-exported: prevent custom `Bits` impls being used in the `bitflags!` macro,
 // or they may fail to compile based on crate features
 pub trait Primitive {}
 
+// Report integers that don't fit in the bits type as overflowing, instead of invalid
+fn int_error(
+    err: core::num::ParseIntError,
+    input: &str,
+    invalid: impl FnOnce(&str) -> ParseError,
+) -> ParseError {
+    match err.kind() {
+        core::num::IntErrorKind::PosOverflow | core::num::IntErrorKind::NegOverflow => {
+            ParseError::overflowing_flag(input)
+        }
+        _ => invalid(input),
+    }
+}
+
 macro_rules! impl_bits {
     ($($u:ty, $i:ty,)*) => {
         $(
             impl Bits for $u {
                 const EMPTY: $u = 0;
                 const ALL: $u = <$u>::MAX;
+                const BITS: u32 = <$u>::BITS;
             }
 
             impl Bits for $i {
                 const EMPTY: $i = 0;
                 const ALL: $i = <$u>::MAX as $i;
+                const BITS: u32 = <$i>::BITS;
+                const SIGNED: bool = true;
+            }
+
+            impl BitsCount for $u {
+                #[inline]
+                fn count_ones(self) -> u32 {
+                    <$u>::count_ones(self)
+                }
+
+                #[inline]
+                fn leading_zeros(self) -> u32 {
+                    <$u>::leading_zeros(self)
+                }
+
+                #[inline]
+                fn trailing_zeros(self) -> u32 {
+                    <$u>::trailing_zeros(self)
+                }
+            }
+
+            impl BitsCount for $i {
+                #[inline]
+                fn count_ones(self) -> u32 {
+                    <$i>::count_ones(self)
+                }
+
+                #[inline]
+                fn leading_zeros(self) -> u32 {
+                    <$i>::leading_zeros(self)
+                }
+
+                #[inline]
+                fn trailing_zeros(self) -> u32 {
+                    <$i>::trailing_zeros(self)
+                }
             }
 
             impl ParseHex for $u {
                 fn parse_hex(input: &str) -> Result<Self, ParseError> {
-                    <$u>::from_str_radix(input, 16).map_err(|_| ParseError::invalid_hex_flag(input))
+                    <$u>::from_str_radix(input, 16).map_err(|e| int_error(e, input, |input| ParseError::invalid_hex_flag(input)))
                 }
             }
 
             impl ParseHex for $i {
                 fn parse_hex(input: &str) -> Result<Self, ParseError> {
-                    <$i>::from_str_radix(input, 16).map_err(|_| ParseError::invalid_hex_flag(input))
+                    // Hex flags are written as the two's complement bits of the value, so
+                    // they're parsed as unsigned and reinterpreted, like `0x80` for `i8::MIN`
+                    <$u>::from_str_radix(input, 16)
+                        .map(|bits| bits as $i)
+                        .map_err(|e| int_error(e, input, |input| ParseError::invalid_hex_flag(input)))
+                }
+
+                fn parse_negative(input: &str) -> Result<Self, ParseError> {
+                    if !input.starts_with('-') {
+                        return Err(ParseError::invalid_negative_flag(input));
+                    }
+
+                    input.parse::<$i>().map_err(|e| int_error(e, input, |input| ParseError::invalid_negative_flag(input)))
                 }
             }
 
@@ -389,6 +1293,46 @@ macro_rules! impl_bits {
                 }
             }
 
+            impl BitsBytes for $u {
+                type Bytes = [u8; core::mem::size_of::<$u>()];
+
+                fn to_le_bytes(&self) -> Self::Bytes {
+                    <$u>::to_le_bytes(*self)
+                }
+
+                fn to_be_bytes(&self) -> Self::Bytes {
+                    <$u>::to_be_bytes(*self)
+                }
+
+                fn from_le_bytes(bytes: Self::Bytes) -> Self {
+                    <$u>::from_le_bytes(bytes)
+                }
+
+                fn from_be_bytes(bytes: Self::Bytes) -> Self {
+                    <$u>::from_be_bytes(bytes)
+                }
+            }
+
+            impl BitsBytes for $i {
+                type Bytes = [u8; core::mem::size_of::<$i>()];
+
+                fn to_le_bytes(&self) -> Self::Bytes {
+                    <$i>::to_le_bytes(*self)
+                }
+
+                fn to_be_bytes(&self) -> Self::Bytes {
+                    <$i>::to_be_bytes(*self)
+                }
+
+                fn from_le_bytes(bytes: Self::Bytes) -> Self {
+                    <$i>::from_le_bytes(bytes)
+                }
+
+                fn from_be_bytes(bytes: Self::Bytes) -> Self {
+                    <$i>::from_be_bytes(bytes)
+                }
+            }
+
             impl Primitive for $i {}
             impl Primitive for $u {}
         )*
@@ -439,6 +1383,18 @@ impl<B: Flags> ImplementedByBitFlagsMacro for B {}
 #[doc(hidden)]
 pub trait ImplementedByBitFlagsMacro {}
 
+/// Writes a flag name with a `rename_all` transform applied.
+///
+/// This is used by the `debug = "set"` option.
+#[doc(hidden)]
+pub struct FlagNameWithRenameAll<'a>(pub RenameAll, pub &'a str);
+
+impl<'a> fmt::Display for FlagNameWithRenameAll<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.write_name(self.1, f)
+    }
+}
+
 pub(crate) mod __private {
-    pub use super::{ImplementedByBitFlagsMacro, PublicFlags};
+    pub use super::{FlagNameWithRenameAll, ImplementedByBitFlagsMacro, PublicFlags};
 }
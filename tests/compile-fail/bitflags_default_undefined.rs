@@ -0,0 +1,18 @@
+use bitflags::bitflags;
+
+bitflags! {
+    #[bitflags(default = A | C)]
+    pub struct Flags1: u32 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+}
+
+bitflags! {
+    #[bitflags(default = "A")]
+    pub struct Flags2: u32 {
+        const A = 1;
+    }
+}
+
+fn main() {}
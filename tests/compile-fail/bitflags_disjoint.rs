@@ -0,0 +1,16 @@
+use bitflags::bitflags;
+
+pub struct Flags(u32);
+
+bitflags! {
+    #[bitflags(disjoint)]
+    impl Flags: u32 {
+        const A = 1;
+        const B = 1 << 1;
+        const C = 1 << 2;
+        // Shares a bit with `B`
+        const D = 1 << 1 | 1 << 3;
+    }
+}
+
+fn main() {}
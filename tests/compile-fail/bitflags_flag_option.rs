@@ -0,0 +1,18 @@
+use bitflags::bitflags;
+
+bitflags! {
+    struct Flags: u8 {
+        #[bitflags(unknown)]
+        const A = 1;
+    }
+}
+
+bitflags! {
+    struct FlagsGroup: u8 {
+        #[bitflags(group = "a")]
+        #[bitflags(group = "b")]
+        const A = 1;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+use bitflags::bitflags;
+
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[bitflags(minimal)]
+    pub struct Flags: u8 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+}
+
+fn main() {
+    let flags = Flags::A | Flags::B;
+
+    // Iteration isn't generated
+    let _ = flags.iter();
+    for _ in flags {}
+
+    // Neither is the text format
+    let _ = format!("{}", flags.0);
+    let _: Result<Flags, _> = "A | B".parse();
+}
@@ -0,0 +1,22 @@
+#![deny(unused_must_use)]
+
+use bitflags::bitflags;
+
+bitflags! {
+    pub struct Flags: u32 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+}
+
+fn main() {
+    let flags = Flags::A;
+
+    flags.contains(Flags::B);
+    flags.intersects(Flags::B);
+    flags.is_empty();
+    flags.bits();
+    flags.union(Flags::B);
+
+    Flags::from_bits_retain(1);
+}
@@ -0,0 +1,14 @@
+use bitflags::{bitflags, TransparentFlags};
+
+bitflags! {
+    #[derive(Clone, Copy)]
+    struct Flags: u8 {
+        const A = 1;
+    }
+}
+
+fn assert_transparent<F: TransparentFlags>() {}
+
+fn main() {
+    assert_transparent::<Flags>();
+}
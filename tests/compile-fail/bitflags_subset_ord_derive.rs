@@ -0,0 +1,27 @@
+use bitflags::bitflags;
+
+bitflags! {
+    #[derive(PartialEq, Eq, PartialOrd)]
+    #[bitflags(subset_ord)]
+    pub struct Flags1: u32 {
+        const A = 1;
+    }
+}
+
+bitflags! {
+    #[bitflags(subset_ord)]
+    #[derive(PartialEq, Eq, Ord)]
+    pub struct Flags2: u32 {
+        const A = 1;
+    }
+}
+
+bitflags! {
+    #[bitflags(subset_ord = true)]
+    #[derive(PartialEq, Eq)]
+    pub struct Flags3: u32 {
+        const A = 1;
+    }
+}
+
+fn main() {}
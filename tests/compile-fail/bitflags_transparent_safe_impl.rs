@@ -0,0 +1,14 @@
+use bitflags::{bitflags, TransparentFlags};
+
+pub struct Flags(u8);
+
+bitflags! {
+    impl Flags: u8 {
+        const A = 1;
+    }
+}
+
+// `TransparentFlags` is an `unsafe` trait
+impl TransparentFlags for Flags {}
+
+fn main() {}
@@ -0,0 +1,58 @@
+use bitflags::bitflags;
+
+bitflags! {
+    #[bitflags(not_an_option)]
+    pub struct Flags1: u32 {
+        const A = 1;
+    }
+}
+
+bitflags! {
+    #[bitflags(debug = "list")]
+    pub struct Flags2: u32 {
+        const A = 1;
+    }
+}
+
+pub struct Flags3(u32);
+
+bitflags! {
+    #[bitflags(debug = "set")]
+    impl Flags3: u32 {
+        const A = 1;
+    }
+}
+
+pub struct Flags4(u32);
+
+bitflags! {
+    #[bitflags(transparent)]
+    impl Flags4: u32 {
+        const A = 1;
+    }
+}
+
+bitflags! {
+    #[bitflags(transparent = true)]
+    pub struct Flags5: u32 {
+        const A = 1;
+    }
+}
+
+pub struct Flags6(u32);
+
+bitflags! {
+    #[bitflags(kind = Flags6Kind)]
+    impl Flags6: u32 {
+        const A = 1;
+    }
+}
+
+bitflags! {
+    #[bitflags(kind)]
+    pub struct Flags7: u32 {
+        const A = 1;
+    }
+}
+
+fn main() {}
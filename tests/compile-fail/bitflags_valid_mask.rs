@@ -0,0 +1,13 @@
+use bitflags::bitflags;
+
+bitflags! {
+    #[bitflags(valid_mask = 0xFF)]
+    pub struct Flags: u32 {
+        const A = 1;
+        const B = 1 << 1;
+        // Outside of the valid mask
+        const C = 1 << 8;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use bitflags::bits_range;
+
+// Bit 32 is past the width of `u32`
+const MASK: u32 = bits_range!(0..33);
+
+fn main() {
+    let _ = MASK;
+}
@@ -0,0 +1,8 @@
+use bitflags::bits_range;
+
+// The start of the range is after its end
+const MASK: u32 = bits_range!(8..4);
+
+fn main() {
+    let _ = MASK;
+}
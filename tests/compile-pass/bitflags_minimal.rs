@@ -0,0 +1,35 @@
+#![no_implicit_prelude]
+
+extern crate bitflags;
+extern crate std;
+
+use std::{assert_eq, format};
+
+bitflags::bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[bitflags(minimal)]
+    pub struct Flags: u32 {
+        const A = 0b00000001;
+        const B = 0b00000010;
+        const AB = Flags::A.union(Flags::B).bits();
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlagsImpl(u32);
+
+bitflags::bitflags! {
+    #[bitflags(minimal)]
+    impl FlagsImpl: u32 {
+        const A = 0b00000001;
+    }
+}
+
+fn main() {
+    assert_eq!(Flags::AB, Flags::A | Flags::B);
+    assert_eq!("Flags(0x3)", format!("{:?}", Flags::AB));
+    assert_eq!(FlagsImpl::A, FlagsImpl::from_bits_truncate(0b11));
+
+    // `from_name` is still generated
+    assert_eq!(std::option::Option::Some(Flags::A), Flags::from_name("A"));
+}
@@ -0,0 +1,25 @@
+use bitflags::bitflags;
+
+bitflags! {
+    /// Docs on the flags type.
+    #[derive(Debug)]
+    #[bitflags(debug = "set",)]
+    #[repr(transparent)]
+    pub struct Flags: u32 {
+        const A = 1;
+    }
+}
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq)]
+    #[bitflags(debug = "set", default = A | B)]
+    pub struct FlagsDefault: u8 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+}
+
+fn main() {
+    assert_eq!("Flags({A})", format!("{:?}", Flags::A));
+    assert_eq!("FlagsDefault({A, B})", format!("{:?}", FlagsDefault::default()));
+}
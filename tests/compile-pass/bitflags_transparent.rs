@@ -0,0 +1,20 @@
+use bitflags::{bitflags, TransparentFlags};
+
+bitflags! {
+    /// Docs on the flags type.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[bitflags(transparent)]
+    pub struct Flags: u64 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+}
+
+fn assert_transparent<F: TransparentFlags>() {}
+
+fn main() {
+    assert_transparent::<Flags>();
+
+    let bits: u64 = unsafe { std::mem::transmute(Flags::A | Flags::B) };
+    assert_eq!(0b11, bits);
+}
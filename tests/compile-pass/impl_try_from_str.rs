@@ -0,0 +1,25 @@
+use std::convert::TryFrom;
+
+use bitflags::bitflags;
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq)]
+    struct Flags: u8 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Flags {
+    type Error = bitflags::parser::ParseError;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        Ok(Flags(TryFrom::try_from(s)?))
+    }
+}
+
+fn main() {
+    assert_eq!(Flags::A | Flags::B, Flags::try_from("A | B").unwrap());
+    assert_eq!(Flags::from_bits_retain(0x4), Flags::try_from("0x4").unwrap());
+    assert!(Flags::try_from("C").is_err());
+}
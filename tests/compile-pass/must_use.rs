@@ -0,0 +1,23 @@
+#![deny(unused_must_use)]
+
+use bitflags::bitflags;
+
+bitflags! {
+    pub struct Flags: u32 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+}
+
+fn main() {
+    let mut flags = Flags::A;
+
+    flags.insert(Flags::B);
+    flags.remove(Flags::B);
+    flags.toggle(Flags::B);
+    flags.set(Flags::B, true);
+    flags.intersect(Flags::A);
+
+    let _ = flags.contains(Flags::B);
+    assert!(flags.intersects(Flags::A));
+}
@@ -0,0 +1,42 @@
+#![no_std]
+
+use bitflags::prelude::*;
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct Flags1: u8 {
+        const A = 1;
+        const B = 1 << 1;
+        const C = 1 << 2;
+    }
+}
+
+// Generic code only needs the prelude to use the `Flags` trait
+fn count<F: Flags>(flags: &F) -> usize {
+    flags.iter().count()
+}
+
+fn roundtrip<F: Flags>(flags: &F) -> F {
+    F::from_bits_retain(flags.bits())
+}
+
+fn first_name<F: Flags>() -> &'static str {
+    let flag: &Flag<F> = &F::FLAGS[0];
+
+    flag.name()
+}
+
+fn is_empty_bits<B: Bits>(bits: B) -> bool {
+    bits == B::EMPTY
+}
+
+#[test]
+fn prelude() {
+    assert_eq!(2, count(&(Flags1::A | Flags1::C)));
+    assert_eq!(
+        Flags1::A | Flags1::from_bits_retain(1 << 7),
+        roundtrip(&(Flags1::A | Flags1::from_bits_retain(1 << 7)))
+    );
+    assert_eq!("A", first_name::<Flags1>());
+    assert!(is_empty_bits(Flags1::empty().bits()));
+}